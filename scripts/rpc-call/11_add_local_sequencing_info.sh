@@ -0,0 +1,23 @@
+#!/bin/bash
+SCRIPT_PATH="$( cd -- "$(dirname "$0")" >/dev/null 2>&1 ; pwd -P )"
+source $SCRIPT_PATH/env.sh
+
+echo "add_sequencing_info (local liveness)"
+
+curl --location $SEQUENCER_INTERNAL_RPC_URL \
+--header 'Content-Type: application/json' \
+--data '{
+  "jsonrpc": "2.0",
+  "method": "add_sequencing_info",
+  "params": {
+    "platform": "local",
+    "service_provider": "'"$LIVENESS_SERVICE_PROVIDER"'",
+
+    "payload": {
+      "cluster_file_path": "'"$LOCAL_CLUSTER_FILE_PATH"'"
+    }
+  },
+  "id": 1
+}'
+echo ""
+echo "add_sequencing_info done"
@@ -8,6 +8,10 @@ LIVENESS_RPC_URL=""
 LIVENESS_WS_URL=""
 LIVENESS_CONTRACT_ADDRESS=""
 CLUSTER_ID=""
+
+### For local (no liveness contract)
+# LIVENESS_PLATFORM="local"
+# LOCAL_CLUSTER_FILE_PATH="" # JSON list of clusters (sequencers, rollups and executors)
 #####################################################################################
 
 
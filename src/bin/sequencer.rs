@@ -1,7 +1,12 @@
+use std::path::{Path, PathBuf};
+
 use clap::{Parser, Subcommand};
 use futures::future::try_join_all;
 use radius_sdk::{
-    json_rpc::{client::RpcClient, server::RpcServer},
+    json_rpc::{
+        client::{Id, RpcClient},
+        server::{RpcParameter, RpcServer},
+    },
     kvstore::{CachedKvStore, KvStoreBuilder},
     util::{get_resource_limit, set_resource_limit, ResourceType},
 };
@@ -15,8 +20,18 @@ use sequencer::{
     error::{self, Error},
     logger::PanicLog,
     merkle_tree_manager::MerkleTreeManager,
-    rpc::{cluster, external, internal},
+    rpc::{
+        cluster,
+        ethereum::EthereumServer,
+        external, internal,
+        subscription::{self, SubscriptionServer},
+    },
     state::AppState,
+    subscription_manager::SubscriptionManager,
+    task::{
+        backfill_transaction, monitor_leader, resume_block_build_jobs, track_skde_key,
+        watch_order_commitment,
+    },
     types::*,
     util::initialize_logger,
 };
@@ -47,6 +62,17 @@ enum Commands {
         #[clap(flatten)]
         config_option: ConfigOption,
     },
+    /// Exports the equivocation evidence recorded by a node
+    ExportEvidence {
+        #[clap(long = "external-rpc-url", default_value = "http://127.0.0.1:3000")]
+        external_rpc_url: String,
+
+        #[clap(long = "rollup-id")]
+        rollup_id: String,
+
+        #[clap(long = "output-path", default_value = ".")]
+        output_path: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -81,6 +107,65 @@ async fn main() -> Result<(), Error> {
         Commands::Start { mut config_option } => {
             start_sequencer(&mut config_option).await?;
         }
+        Commands::ExportEvidence {
+            external_rpc_url,
+            rollup_id,
+            output_path,
+        } => {
+            tracing_subscriber::fmt().init();
+            export_evidence(&external_rpc_url, &rollup_id, &output_path).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes each evidence to its own JSON file so that it can be submitted
+/// on its own.
+async fn export_evidence(
+    external_rpc_url: &str,
+    rollup_id: &str,
+    output_path: &Path,
+) -> Result<(), Error> {
+    let rpc_client = RpcClient::new().map_err(error::Error::RpcClient)?;
+
+    let parameter = external::GetEquivocationEvidenceList {
+        rollup_id: rollup_id.to_owned(),
+    };
+    let response: external::GetEquivocationEvidenceListResponse = rpc_client
+        .request(
+            external_rpc_url,
+            external::GetEquivocationEvidenceList::method(),
+            &parameter,
+            Id::Null,
+        )
+        .await?;
+
+    std::fs::create_dir_all(output_path)?;
+    for (rollup_block_height, transaction_order) in response.equivocation_evidence_list {
+        let parameter = external::GetEquivocationEvidence {
+            rollup_id: rollup_id.to_owned(),
+            rollup_block_height,
+            transaction_order,
+        };
+        let equivocation_evidence: EquivocationEvidence = rpc_client
+            .request(
+                external_rpc_url,
+                external::GetEquivocationEvidence::method(),
+                &parameter,
+                Id::Null,
+            )
+            .await?;
+
+        let file_path = output_path.join(format!(
+            "{}_{}_{}.json",
+            rollup_id, rollup_block_height, transaction_order
+        ));
+        let evidence_string = serde_json::to_string_pretty(&equivocation_evidence)
+            .map_err(|error| error::Error::Internal(error.into()))?;
+        std::fs::write(&file_path, evidence_string)?;
+
+        tracing::info!("Exported equivocation evidence to {:?}", file_path);
     }
 
     Ok(())
@@ -131,7 +216,7 @@ async fn start_sequencer(config_option: &mut ConfigOption) -> Result<(), Error>
         .skde_params;
 
     let rpc_client = RpcClient::new().map_err(error::Error::RpcClient)?;
-    let merkle_tree_manager = MerkleTreeManager::init(&rpc_client).await;
+    let merkle_tree_manager = MerkleTreeManager::init().await;
     let app_state: AppState = AppState::new(
         config,
         seeder_client,
@@ -143,15 +228,34 @@ async fn start_sequencer(config_option: &mut ConfigOption) -> Result<(), Error>
         profiler,
         rpc_client,
         merkle_tree_manager,
+        SubscriptionManager::default(),
     );
 
     initialize_clients(app_state.clone()).await?;
+    resume_block_build_jobs(app_state.clone())?;
+    monitor_leader(app_state.clone());
+    backfill_transaction(app_state.clone());
+    track_skde_key(app_state.clone());
+    if app_state.config().is_watcher {
+        watch_order_commitment(app_state.clone());
+    }
 
     let internal_handle = tokio::spawn(initialize_internal_rpc_server(app_state.clone()));
     let cluster_handle = tokio::spawn(initialize_cluster_rpc_server(app_state.clone()));
     let external_handle = tokio::spawn(initialize_external_rpc_server(app_state.clone()));
-
-    let handles = vec![internal_handle, cluster_handle, external_handle];
+    let subscription_handle = tokio::spawn(initialize_subscription_rpc_server(app_state.clone()));
+
+    let mut handles = vec![
+        internal_handle,
+        cluster_handle,
+        external_handle,
+        subscription_handle,
+    ];
+    if app_state.config().ethereum_rpc_url.is_some() {
+        handles.push(tokio::spawn(initialize_ethereum_rpc_server(
+            app_state.clone(),
+        )));
+    }
     let results = try_join_all(handles).await;
     if let Err(e) = results {
         tracing::error!("One of the RPC servers terminated unexpectedly: {:?}", e);
@@ -200,13 +304,13 @@ async fn initialize_clients(app_state: AppState) -> Result<(), Error> {
                     liveness_info,
                 );
             }
-            SequencingInfoPayload::Local(_payload) => {
-                tracing::warn!(
-                    "Local LivenessClient not implemented for platform {:?} and service provider {:?}",
-                    platform,
-                    service_provider
+            SequencingInfoPayload::Local(liveness_info) => {
+                liveness::local::LivenessClient::initialize(
+                    app_state.clone(),
+                    *platform,
+                    *service_provider,
+                    liveness_info,
                 );
-                todo!("Implement 'LivenessClient' for local sequencing.");
             }
         }
     }
@@ -247,11 +351,17 @@ async fn initialize_internal_rpc_server(context: AppState) -> Result<(), Error>
         .register_rpc_method::<internal::AddSequencingInfo>()?
         .register_rpc_method::<internal::AddValidationInfo>()?
         .register_rpc_method::<internal::AddCluster>()?
+        .register_rpc_method::<internal::AddLocalCluster>()?
         .register_rpc_method::<internal::GetCluster>()?
         .register_rpc_method::<internal::GetClusterIdList>()?
         .register_rpc_method::<internal::GetSequencingInfos>()?
         .register_rpc_method::<internal::GetSequencingInfo>()?
         .register_rpc_method::<internal::SetMaxGasLimit>()?
+        .register_rpc_method::<internal::SetChainId>()?
+        .register_rpc_method::<internal::SetSignatureScheme>()?
+        .register_rpc_method::<internal::SetNoncePolicy>()?
+        .register_rpc_method::<internal::SetMerkleHashScheme>()?
+        .register_rpc_method::<internal::SetTimeLockPuzzleParams>()?
         .init(internal_rpc_url.clone())
         .await?;
 
@@ -270,9 +380,17 @@ async fn initialize_cluster_rpc_server(context: AppState) -> Result<(), Error> {
     let cluster_rpc_server = RpcServer::new(context)
         .register_rpc_method::<cluster::SyncEncryptedTransaction>()?
         .register_rpc_method::<cluster::SyncRawTransaction>()?
+        .register_rpc_method::<cluster::SyncEncryptedTransactionBatch>()?
+        .register_rpc_method::<cluster::SyncRawTransactionBatch>()?
         .register_rpc_method::<cluster::FinalizeBlock>()?
         .register_rpc_method::<cluster::SyncBlock>()?
         .register_rpc_method::<cluster::SyncMaxGasLimit>()?
+        .register_rpc_method::<cluster::SyncChainId>()?
+        .register_rpc_method::<cluster::SyncSignatureScheme>()?
+        .register_rpc_method::<cluster::SyncNoncePolicy>()?
+        .register_rpc_method::<cluster::SyncMerkleHashScheme>()?
+        .register_rpc_method::<cluster::SyncTimeLockPuzzleParams>()?
+        .register_rpc_method::<cluster::ChangeLeader>()?
         .register_rpc_method::<external::GetRawTransactionList>()?
         .init(cluster_rpc_url.clone())
         .await?;
@@ -302,13 +420,26 @@ async fn initialize_external_rpc_server(context: AppState) -> Result<(), Error>
         .register_rpc_method::<external::GetRawTransactionWithOrderCommitment>()?
         .register_rpc_method::<external::GetOrderCommitment>()?
         .register_rpc_method::<external::SendRawTransaction>()?
+        .register_rpc_method::<external::SendEncryptedTransactionBatch>()?
+        .register_rpc_method::<external::SendRawTransactionBatch>()?
         .register_rpc_method::<external::GetRawTransactionList>()?
         .register_rpc_method::<external::GetEncryptedTransactionList>()?
         .register_rpc_method::<external::GetRollup>()?
         .register_rpc_method::<external::GetRollupMetadata>()?
+        .register_rpc_method::<external::GetSenderNonce>()?
+        .register_rpc_method::<external::GetTransactionOrderStatus>()?
+        .register_rpc_method::<external::GetTransactionProof>()?
+        .register_rpc_method::<external::GetTransactionRange>()?
+        .register_rpc_method::<external::GetTransactionStatus>()?
         .register_rpc_method::<external::GetBlock>()?
+        .register_rpc_method::<external::GetBlockBuildJob>()?
         .register_rpc_method::<external::GetBlockHeight>()?
+        .register_rpc_method::<external::GetBlockMismatch>()?
+        .register_rpc_method::<external::GetBlockMismatchList>()?
+        .register_rpc_method::<external::GetEquivocationEvidence>()?
+        .register_rpc_method::<external::GetEquivocationEvidenceList>()?
         .register_rpc_method::<external::GetVersion>()?
+        .register_rpc_method::<external::VerifyOrderCommitment>()?
         .init(external_rpc_url)
         .await?;
 
@@ -316,6 +447,41 @@ async fn initialize_external_rpc_server(context: AppState) -> Result<(), Error>
     Ok(())
 }
 
+async fn initialize_subscription_rpc_server(context: AppState) -> Result<(), Error> {
+    let subscription_rpc_url = anywhere(&context.config().subscription_port()?);
+
+    let subscription_rpc_server = SubscriptionServer::new(context)
+        .register_subscription::<subscription::SubscribeBlocks>()?
+        .register_subscription::<subscription::SubscribeOrderCommitments>()?
+        .register_subscription::<subscription::SubscribeTransaction>()?
+        .init(subscription_rpc_url.clone())
+        .await?;
+
+    tracing::info!(
+        "Successfully started the subscription RPC server: {}",
+        subscription_rpc_url
+    );
+
+    subscription_rpc_server.stopped().await;
+    Ok(())
+}
+
+async fn initialize_ethereum_rpc_server(context: AppState) -> Result<(), Error> {
+    let ethereum_rpc_url = anywhere(&context.config().ethereum_port()?);
+
+    let ethereum_rpc_server = EthereumServer::new(context)?
+        .init(ethereum_rpc_url.clone())
+        .await?;
+
+    tracing::info!(
+        "Successfully started the Ethereum JSON-RPC server: {}",
+        ethereum_rpc_url
+    );
+
+    ethereum_rpc_server.stopped().await;
+    Ok(())
+}
+
 pub fn anywhere(port: &str) -> String {
     format!("0.0.0.0:{}", port)
 }
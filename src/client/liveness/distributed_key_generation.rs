@@ -51,6 +51,23 @@ impl DistributedKeyGenerationClient {
             .map_err(DistributedKeyGenerationClientError::GetDecryptionKey)
     }
 
+    pub async fn get_latest_encryption_key(
+        &self,
+    ) -> Result<GetLatestEncryptionKeyResponse, DistributedKeyGenerationClientError> {
+        let parameter = GetLatestEncryptionKey {};
+
+        self.inner
+            .rpc_client
+            .request(
+                &self.inner.rpc_url,
+                GetLatestEncryptionKey::METHOD_NAME,
+                &parameter,
+                Id::Null,
+            )
+            .await
+            .map_err(DistributedKeyGenerationClientError::GetLatestEncryptionKey)
+    }
+
     pub async fn get_skde_params(
         &self,
     ) -> Result<GetSkdeParamsResponse, DistributedKeyGenerationClientError> {
@@ -83,6 +100,19 @@ pub struct GetDecryptionKeyResponse {
     pub decryption_key: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetLatestEncryptionKey {}
+
+impl GetLatestEncryptionKey {
+    pub const METHOD_NAME: &'static str = "get_latest_encryption_key";
+}
+
+/// Only the key id is read. The encryption key itself is for the users.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetLatestEncryptionKeyResponse {
+    pub key_id: u64,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetSkdeParams {}
 
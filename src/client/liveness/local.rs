@@ -1 +1,366 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+    sync::Arc,
+};
 
+use radius_sdk::signature::{Address, PrivateKeySigner};
+use serde::{Deserialize, Serialize};
+
+use super::seeder::SequencerRpcInfo;
+use crate::{error::Error, state::AppState, types::*};
+
+/// Local clusters do not follow a chain, so they are stored at a single
+/// platform block height.
+pub const LOCAL_PLATFORM_BLOCK_HEIGHT: u64 = 0;
+
+/// Sequencer set, rollups and executors of a cluster that is not registered
+/// on a liveness contract.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LocalClusterInfo {
+    pub cluster_id: String,
+    pub sequencer_rpc_info_list: Vec<SequencerRpcInfo>,
+    pub rollup_info_list: Vec<LocalRollupInfo>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LocalRollupInfo {
+    pub rollup_id: String,
+    pub rollup_type: RollupType,
+    pub encrypted_transaction_type: EncryptedTransactionType,
+    pub order_commitment_type: OrderCommitmentType,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub owner: Address,
+
+    pub validation_info: RollupValidationInfo,
+
+    #[serde(serialize_with = "serialize_address_list")]
+    pub executor_address_list: Vec<Address>,
+}
+
+pub struct LivenessClient {
+    inner: Arc<LivenessClientInner>,
+}
+
+struct LivenessClientInner {
+    platform: Platform,
+    service_provider: ServiceProvider,
+    liveness_info: LivenessLocal,
+}
+
+impl Clone for LivenessClient {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl LivenessClient {
+    pub fn new(
+        platform: Platform,
+        service_provider: ServiceProvider,
+        liveness_info: LivenessLocal,
+    ) -> Self {
+        Self {
+            inner: Arc::new(LivenessClientInner {
+                platform,
+                service_provider,
+                liveness_info,
+            }),
+        }
+    }
+
+    pub fn initialize(
+        context: AppState,
+        platform: Platform,
+        service_provider: ServiceProvider,
+        liveness_info: LivenessLocal,
+    ) {
+        tokio::spawn(async move {
+            if let Err(error) =
+                Self::initialize_inner(context, platform, service_provider, liveness_info).await
+            {
+                tracing::error!(
+                    "Failed to initialize the local liveness client for {:?}, {:?} - {:?}",
+                    platform,
+                    service_provider,
+                    error
+                );
+            }
+        });
+    }
+
+    async fn initialize_inner(
+        context: AppState,
+        platform: Platform,
+        service_provider: ServiceProvider,
+        liveness_info: LivenessLocal,
+    ) -> Result<(), Error> {
+        let signer = PrivateKeySigner::from_str(platform.into(), &context.config().signing_key)
+            .map_err(Error::Signature)?;
+        context
+            .add_signer(platform, signer)
+            .await
+            .map_err(Error::CachedKvStore)?;
+
+        let liveness_client = Self::new(platform, service_provider, liveness_info);
+
+        // Clusters added through the internal RPC are already stored, so
+        // only the ones listed in the file are (re)loaded.
+        for cluster_info in liveness_client.load_cluster_info_list()? {
+            liveness_client
+                .initialize_cluster(&context, &cluster_info)
+                .await?;
+        }
+
+        context
+            .add_liveness_client(platform, service_provider, liveness_client)
+            .await
+            .map_err(Error::CachedKvStore)?;
+
+        tracing::info!(
+            "Initialized the local liveness client for {:?}, {:?}",
+            platform,
+            service_provider
+        );
+
+        Ok(())
+    }
+
+    /// Reads the clusters from the configured file, if any.
+    pub fn load_cluster_info_list(&self) -> Result<Vec<LocalClusterInfo>, Error> {
+        let cluster_file_path = match &self.inner.liveness_info.cluster_file_path {
+            Some(cluster_file_path) if !cluster_file_path.is_empty() => cluster_file_path,
+            _ => return Ok(Vec::new()),
+        };
+
+        let cluster_file = std::fs::read_to_string(cluster_file_path)?;
+
+        serde_json::from_str(&cluster_file).map_err(|error| {
+            tracing::error!(
+                "Failed to parse the local cluster file {:?}: {:?}",
+                cluster_file_path,
+                error
+            );
+            Error::Deserialize
+        })
+    }
+
+    pub fn find_cluster_info(&self, cluster_id: &str) -> Result<LocalClusterInfo, Error> {
+        self.load_cluster_info_list()?
+            .into_iter()
+            .find(|cluster_info| cluster_info.cluster_id == cluster_id)
+            .ok_or(Error::ClusterNotFound)
+    }
+
+    /// Stores the cluster and its rollups the same way
+    /// [`initialize_new_cluster`](super::radius::initialize_new_cluster) does
+    /// for clusters registered on chain.
+    pub async fn initialize_cluster(
+        &self,
+        context: &AppState,
+        cluster_info: &LocalClusterInfo,
+    ) -> Result<(), Error> {
+        tracing::debug!(
+            "Initializing the local cluster - platform: {:?} / service provider: {:?} / cluster id: {:?}..",
+            self.platform(),
+            self.service_provider(),
+            cluster_info.cluster_id
+        );
+
+        for rollup_info in cluster_info.rollup_info_list.iter() {
+            update_or_create_rollup(
+                self.platform(),
+                self.service_provider(),
+                &cluster_info.cluster_id,
+                rollup_info,
+            )?;
+        }
+
+        let sequencer_address = context
+            .get_signer(self.platform())
+            .await
+            .map_err(|_| Error::SignerNotFound)?
+            .address()
+            .clone();
+
+        let sequencer_rpc_infos = cluster_info
+            .sequencer_rpc_info_list
+            .iter()
+            .cloned()
+            .enumerate()
+            .collect::<BTreeMap<usize, SequencerRpcInfo>>();
+
+        let rollup_id_list = cluster_info
+            .rollup_info_list
+            .iter()
+            .map(|rollup_info| rollup_info.rollup_id.clone())
+            .collect::<BTreeSet<String>>();
+
+        let cluster = Cluster::new(sequencer_rpc_infos, rollup_id_list, sequencer_address, 0);
+        cluster
+            .put(
+                self.platform(),
+                self.service_provider(),
+                &cluster_info.cluster_id,
+                LOCAL_PLATFORM_BLOCK_HEIGHT,
+            )
+            .map_err(Error::Database)?;
+
+        let mut cluster_id_list = ClusterIdList::get_mut_or(
+            self.platform(),
+            self.service_provider(),
+            ClusterIdList::default,
+        )
+        .map_err(Error::Database)?;
+        cluster_id_list.insert(&cluster_info.cluster_id);
+        cluster_id_list.update().map_err(Error::Database)?;
+
+        let mut latest_cluster_block_height = LatestClusterBlockHeight::get_mut_or(
+            self.platform(),
+            self.service_provider(),
+            &cluster_info.cluster_id,
+            LatestClusterBlockHeight::default,
+        )
+        .map_err(Error::Database)?;
+        latest_cluster_block_height.set_block_height(LOCAL_PLATFORM_BLOCK_HEIGHT);
+        latest_cluster_block_height
+            .update()
+            .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.inner.platform
+    }
+
+    pub fn service_provider(&self) -> ServiceProvider {
+        self.inner.service_provider
+    }
+}
+
+fn update_or_create_rollup(
+    platform: Platform,
+    service_provider: ServiceProvider,
+    cluster_id: &str,
+    rollup_info: &LocalRollupInfo,
+) -> Result<(), Error> {
+    match Rollup::get_mut(&rollup_info.rollup_id) {
+        Ok(mut rollup) => {
+            rollup.set_executor_address_list(rollup_info.executor_address_list.clone());
+            rollup.update().map_err(Error::Database)?;
+
+            Ok(())
+        }
+        Err(error) => {
+            if !error.is_none_type() {
+                return Err(Error::Database(error));
+            }
+
+            let rollup = Rollup::new(
+                rollup_info.rollup_id.clone(),
+                rollup_info.rollup_type,
+                rollup_info.encrypted_transaction_type,
+                rollup_info.owner.clone(),
+                rollup_info.validation_info.clone(),
+                rollup_info.order_commitment_type,
+                rollup_info.executor_address_list.clone(),
+                cluster_id.to_owned(),
+                platform,
+                service_provider,
+            );
+
+            let mut rollup_id_list =
+                RollupIdList::get_mut_or(RollupIdList::default).map_err(Error::Database)?;
+            rollup_id_list.insert(&rollup.rollup_id);
+            rollup_id_list.update().map_err(Error::Database)?;
+
+            let rollup_metadata = RollupMetadata {
+                cluster_id: cluster_id.to_owned(),
+                ..Default::default()
+            };
+            rollup_metadata
+                .put(&rollup.rollup_id)
+                .map_err(Error::Database)?;
+
+            rollup.put(&rollup.rollup_id).map_err(Error::Database)?;
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn liveness_client(cluster_file_path: Option<&str>) -> LivenessClient {
+        LivenessClient::new(
+            Platform::Local,
+            ServiceProvider::Radius,
+            LivenessLocal {
+                cluster_file_path: cluster_file_path.map(str::to_owned),
+            },
+        )
+    }
+
+    fn write_cluster_file(name: &str, cluster_file: &str) -> String {
+        let cluster_file_path = std::env::temp_dir().join(format!(
+            "sequencer-test-{}-{}.json",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&cluster_file_path, cluster_file).unwrap();
+
+        cluster_file_path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn missing_cluster_file_path_lists_no_cluster() {
+        for cluster_file_path in [None, Some("")] {
+            let cluster_info_list = liveness_client(cluster_file_path)
+                .load_cluster_info_list()
+                .unwrap();
+
+            assert!(cluster_info_list.is_empty());
+        }
+    }
+
+    #[test]
+    fn cluster_is_found_in_the_cluster_file() {
+        let cluster_file_path = write_cluster_file(
+            "cluster_file",
+            r#"[
+                {"cluster_id": "cluster_1", "sequencer_rpc_info_list": [], "rollup_info_list": []},
+                {"cluster_id": "cluster_2", "sequencer_rpc_info_list": [], "rollup_info_list": []}
+            ]"#,
+        );
+        let liveness_client = liveness_client(Some(&cluster_file_path));
+
+        assert_eq!(liveness_client.load_cluster_info_list().unwrap().len(), 2);
+        assert_eq!(
+            liveness_client
+                .find_cluster_info("cluster_2")
+                .unwrap()
+                .cluster_id,
+            "cluster_2"
+        );
+        assert!(matches!(
+            liveness_client.find_cluster_info("cluster_3"),
+            Err(Error::ClusterNotFound)
+        ));
+    }
+
+    #[test]
+    fn malformed_cluster_file_is_rejected() {
+        let cluster_file_path = write_cluster_file("malformed_cluster_file", "{");
+
+        assert!(matches!(
+            liveness_client(Some(&cluster_file_path)).load_cluster_info_list(),
+            Err(Error::Deserialize)
+        ));
+    }
+}
@@ -7,7 +7,7 @@ use radius_sdk::validation::eigenlayer::{
 };
 use tokio::time::{sleep, Duration};
 
-use crate::{error::Error, state::AppState, types::*};
+use crate::{error::Error, state::AppState, task::wait_for_block_commitment, types::*};
 
 pub struct ValidationClient {
     inner: Arc<ValidationClientInner>,
@@ -115,7 +115,10 @@ impl ValidationClient {
                 );
                 validation_client
                     .subscriber()
-                    .initialize_event_handler(callback, validation_client.clone())
+                    .initialize_event_handler(
+                        callback,
+                        (context.clone(), validation_client.clone()),
+                    )
                     .await
                     .unwrap();
             }
@@ -136,26 +139,122 @@ impl ValidationClient {
     }
 }
 
-async fn callback(event: Avs::NewTaskCreated, context: ValidationClient) {
+async fn callback(
+    event: Avs::NewTaskCreated,
+    (context, validation_client): (AppState, ValidationClient),
+) {
     let rollup = Rollup::get(&event.rollupId).ok();
     if let Some(rollup) = rollup {
-        let block = Block::get(&rollup.rollup_id, event.task.blockNumber).unwrap();
-
-        if block.block_creator_address != context.publisher().address() {
-            let task = IValidationServiceManager::Task {
-                commitment: Bytes::from_iter(&[0u8; 32]),
-                blockNumber: 0,
-                rollupId: rollup.rollup_id,
-                clusterId: rollup.cluster_id,
-                taskCreatedBlock: event.taskCreatedBlock,
-            };
-
-            let transaction_hash = context
-                .publisher()
-                .respond_to_task(task, event.taskIndex, Bytes::from_iter(&[0_u8; 64]))
-                .await
-                .unwrap();
-            tracing::info!("[EigenLayer] respond_to_task: {:?}", transaction_hash);
+        let (block_commitment, block_creator_address) = match wait_for_block_commitment(
+            &context,
+            &rollup.rollup_id,
+            event.task.blockNumber,
+        )
+        .await
+        {
+            Ok(local_block) => local_block,
+            Err(error) => {
+                tracing::error!(
+                    "[EigenLayer] Failed to get the local block - rollup_id: {:?} / block_number: {:?} / error: {:?}",
+                    rollup.rollup_id,
+                    event.task.blockNumber,
+                    error
+                );
+                return;
+            }
+        };
+
+        if block_creator_address == validation_client.publisher().address() {
+            return;
+        }
+
+        let block_commitment = match block_commitment.as_bytes() {
+            Ok(block_commitment) => block_commitment,
+            Err(error) => {
+                tracing::error!("[EigenLayer] Invalid block commitment: {:?}", error);
+                return;
+            }
+        };
+
+        // EigenLayer has no negative response, so a mismatching task is left
+        // unsigned.
+        if block_commitment != event.task.commitment.to_vec() {
+            tracing::warn!(
+                "[EigenLayer] Block commitment mismatch - rollup_id: {:?} / block_number: {:?} / local: {:?} / task: {:?}",
+                rollup.rollup_id,
+                event.task.blockNumber,
+                const_hex::encode_prefixed(&block_commitment),
+                event.task.commitment
+            );
+            return;
+        }
+
+        let signature = match sign_block_commitment(
+            &context,
+            &rollup,
+            event.task.blockNumber,
+            &block_commitment,
+        )
+        .await
+        {
+            Ok(signature) => signature,
+            Err(error) => {
+                tracing::error!("[EigenLayer] Failed to sign the task response: {:?}", error);
+                return;
+            }
+        };
+
+        let task = IValidationServiceManager::Task {
+            commitment: Bytes::from(block_commitment),
+            blockNumber: event.task.blockNumber,
+            rollupId: rollup.rollup_id,
+            clusterId: rollup.cluster_id,
+            taskCreatedBlock: event.taskCreatedBlock,
+        };
+
+        match validation_client
+            .publisher()
+            .respond_to_task(task, event.taskIndex, signature)
+            .await
+        {
+            Ok(transaction_hash) => {
+                tracing::info!("[EigenLayer] respond_to_task: {:?}", transaction_hash);
+            }
+            Err(error) => {
+                tracing::error!("[EigenLayer] respond_to_task: {:?}", error);
+            }
         }
     }
 }
+
+/// Signs the block commitment the same way the leader signs it when the
+/// block is built.
+async fn sign_block_commitment(
+    context: &AppState,
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    block_commitment: &[u8],
+) -> Result<Bytes, Error> {
+    let block_commitment: [u8; 32] = block_commitment.try_into().map_err(|_| Error::Convert)?;
+
+    let signer = context
+        .get_signer(rollup.platform)
+        .await
+        .map_err(|_| Error::SignerNotFound)?;
+    let signature = rollup
+        .get_signature_scheme(rollup_block_height)
+        .map_err(Error::Database)?
+        .sign(
+            &signer,
+            &context.config().signing_key,
+            &BlockCommitmentMessage {
+                rollup_id: &rollup.rollup_id,
+                rollup_block_height,
+                block_commitment,
+            },
+        )?;
+
+    let signature = const_hex::decode(signature.as_hex_string()).map_err(|_| Error::Convert)?;
+
+    Ok(Bytes::from(signature))
+}
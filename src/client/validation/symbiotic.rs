@@ -5,7 +5,7 @@ use radius_sdk::validation::symbiotic::{
 };
 use tokio::time::{sleep, Duration};
 
-use crate::{error::Error, state::AppState, types::*};
+use crate::{error::Error, state::AppState, task::wait_for_block_commitment, types::*};
 
 pub struct ValidationClient {
     inner: Arc<ValidationClientInner>,
@@ -111,7 +111,10 @@ impl ValidationClient {
                 );
                 validation_client
                     .subscriber()
-                    .initialize_event_handler(callback, validation_client.clone())
+                    .initialize_event_handler(
+                        callback,
+                        (context.clone(), validation_client.clone()),
+                    )
                     .await
                     .unwrap();
             }
@@ -132,34 +135,83 @@ impl ValidationClient {
     }
 }
 
-async fn callback(event: ValidationServiceManager::NewTaskCreated, context: ValidationClient) {
+async fn callback(
+    event: ValidationServiceManager::NewTaskCreated,
+    (context, validation_client): (AppState, ValidationClient),
+) {
     let rollup = Rollup::get(&event.rollupId).ok();
     if let Some(rollup) = rollup {
-        let block = Block::get(&rollup.rollup_id, event.blockNumber.try_into().unwrap()).unwrap();
-
         tracing::info!("[Symbiotic] NewTaskCreated: clusterId: {:?} / rollupId: {:?} / referenceTaskIndex: {:?} / blockNumber: {:?} / blockCommitment: {:?}", event.clusterId, event.rollupId, event.referenceTaskIndex, event.blockNumber, event.blockCommitment);
 
-        if block.block_creator_address != context.publisher().address() {
-            for _ in 0..10 {
-                match context
-                    .publisher()
-                    .respond_to_task(
-                        &rollup.cluster_id,
-                        &rollup.rollup_id,
-                        event.referenceTaskIndex.try_into().unwrap(),
-                        true,
-                    )
-                    .await
-                    .map_err(|error| error.to_string())
-                {
-                    Ok(transaction_hash) => {
-                        tracing::info!("[Symbiotic] respond_to_task: {:?}", transaction_hash);
-                        break;
-                    }
-                    Err(error) => {
-                        tracing::warn!("[Symbiotic] respond_to_task: {:?}", error);
-                        sleep(Duration::from_secs(1)).await;
-                    }
+        let (block_number, reference_task_index) = match (
+            event.blockNumber.try_into(),
+            event.referenceTaskIndex.try_into(),
+        ) {
+            (Ok(block_number), Ok(reference_task_index)) => (block_number, reference_task_index),
+            _ => {
+                tracing::error!(
+                    "[Symbiotic] Invalid task - rollup_id: {:?} / block_number: {:?} / reference_task_index: {:?}",
+                    rollup.rollup_id,
+                    event.blockNumber,
+                    event.referenceTaskIndex
+                );
+                return;
+            }
+        };
+
+        let (block_commitment, block_creator_address) = match wait_for_block_commitment(
+            &context,
+            &rollup.rollup_id,
+            block_number,
+        )
+        .await
+        {
+            Ok(local_block) => local_block,
+            Err(error) => {
+                tracing::error!(
+                    "[Symbiotic] Failed to get the local block - rollup_id: {:?} / block_number: {:?} / error: {:?}",
+                    rollup.rollup_id,
+                    event.blockNumber,
+                    error
+                );
+                return;
+            }
+        };
+
+        if block_creator_address == validation_client.publisher().address() {
+            return;
+        }
+
+        let is_valid = block_commitment.matches(event.blockCommitment.as_slice());
+        if !is_valid {
+            tracing::warn!(
+                "[Symbiotic] Block commitment mismatch - rollup_id: {:?} / block_number: {:?} / local: {:?} / task: {:?}",
+                rollup.rollup_id,
+                event.blockNumber,
+                block_commitment,
+                event.blockCommitment
+            );
+        }
+
+        for _ in 0..10 {
+            match validation_client
+                .publisher()
+                .respond_to_task(
+                    &rollup.cluster_id,
+                    &rollup.rollup_id,
+                    reference_task_index,
+                    is_valid,
+                )
+                .await
+                .map_err(|error| error.to_string())
+            {
+                Ok(transaction_hash) => {
+                    tracing::info!("[Symbiotic] respond_to_task: {:?}", transaction_hash);
+                    break;
+                }
+                Err(error) => {
+                    tracing::warn!("[Symbiotic] respond_to_task: {:?}", error);
+                    sleep(Duration::from_secs(1)).await;
                 }
             }
         }
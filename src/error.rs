@@ -24,13 +24,17 @@ pub enum Error {
     MerkleTreeDoesNotExist(String),
     InitializeNewCluster(Box<dyn std::error::Error>),
     EmptyLeader,
+    InvalidLeaderChange,
     EmptyLeaderClusterRpcUrl,
+    EmptyExecutionRpcUrl,
     InvalidPlatformBlockHeight,
     ClusterNotFound,
     RollupNotFound,
     SignerNotFound,
     SequencerInfoNotFound,
     ExecutorAddressNotFound,
+    TransactionNotFound,
+    BlockNotFound,
     PlainDataDoesNotExist,
     UnsupportedEncryptedMempool,
     BlockHeightMismatch,
@@ -46,12 +50,28 @@ pub enum Error {
 
     Decryption,
     Deserialize,
+    InvalidTimeLockPuzzle,
+    EmptyTimeLockPuzzleParams,
 
     Convert,
     InvalidSignature,
+    ExpiredClusterMessage,
+    ReplayedClusterMessage,
+    UnauthorizedClusterMessage,
+    BlockMismatch,
+    InvalidOrderCommitment,
     InvalidTransaction,
+    InvalidTransactionSignature,
+    ChainIdMismatch,
+    DuplicateNonce,
+    NonceGap,
+    DuplicateTransaction,
+    ExpiredKeyId,
+    UnknownKeyId,
+    EmptyTransactionList,
     ExceedMaxGasLimit,
     RpcServerTerminated,
+    SubscriptionLagged,
     DatabaseVersionMismatch,
     Parse,
 }
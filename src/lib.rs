@@ -9,4 +9,6 @@ pub mod types;
 pub extern crate skde;
 pub mod merkle_tree_manager;
 pub mod migration;
+pub mod subscription_manager;
 pub mod util;
+pub mod verifier;
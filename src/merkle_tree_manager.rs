@@ -1,9 +1,8 @@
 use std::{collections::HashMap, sync::Arc};
 
-use radius_sdk::json_rpc::client::RpcClient;
 use tokio::sync::Mutex;
 
-use crate::{error::Error, types::*, util::fetch_raw_transaction_info};
+use crate::{error::Error, types::*};
 
 pub struct MerkleTreeManager {
     inner: Arc<Mutex<HashMap<String, MerkleTree>>>,
@@ -26,97 +25,22 @@ impl Default for MerkleTreeManager {
 }
 
 impl MerkleTreeManager {
-    pub async fn init(rpc_client: &RpcClient) -> Self {
+    /// Restores the Merkle tree of each rollup's open block from its
+    /// [`MerkleTreeCheckpoint`]. A rollup whose tree cannot be restored is
+    /// left out, so that no order commitment is issued over a wrong tree.
+    pub async fn init() -> Self {
         let merkle_tree_manager = Self::default();
 
         let rollup_id_list = RollupIdList::get_or(RollupIdList::default).unwrap();
         for rollup_id in rollup_id_list.iter() {
-            let merkle_tree = MerkleTree::new();
-
-            if let Some(rollup_metadata) = RollupMetadata::get(rollup_id).ok() {
-                if rollup_metadata.transaction_order > 0 {
-                    tracing::info!(
-                        "Building merkle tree for rollup - rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?}",
-                        rollup_id,
-                        rollup_metadata.rollup_block_height,
-                        rollup_metadata.transaction_order
-                    );
-                    let rollup = Rollup::get(rollup_id).unwrap();
-                    let latest_cluster_block_height = LatestClusterBlockHeight::get_or(
-                        rollup.platform,
-                        rollup.service_provider,
-                        &rollup.cluster_id,
-                        LatestClusterBlockHeight::default,
-                    )
-                    .unwrap();
-
-                    let cluster = Cluster::get(
-                        rollup.platform,
-                        rollup.service_provider,
-                        &rollup.cluster_id,
-                        latest_cluster_block_height.get_block_height(),
-                    )
-                    .unwrap();
-
-                    for index in 0..rollup_metadata.transaction_order {
-                        let get_raw_transaction_result = RawTransactionModel::get(
-                            rollup_id,
-                            rollup_metadata.rollup_block_height,
-                            index,
-                        );
-
-                        let raw_transaction_hash = match get_raw_transaction_result {
-                            Ok((raw_transaction, _)) => raw_transaction.raw_transaction_hash(),
-                            Err(_) => {
-                                tracing::warn!(
-                                "Failed to get raw transaction - rollup_id: {:?} / rollup_block_height: {:?} / index: {:?}",
-                                rollup_id,
-                                rollup_metadata.rollup_block_height,
-                                index
-                            );
-
-                                let raw_transaction_hash = match fetch_raw_transaction_info(
-                                    rpc_client,
-                                    &cluster,
-                                    &rollup_id,
-                                    rollup_metadata.rollup_block_height,
-                                    index,
-                                )
-                                .await
-                                {
-                                    Ok((raw_transaction, _)) => {
-                                        raw_transaction.raw_transaction_hash()
-                                    }
-                                    Err(error) => {
-                                        tracing::warn!(
-                                        "Failed to fetch raw transaction - rollup_id: {:?} / rollup_block_height: {:?} / index: {:?} / error: {:?}",
-                                        rollup_id,
-                                        rollup_metadata.rollup_block_height,
-                                        index,
-                                        error
-                                    );
-
-                                        let encrypted_transaction = EncryptedTransactionModel::get(
-                                            rollup_id,
-                                            rollup_metadata.rollup_block_height,
-                                            index,
-                                        )
-                                        .unwrap();
-
-                                        encrypted_transaction.raw_transaction_hash()
-                                    }
-                                };
-
-                                raw_transaction_hash
-                            }
-                        };
-
-                        merkle_tree.add_data(raw_transaction_hash.as_ref()).await;
-                    }
-                }
+            match restore_merkle_tree(rollup_id).await {
+                Ok(merkle_tree) => merkle_tree_manager.insert(rollup_id, merkle_tree).await,
+                Err(error) => tracing::error!(
+                    "Failed to restore merkle tree - rollup_id: {:?} / error: {:?}",
+                    rollup_id,
+                    error
+                ),
             }
-
-            merkle_tree_manager.insert(rollup_id, merkle_tree).await;
         }
 
         merkle_tree_manager
@@ -136,3 +60,71 @@ impl MerkleTreeManager {
         Ok(merkle_tree.clone())
     }
 }
+
+async fn restore_merkle_tree(rollup_id: &str) -> Result<MerkleTree, Error> {
+    let rollup_metadata = match RollupMetadata::get(rollup_id) {
+        Ok(rollup_metadata) => rollup_metadata,
+        Err(error) if error.is_none_type() => return Ok(MerkleTree::new()),
+        Err(error) => return Err(Error::Database(error)),
+    };
+    let rollup_block_height = rollup_metadata.rollup_block_height;
+    let transaction_count = rollup_metadata.transaction_order;
+
+    if transaction_count == 0 {
+        return Ok(MerkleTree::with_checkpoint(
+            rollup_id,
+            rollup_block_height,
+            rollup_metadata.merkle_hash_scheme,
+        ));
+    }
+
+    match MerkleTreeCheckpoint::get(rollup_id) {
+        Ok(merkle_tree_checkpoint)
+            if merkle_tree_checkpoint.rollup_block_height == rollup_block_height
+                && merkle_tree_checkpoint.leaf_index + 1 == transaction_count
+                && merkle_tree_checkpoint.hash_scheme == rollup_metadata.merkle_hash_scheme =>
+        {
+            if let Some(merkle_tree) =
+                MerkleTree::from_checkpoint(rollup_id, &merkle_tree_checkpoint)
+            {
+                return Ok(merkle_tree);
+            }
+        }
+        Ok(_) => {}
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(Error::Database(error)),
+    }
+
+    // Followers do not add leaves and databases written before checkpoints
+    // have none, so the tree is rebuilt from the stored transactions.
+    tracing::info!(
+        "Rebuilding merkle tree - rollup_id: {:?} / rollup_block_height: {:?} / transaction_count: {:?}",
+        rollup_id,
+        rollup_block_height,
+        transaction_count
+    );
+
+    let merkle_tree = MerkleTree::with_checkpoint(
+        rollup_id,
+        rollup_block_height,
+        rollup_metadata.merkle_hash_scheme,
+    );
+    for transaction_order in 0..transaction_count {
+        let transaction_hash =
+            match RawTransactionModel::get(rollup_id, rollup_block_height, transaction_order) {
+                Ok((raw_transaction, _is_direct_sent)) => raw_transaction.raw_transaction_hash()?,
+                Err(error) if error.is_none_type() => EncryptedTransactionModel::get(
+                    rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                )
+                .map_err(Error::Database)?
+                .raw_transaction_hash(),
+                Err(error) => return Err(Error::Database(error)),
+            };
+
+        merkle_tree.add_data(transaction_hash.as_ref()).await;
+    }
+
+    Ok(merkle_tree)
+}
@@ -93,6 +93,10 @@ fn migrate_rollup(kv_store: &KvStore, rollup_id: &str) -> Result<(), Error> {
             validation_info: old_rollup.validation_info,
             executor_address_list: old_rollup.executor_address_list,
             max_gas_limit: 0,
+            chain_id: None,
+            nonce_policy: NoncePolicy::default(),
+            signature_scheme: SignatureScheme::default(),
+            merkle_hash_scheme: MerkleHashScheme::default(),
         };
 
         kv_store
@@ -123,6 +127,9 @@ fn migrate_rollup_metadata(kv_store: &KvStore, rollup_id: &str) -> Result<(), Er
             leader_sequencer_rpc_info: old_metadata.leader_sequencer_rpc_info,
             max_gas_limit: 0,
             current_gas: 0,
+            merkle_hash_scheme: MerkleHashScheme::default(),
+            signature_scheme: SignatureScheme::default(),
+            merkle_root_version: MerkleRootVersion::unversioned(),
         };
 
         kv_store
@@ -0,0 +1,114 @@
+use crate::{
+    rpc::prelude::*,
+    task::{get_block_cluster, select_fallback_leader},
+    util::health_check,
+};
+
+/// Announces that the sender took over as the leader of the current rollup
+/// block after the previous leader stopped responding.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChangeLeader {
+    pub message: ChangeLeaderMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChangeLeaderMessage {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+
+    #[serde(serialize_with = "serialize_address")]
+    pub previous_leader_address: Address,
+    #[serde(serialize_with = "serialize_address")]
+    pub new_leader_address: Address,
+}
+
+impl RpcParameter<AppState> for ChangeLeader {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "change_leader"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::info!(
+            "Change leader - rollup_id: {:?} / rollup_block_height: {:?} / previous leader: {:?} / new leader: {:?}",
+            self.message.rollup_id,
+            self.message.rollup_block_height,
+            self.message.previous_leader_address.as_hex_string(),
+            self.message.new_leader_address.as_hex_string()
+        );
+
+        let rollup = Rollup::get(&self.message.rollup_id)?;
+
+        // The message must be signed by the sequencer taking over.
+        self.envelope
+            .ensure_signer(&self.message.new_leader_address)?;
+        self.envelope
+            .open(rollup.platform.into(), Self::method(), &self.message)?;
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        if self.message.rollup_block_height != rollup_metadata.rollup_block_height {
+            return Err(Error::BlockHeightMismatch.into());
+        }
+
+        let current_leader_address = &rollup_metadata.leader_sequencer_rpc_info.address;
+        if *current_leader_address == self.message.new_leader_address {
+            return Ok(());
+        }
+        if *current_leader_address != self.message.previous_leader_address {
+            return Err(Error::InvalidLeaderChange.into());
+        }
+
+        let cluster = get_block_cluster(&rollup, rollup_metadata.platform_block_height)?;
+        let new_leader_rpc_info = cluster
+            .get_sequencer_rpc_info(&self.message.new_leader_address)
+            .ok_or(Error::SequencerInfoNotFound)?;
+
+        // Only accept the change while the previous leader is unreachable from
+        // this sequencer as well.
+        if let Some(previous_leader_external_rpc_url) =
+            &rollup_metadata.leader_sequencer_rpc_info.external_rpc_url
+        {
+            if health_check(previous_leader_external_rpc_url).await.is_ok() {
+                tracing::warn!(
+                    "Rejected the leader change - the previous leader is still alive: {:?}",
+                    self.message.previous_leader_address.as_hex_string()
+                );
+                return Err(Error::InvalidLeaderChange.into());
+            }
+        }
+
+        // Only the first live fallback leader this sequencer sees, counting
+        // itself, may take over.
+        let is_fallback_leader =
+            select_fallback_leader(&cluster, &self.message.previous_leader_address)
+                .await
+                .is_some_and(|sequencer_rpc_info| {
+                    sequencer_rpc_info.address == self.message.new_leader_address
+                });
+        if !is_fallback_leader {
+            tracing::warn!(
+                "Rejected the leader change - not the fallback leader: {:?}",
+                self.message.new_leader_address.as_hex_string()
+            );
+            return Err(Error::InvalidLeaderChange.into());
+        }
+
+        let mut rollup_metadata = RollupMetadata::get_mut(&self.message.rollup_id)?;
+        if rollup_metadata.rollup_block_height != self.message.rollup_block_height {
+            return Err(Error::BlockHeightMismatch.into());
+        }
+        rollup_metadata.is_leader = self.message.new_leader_address == cluster.sequencer_address;
+        rollup_metadata.leader_sequencer_rpc_info = new_leader_rpc_info;
+        rollup_metadata.update()?;
+
+        BlockLeader::record(
+            &self.message.rollup_id,
+            self.message.rollup_block_height,
+            &self.message.new_leader_address,
+        )?;
+
+        Ok(())
+    }
+}
@@ -3,7 +3,10 @@ use std::str::FromStr;
 use ethers_core::types::{Signature as EthSignature, H256};
 use radius_sdk::{signature::ChainType, validation::symbiotic::types::Keccak256};
 
-use crate::{rpc::prelude::*, task::build_block};
+use crate::{
+    rpc::prelude::*,
+    task::{build_block, get_block_cluster},
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FinalizeBlock {
@@ -35,8 +38,35 @@ pub struct SignMessage {
     pub next_block_creator_address: String,
 }
 
+impl Eip712Message for FinalizeBlockMessage {
+    const TYPE: &'static str = "FinalizeBlock(string rollupId,address executorAddress,uint64 platformBlockHeight,uint64 rollupBlockHeight,address blockCreatorAddress,address nextBlockCreatorAddress)";
+
+    fn encode_data(&self) -> Result<Vec<u8>, Error> {
+        let mut encoded_data = encode_string(&self.rollup_id).to_vec();
+        encoded_data.extend(encode_address(&self.executor_address)?);
+        encoded_data.extend(encode_uint(self.platform_block_height));
+        encoded_data.extend(encode_uint(self.rollup_block_height));
+        encoded_data.extend(encode_address(&self.block_creator_address)?);
+        encoded_data.extend(encode_address(&self.next_block_creator_address)?);
+
+        Ok(encoded_data)
+    }
+}
+
 impl FinalizeBlock {
-    pub fn get_executor_address(&self, chain_type: ChainType) -> Result<Address, RpcError> {
+    pub fn get_executor_address(
+        &self,
+        chain_type: ChainType,
+        signature_scheme: &SignatureScheme,
+    ) -> Result<Address, RpcError> {
+        if let SignatureScheme::Eip712(domain) = signature_scheme {
+            return Ok(domain.recover(
+                chain_type,
+                &self.signature,
+                &self.finalize_block_message,
+            )?);
+        }
+
         // Serialize the sign message into JSON bytes
         let message_bytes = serde_json::to_vec(&SignMessage {
             rollup_id: self.finalize_block_message.rollup_id.clone(),
@@ -96,7 +126,10 @@ impl RpcParameter<AppState> for FinalizeBlock {
 
         // Check the executor address
         let rollup = Rollup::get(&self.finalize_block_message.rollup_id)?;
-        let signer_address = self.get_executor_address(rollup.platform.into())?;
+        let signer_address = self.get_executor_address(
+            rollup.platform.into(),
+            &rollup.get_signature_scheme(self.finalize_block_message.rollup_block_height)?,
+        )?;
 
         rollup
             .executor_address_list
@@ -117,7 +150,10 @@ impl RpcParameter<AppState> for FinalizeBlock {
             self.finalize_block_message.platform_block_height,
         );
 
-        let cluster = if cluster.is_err() {
+        let cluster = if cluster.is_err() && rollup.platform == Platform::Local {
+            // Local clusters are stored at a single platform block height.
+            get_block_cluster(&rollup, self.finalize_block_message.platform_block_height)?
+        } else if cluster.is_err() {
             tracing::warn!("Failed to retrieve cluster - cluster_id: {:?} / platform_block_height: {:?} / error: {:?}", 
             &rollup.cluster_id,
             self.finalize_block_message.platform_block_height,
@@ -140,30 +176,35 @@ impl RpcParameter<AppState> for FinalizeBlock {
         } else {
             cluster.unwrap()
         };
-        let transaction_count = self
+        let (transaction_count, merkle_hash_scheme, signature_scheme, merkle_root_version) = self
             .finalize_block(context.clone(), &cluster, &rollup)
             .await?;
 
         build_block(
             context,
-            cluster,
             self.finalize_block_message,
             self.signature,
-            rollup.encrypted_transaction_type.clone(),
+            rollup.encrypted_transaction_type,
             transaction_count,
-        );
+            merkle_hash_scheme,
+            signature_scheme,
+            merkle_root_version,
+        )?;
 
         Ok(())
     }
 }
 
 impl FinalizeBlock {
+    /// Opens the next block and returns the transaction count, the Merkle
+    /// hash and signature schemes and the Merkle root version of the finalized
+    /// one.
     pub async fn finalize_block(
         &self,
         context: AppState,
         cluster: &Cluster,
         rollup: &Rollup,
-    ) -> Result<u64, RpcError> {
+    ) -> Result<(u64, MerkleHashScheme, SignatureScheme, MerkleRootVersion), RpcError> {
         let next_rollup_block_height = self.finalize_block_message.rollup_block_height + 1;
 
         let signer = context.get_signer(rollup.platform).await?;
@@ -171,10 +212,26 @@ impl FinalizeBlock {
         let is_leader = sequencer_address == self.finalize_block_message.next_block_creator_address;
 
         let mut transaction_count = 0;
+        let mut merkle_hash_scheme = rollup.merkle_hash_scheme;
+        let mut signature_scheme = rollup.signature_scheme.clone();
+        let mut merkle_root_version = MerkleRootVersion::LATEST;
 
         match RollupMetadata::get_mut(&self.finalize_block_message.rollup_id) {
             Ok(mut rollup_metadata) => {
+                // A replayed message must not rewind the rollup metadata.
+                if next_rollup_block_height <= rollup_metadata.rollup_block_height {
+                    tracing::warn!(
+                        "Rejected a stale block - rollup_id: {:?} / rollup block height: {:?}",
+                        self.finalize_block_message.rollup_id,
+                        self.finalize_block_message.rollup_block_height
+                    );
+                    return Err(Error::BlockHeightMismatch.into());
+                }
+
                 transaction_count = rollup_metadata.transaction_order; // 2156
+                merkle_hash_scheme = rollup_metadata.merkle_hash_scheme;
+                signature_scheme = rollup_metadata.signature_scheme.clone();
+                merkle_root_version = rollup_metadata.merkle_root_version;
 
                 rollup_metadata.rollup_block_height = next_rollup_block_height;
                 rollup_metadata.transaction_order = 0;
@@ -183,6 +240,9 @@ impl FinalizeBlock {
                 rollup_metadata.is_leader = is_leader;
                 rollup_metadata.max_gas_limit = rollup.max_gas_limit;
                 rollup_metadata.current_gas = 0;
+                rollup_metadata.merkle_hash_scheme = rollup.merkle_hash_scheme;
+                rollup_metadata.signature_scheme = rollup.signature_scheme.clone();
+                rollup_metadata.merkle_root_version = MerkleRootVersion::LATEST;
 
                 if let Some(sequencer_rpc_info) = cluster
                     .get_sequencer_rpc_info(&self.finalize_block_message.next_block_creator_address)
@@ -195,7 +255,14 @@ impl FinalizeBlock {
 
                 context
                     .merkle_tree_manager()
-                    .insert(&self.finalize_block_message.rollup_id, MerkleTree::new())
+                    .insert(
+                        &self.finalize_block_message.rollup_id,
+                        MerkleTree::with_checkpoint(
+                            &self.finalize_block_message.rollup_id,
+                            next_rollup_block_height,
+                            rollup.merkle_hash_scheme,
+                        ),
+                    )
                     .await;
                 rollup_metadata.update()?;
             }
@@ -210,6 +277,8 @@ impl FinalizeBlock {
                     rollup_metadata.is_leader = is_leader;
                     rollup_metadata.max_gas_limit = rollup.max_gas_limit;
                     rollup_metadata.current_gas = 0;
+                    rollup_metadata.merkle_hash_scheme = rollup.merkle_hash_scheme;
+                    rollup_metadata.signature_scheme = rollup.signature_scheme.clone();
 
                     if let Some(sequencer_rpc_info) = cluster.get_sequencer_rpc_info(
                         &self.finalize_block_message.next_block_creator_address,
@@ -222,7 +291,14 @@ impl FinalizeBlock {
 
                     context
                         .merkle_tree_manager()
-                        .insert(&self.finalize_block_message.rollup_id, MerkleTree::new())
+                        .insert(
+                            &self.finalize_block_message.rollup_id,
+                            MerkleTree::with_checkpoint(
+                                &self.finalize_block_message.rollup_id,
+                                next_rollup_block_height,
+                                rollup.merkle_hash_scheme,
+                            ),
+                        )
                         .await;
                     rollup_metadata.put(&self.finalize_block_message.rollup_id)?;
                 } else {
@@ -231,6 +307,17 @@ impl FinalizeBlock {
             }
         }
 
-        Ok(transaction_count)
+        BlockLeader::record(
+            &self.finalize_block_message.rollup_id,
+            next_rollup_block_height,
+            &self.finalize_block_message.next_block_creator_address,
+        )?;
+
+        Ok((
+            transaction_count,
+            merkle_hash_scheme,
+            signature_scheme,
+            merkle_root_version,
+        ))
     }
 }
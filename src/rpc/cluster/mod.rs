@@ -1,11 +1,27 @@
+mod change_leader;
 mod finalize_block;
 mod sync_block;
+mod sync_chain_id;
 mod sync_encrypted_transaction;
+mod sync_encrypted_transaction_batch;
 mod sync_max_gas_limit;
+mod sync_merkle_hash_scheme;
+mod sync_nonce_policy;
 mod sync_raw_transaction;
+mod sync_raw_transaction_batch;
+mod sync_signature_scheme;
+mod sync_time_lock_puzzle_params;
 
+pub use change_leader::*;
 pub use finalize_block::*;
-pub use sync_block::SyncBlock;
+pub use sync_block::{SyncBlock, SyncBlockMessage};
+pub use sync_chain_id::*;
 pub use sync_encrypted_transaction::*;
+pub use sync_encrypted_transaction_batch::*;
 pub use sync_max_gas_limit::*;
+pub use sync_merkle_hash_scheme::*;
+pub use sync_nonce_policy::*;
 pub use sync_raw_transaction::*;
+pub use sync_raw_transaction_batch::*;
+pub use sync_signature_scheme::*;
+pub use sync_time_lock_puzzle_params::*;
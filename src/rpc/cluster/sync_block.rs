@@ -1,10 +1,19 @@
 use crate::{
-    rpc::{cluster::FinalizeBlockMessage, prelude::*},
-    task::follow_block,
+    rpc::{
+        cluster::{FinalizeBlock, FinalizeBlockMessage},
+        prelude::*,
+    },
+    task::{follow_block, get_block_cluster},
 };
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncBlock {
+    pub message: SyncBlockMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncBlockMessage {
     pub finalize_block_message: FinalizeBlockMessage,
     pub rollup_signature: Signature,
 
@@ -20,31 +29,66 @@ impl RpcParameter<AppState> for SyncBlock {
     }
 
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let SyncBlockMessage {
+            finalize_block_message,
+            rollup_signature,
+            transaction_count,
+            leader_sequencer_signature,
+        } = self.message.clone();
+
         tracing::debug!(
             "sync block - executor address: {:?}, rollup_id: {:?}, platform block height: {:?}, rollup block height: {:?}, transaction count: {:?}",
-            self.finalize_block_message.executor_address.as_hex_string(),
-            self.finalize_block_message.rollup_id,
-            self.finalize_block_message.platform_block_height,
-            self.finalize_block_message.rollup_block_height,
-            self.transaction_count,
+            finalize_block_message.executor_address.as_hex_string(),
+            finalize_block_message.rollup_id,
+            finalize_block_message.platform_block_height,
+            finalize_block_message.rollup_block_height,
+            transaction_count,
         );
 
-        let rollup = Rollup::get(&self.finalize_block_message.rollup_id).map_err(|e| {
+        let rollup = Rollup::get(&finalize_block_message.rollup_id).map_err(|e| {
             tracing::error!("Failed to retrieve rollup: {:?}", e);
             Error::RollupNotFound
         })?;
 
+        // The finalized block must come from an executor of the rollup.
+        let executor_address = FinalizeBlock {
+            finalize_block_message: finalize_block_message.clone(),
+            signature: rollup_signature.clone(),
+        }
+        .get_executor_address(
+            rollup.platform.into(),
+            &rollup.get_signature_scheme(finalize_block_message.rollup_block_height)?,
+        )?;
+        if !rollup.executor_address_list.contains(&executor_address) {
+            tracing::warn!(
+                "Executor address not found: {:?}",
+                executor_address.as_hex_string()
+            );
+            return Err(Error::ExecutorAddressNotFound.into());
+        }
+
+        // The leader signature is checked against the locally built block
+        // commitment by the block builder, so the message must come from the
+        // same sequencer.
+        self.envelope
+            .ensure_signer(&finalize_block_message.next_block_creator_address)?;
+        self.envelope
+            .open(rollup.platform.into(), Self::method(), &self.message)?;
+
         let cluster = Cluster::get(
             rollup.platform,
             rollup.service_provider,
             &rollup.cluster_id,
-            self.finalize_block_message.platform_block_height,
+            finalize_block_message.platform_block_height,
         );
 
-        let cluster = if cluster.is_err() {
+        let cluster = if cluster.is_err() && rollup.platform == Platform::Local {
+            // Local clusters are stored at a single platform block height.
+            get_block_cluster(&rollup, finalize_block_message.platform_block_height)?
+        } else if cluster.is_err() {
             tracing::warn!("Failed to retrieve cluster - cluster_id: {:?} / platform_block_height: {:?} / error: {:?}", 
             &rollup.cluster_id,
-            self.finalize_block_message.platform_block_height,
+            finalize_block_message.platform_block_height,
             cluster.err());
 
             let liveness_client: liveness::radius::LivenessClient = context
@@ -58,45 +102,73 @@ impl RpcParameter<AppState> for SyncBlock {
                 context.clone(),
                 &rollup.cluster_id,
                 &liveness_client,
-                self.finalize_block_message.platform_block_height,
+                finalize_block_message.platform_block_height,
             )
             .await?
         } else {
             cluster.unwrap()
         };
 
-        let next_rollup_block_height = self.finalize_block_message.rollup_block_height + 1;
+        let next_rollup_block_height = finalize_block_message.rollup_block_height + 1;
         let signer = context.get_signer(rollup.platform).await.map_err(|_| {
             tracing::error!("Signer not found for platform {:?}", rollup.platform);
             Error::SignerNotFound
         })?;
         let sequencer_address = signer.address().clone();
-        let is_leader = sequencer_address == self.finalize_block_message.next_block_creator_address;
+        let is_leader = sequencer_address == finalize_block_message.next_block_creator_address;
 
         let leader_sequencer_rpc_info = cluster
-            .get_sequencer_rpc_info(&self.finalize_block_message.next_block_creator_address)
+            .get_sequencer_rpc_info(&finalize_block_message.next_block_creator_address)
             .ok_or_else(|| {
                 tracing::error!(
                     "Sequencer RPC info not found for address {:?}",
-                    self.finalize_block_message.next_block_creator_address
+                    finalize_block_message.next_block_creator_address
                 );
                 Error::SequencerInfoNotFound
             })?;
 
-        match RollupMetadata::get_mut(&self.finalize_block_message.rollup_id) {
+        // The finalized block keeps the scheme it was opened with.
+        let mut block_merkle_hash_scheme = rollup.merkle_hash_scheme;
+        let mut block_signature_scheme = rollup.signature_scheme.clone();
+        let mut block_merkle_root_version = MerkleRootVersion::LATEST;
+        match RollupMetadata::get_mut(&finalize_block_message.rollup_id) {
             Ok(mut rollup_metadata) => {
+                // A stale block must not rewind the rollup metadata.
+                if next_rollup_block_height <= rollup_metadata.rollup_block_height {
+                    tracing::warn!(
+                        "Rejected a stale block - rollup_id: {:?} / rollup block height: {:?}",
+                        finalize_block_message.rollup_id,
+                        finalize_block_message.rollup_block_height
+                    );
+                    return Err(Error::BlockHeightMismatch.into());
+                }
+
+                block_merkle_hash_scheme = rollup_metadata.merkle_hash_scheme;
+                block_signature_scheme = rollup_metadata.signature_scheme.clone();
+                block_merkle_root_version = rollup_metadata.merkle_root_version;
+
                 rollup_metadata.rollup_block_height = next_rollup_block_height;
                 rollup_metadata.transaction_order = 0;
                 rollup_metadata.platform_block_height =
-                    self.finalize_block_message.platform_block_height;
+                    finalize_block_message.platform_block_height;
                 rollup_metadata.is_leader = is_leader;
                 rollup_metadata.max_gas_limit = rollup.max_gas_limit;
                 rollup_metadata.current_gas = 0;
                 rollup_metadata.leader_sequencer_rpc_info = leader_sequencer_rpc_info;
+                rollup_metadata.merkle_hash_scheme = rollup.merkle_hash_scheme;
+                rollup_metadata.signature_scheme = rollup.signature_scheme.clone();
+                rollup_metadata.merkle_root_version = MerkleRootVersion::LATEST;
 
                 context
                     .merkle_tree_manager()
-                    .insert(&self.finalize_block_message.rollup_id, MerkleTree::new())
+                    .insert(
+                        &finalize_block_message.rollup_id,
+                        MerkleTree::with_checkpoint(
+                            &finalize_block_message.rollup_id,
+                            next_rollup_block_height,
+                            rollup.merkle_hash_scheme,
+                        ),
+                    )
                     .await;
                 rollup_metadata.update()?;
             }
@@ -107,18 +179,28 @@ impl RpcParameter<AppState> for SyncBlock {
                         rollup_block_height: next_rollup_block_height,
                         transaction_order: 0,
                         cluster_id: rollup.cluster_id,
-                        platform_block_height: self.finalize_block_message.platform_block_height,
+                        platform_block_height: finalize_block_message.platform_block_height,
                         is_leader,
                         leader_sequencer_rpc_info,
                         max_gas_limit: rollup.max_gas_limit,
                         current_gas: 0,
+                        merkle_hash_scheme: rollup.merkle_hash_scheme,
+                        signature_scheme: rollup.signature_scheme.clone(),
+                        merkle_root_version: MerkleRootVersion::LATEST,
                     };
 
                     context
                         .merkle_tree_manager()
-                        .insert(&self.finalize_block_message.rollup_id, MerkleTree::new())
+                        .insert(
+                            &finalize_block_message.rollup_id,
+                            MerkleTree::with_checkpoint(
+                                &finalize_block_message.rollup_id,
+                                next_rollup_block_height,
+                                rollup.merkle_hash_scheme,
+                            ),
+                        )
                         .await;
-                    rollup_metadata.put(&self.finalize_block_message.rollup_id)?;
+                    rollup_metadata.put(&finalize_block_message.rollup_id)?;
                 } else {
                     tracing::error!("Failed to retrieve rollup metadata: {:?}", error);
                     return Err(error.into());
@@ -126,14 +208,23 @@ impl RpcParameter<AppState> for SyncBlock {
             }
         }
 
+        BlockLeader::record(
+            &finalize_block_message.rollup_id,
+            next_rollup_block_height,
+            &finalize_block_message.next_block_creator_address,
+        )?;
+
         follow_block(
             context.clone(),
-            cluster,
-            self.finalize_block_message,
+            finalize_block_message,
+            rollup_signature,
             rollup.encrypted_transaction_type,
-            self.transaction_count,
-            self.leader_sequencer_signature,
-        );
+            transaction_count,
+            block_merkle_hash_scheme,
+            block_signature_scheme,
+            block_merkle_root_version,
+            leader_sequencer_signature,
+        )?;
 
         Ok(())
     }
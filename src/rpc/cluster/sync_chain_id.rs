@@ -0,0 +1,53 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncChainId {
+    pub message: SyncChainIdMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncChainIdMessage {
+    pub rollup_id: String,
+    pub chain_id: Option<u64>,
+}
+
+impl RpcParameter<AppState> for SyncChainId {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_chain_id"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync chain id - rollup id: {:?}, chain id: {:?}",
+            self.message.rollup_id,
+            self.message.chain_id
+        );
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.message.rollup_id)?;
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        // Any sequencer of the cluster may update the chain id.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
+        }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.chain_id = self.message.chain_id;
+        locked_rollup.update()?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
-use crate::rpc::prelude::*;
+use crate::{rpc::prelude::*, task::put_order_commitment};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncEncryptedTransaction {
     pub message: SyncEncryptedTransactionMessage,
-    pub signature: Signature,
+    pub envelope: ClusterEnvelope,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -39,16 +39,17 @@ impl RpcParameter<AppState> for SyncEncryptedTransaction {
         let rollup = Rollup::get(&self.message.rollup_id)?;
         let mut rollup_metadata = RollupMetadata::get_mut(&self.message.rollup_id)?;
 
-        // Verify the leader signature
-        let leader_address = &rollup_metadata.leader_sequencer_rpc_info.address;
-        self.signature
-            .verify_message(rollup.platform.into(), &self.message, leader_address)?;
-
         // Check the rollup block height
         if self.message.rollup_block_height != rollup_metadata.rollup_block_height {
             return Err(Error::BlockHeightMismatch.into());
         }
 
+        // Only the current leader of the rollup block height may sync it.
+        self.envelope
+            .ensure_signer(&rollup_metadata.leader_sequencer_rpc_info.address)?;
+        self.envelope
+            .open(rollup.platform.into(), Self::method(), &self.message)?;
+
         let transaction_hash = self.message.encrypted_transaction.raw_transaction_hash();
 
         EncryptedTransactionModel::put_with_transaction_hash(
@@ -56,7 +57,6 @@ impl RpcParameter<AppState> for SyncEncryptedTransaction {
             &transaction_hash,
             &self.message.encrypted_transaction,
         )?;
-
         EncryptedTransactionModel::put(
             &self.message.rollup_id,
             self.message.rollup_block_height,
@@ -64,13 +64,35 @@ impl RpcParameter<AppState> for SyncEncryptedTransaction {
             &self.message.encrypted_transaction,
         )?;
 
-        self.message.order_commitment.put(
+        put_order_commitment(
+            &rollup,
+            self.message.rollup_block_height,
+            self.message.transaction_order,
+            self.message.order_commitment,
+        )?;
+        TransactionOrderIndex::record(
             &self.message.rollup_id,
+            TransactionKind::Encrypted,
+            &transaction_hash,
             self.message.rollup_block_height,
             self.message.transaction_order,
+            true,
+        )?;
+
+        SenderNonce::follow(
+            &self.message.rollup_id,
+            self.message.encrypted_transaction.sender_nonce_list(),
+        );
+
+        TransactionOrderStatus::record(
+            &self.message.rollup_id,
+            self.message.rollup_block_height,
+            [self.message.transaction_order],
         )?;
 
-        rollup_metadata.current_gas += transaction_gas_limit;
+        rollup_metadata.current_gas = rollup_metadata
+            .current_gas
+            .saturating_add(transaction_gas_limit);
         if rollup_metadata.transaction_order < self.message.transaction_order {
             rollup_metadata.transaction_order = self.message.transaction_order;
         }
@@ -3,7 +3,7 @@ use crate::rpc::prelude::*;
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncMaxGasLimit {
     pub message: SyncMaxGasLimitMessage,
-    pub signature: Signature,
+    pub envelope: ClusterEnvelope,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -34,22 +34,20 @@ impl RpcParameter<AppState> for SyncMaxGasLimit {
             &locked_rollup.cluster_id,
             rollup_metadata.platform_block_height,
         )?;
-        let sequencer_address_list = cluster.get_sequencer_address_list();
 
-        let chain_type = locked_rollup.platform.into();
-        for sequencer_address in sequencer_address_list {
-            let verify_result =
-                self.signature
-                    .verify_message(chain_type, &self.message, sequencer_address);
-
-            if verify_result.is_ok() {
-                locked_rollup.max_gas_limit = self.message.max_gas_limit;
-                locked_rollup.update()?;
-
-                return Ok(());
-            }
+        // Any sequencer of the cluster may update the max gas limit.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
         }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.max_gas_limit = self.message.max_gas_limit;
+        locked_rollup.update()?;
 
-        Err(Error::InvalidSignature)?
+        Ok(())
     }
 }
@@ -0,0 +1,53 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncMerkleHashScheme {
+    pub message: SyncMerkleHashSchemeMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncMerkleHashSchemeMessage {
+    pub rollup_id: String,
+    pub merkle_hash_scheme: MerkleHashScheme,
+}
+
+impl RpcParameter<AppState> for SyncMerkleHashScheme {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_merkle_hash_scheme"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync Merkle hash scheme - rollup id: {:?}, Merkle hash scheme: {:?}",
+            self.message.rollup_id,
+            self.message.merkle_hash_scheme
+        );
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.message.rollup_id)?;
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        // Any sequencer of the cluster may update the Merkle hash scheme.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
+        }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.merkle_hash_scheme = self.message.merkle_hash_scheme;
+        locked_rollup.update()?;
+
+        Ok(())
+    }
+}
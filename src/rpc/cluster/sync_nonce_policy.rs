@@ -0,0 +1,53 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncNoncePolicy {
+    pub message: SyncNoncePolicyMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncNoncePolicyMessage {
+    pub rollup_id: String,
+    pub nonce_policy: NoncePolicy,
+}
+
+impl RpcParameter<AppState> for SyncNoncePolicy {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_nonce_policy"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync nonce policy - rollup id: {:?}, nonce policy: {:?}",
+            self.message.rollup_id,
+            self.message.nonce_policy
+        );
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.message.rollup_id)?;
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        // Any sequencer of the cluster may update the nonce policy.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
+        }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.nonce_policy = self.message.nonce_policy;
+        locked_rollup.update()?;
+
+        Ok(())
+    }
+}
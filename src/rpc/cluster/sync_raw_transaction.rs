@@ -1,9 +1,9 @@
-use crate::rpc::prelude::*;
+use crate::{rpc::prelude::*, task::put_order_commitment};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncRawTransaction {
     pub message: SyncRawTransactionMessage,
-    pub signature: Signature,
+    pub envelope: ClusterEnvelope,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -37,21 +37,18 @@ impl RpcParameter<AppState> for SyncRawTransaction {
         let rollup = Rollup::get(&self.message.rollup_id)?;
         let mut rollup_metadata = RollupMetadata::get_mut(&self.message.rollup_id)?;
 
-        // Verify the leader signature
-        let leader_address = &rollup_metadata.leader_sequencer_rpc_info.address;
-        self.signature
-            .verify_message(rollup.platform.into(), &self.message, leader_address)
-            .map_err(|error| {
-                tracing::error!("Failed to verify the leader signature: {:?}", error);
-                Error::InvalidSignature
-            })?;
-
         // Check the rollup block height
         if self.message.rollup_block_height != rollup_metadata.rollup_block_height {
             return Err(Error::BlockHeightMismatch.into());
         }
 
-        let transaction_hash = self.message.raw_transaction.raw_transaction_hash();
+        // Only the current leader of the rollup block height may sync it.
+        self.envelope
+            .ensure_signer(&rollup_metadata.leader_sequencer_rpc_info.address)?;
+        self.envelope
+            .open(rollup.platform.into(), Self::method(), &self.message)?;
+
+        let transaction_hash = self.message.raw_transaction.raw_transaction_hash()?;
 
         RawTransactionModel::put_with_transaction_hash(
             &self.message.rollup_id,
@@ -59,7 +56,6 @@ impl RpcParameter<AppState> for SyncRawTransaction {
             self.message.raw_transaction.clone(),
             self.message.is_direct_sent,
         )?;
-
         RawTransactionModel::put(
             &self.message.rollup_id,
             self.message.rollup_block_height,
@@ -69,14 +65,36 @@ impl RpcParameter<AppState> for SyncRawTransaction {
         )?;
 
         if let Some(order_commitment) = self.message.order_commitment {
-            order_commitment.put(
-                &self.message.rollup_id,
+            put_order_commitment(
+                &rollup,
                 self.message.rollup_block_height,
                 self.message.transaction_order,
+                order_commitment,
             )?;
         }
+        TransactionOrderIndex::record(
+            &self.message.rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash,
+            self.message.rollup_block_height,
+            self.message.transaction_order,
+            true,
+        )?;
+
+        SenderNonce::follow(
+            &self.message.rollup_id,
+            self.message.raw_transaction.validate(None),
+        );
+
+        TransactionOrderStatus::record(
+            &self.message.rollup_id,
+            self.message.rollup_block_height,
+            [self.message.transaction_order],
+        )?;
 
-        rollup_metadata.current_gas += transaction_gas_limit;
+        rollup_metadata.current_gas = rollup_metadata
+            .current_gas
+            .saturating_add(transaction_gas_limit);
         if rollup_metadata.transaction_order < self.message.transaction_order {
             rollup_metadata.transaction_order = self.message.transaction_order;
         }
@@ -0,0 +1,145 @@
+use crate::{
+    rpc::prelude::*, task::put_order_commitment, verifier::verify_leader_bundle_order_commitment,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncRawTransactionBatch {
+    pub message: SyncRawTransactionBatchMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncRawTransactionBatchMessage {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+    pub first_transaction_order: u64,
+    pub raw_transaction_list: Vec<RawTransaction>,
+    pub bundle_order_commitment: BundleOrderCommitment,
+    pub is_direct_sent: bool,
+}
+
+impl RpcParameter<AppState> for SyncRawTransactionBatch {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_raw_transaction_batch"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync raw transaction batch - rollup id: {:?}, rollup block height: {:?}, first transaction order: {:?}, transaction count: {:?}",
+            self.message.rollup_id,
+            self.message.rollup_block_height,
+            self.message.first_transaction_order,
+            self.message.raw_transaction_list.len(),
+        );
+
+        if self.message.raw_transaction_list.is_empty() {
+            return Err(Error::EmptyTransactionList.into());
+        }
+
+        let batch_gas_limit = get_batch_gas_limit(
+            self.message
+                .raw_transaction_list
+                .iter()
+                .map(RawTransaction::get_transaction_gas_limit),
+        )?;
+
+        let rollup = Rollup::get(&self.message.rollup_id)?;
+        let mut rollup_metadata = RollupMetadata::get_mut(&self.message.rollup_id)?;
+
+        // Check the rollup block height
+        if self.message.rollup_block_height != rollup_metadata.rollup_block_height {
+            return Err(Error::BlockHeightMismatch.into());
+        }
+
+        // Only the current leader of the rollup block height may sync it.
+        self.envelope
+            .ensure_signer(&rollup_metadata.leader_sequencer_rpc_info.address)?;
+        self.envelope
+            .open(rollup.platform.into(), Self::method(), &self.message)?;
+
+        let transaction_hash_list = self
+            .message
+            .raw_transaction_list
+            .iter()
+            .map(RawTransaction::raw_transaction_hash)
+            .collect::<Result<Vec<RawTransactionHash>, Error>>()?;
+        verify_leader_bundle_order_commitment(
+            &rollup,
+            self.message.rollup_block_height,
+            self.message.first_transaction_order,
+            &transaction_hash_list,
+            &self.message.bundle_order_commitment,
+        )?;
+
+        // Order following the last one of the batch.
+        let next_transaction_order =
+            self.message.first_transaction_order + transaction_hash_list.len() as u64;
+
+        for (index, ((raw_transaction, transaction_hash), order_commitment)) in self
+            .message
+            .raw_transaction_list
+            .iter()
+            .zip(&transaction_hash_list)
+            .zip(self.message.bundle_order_commitment.order_commitment_list)
+            .enumerate()
+        {
+            let transaction_order = self.message.first_transaction_order + index as u64;
+
+            RawTransactionModel::put_with_transaction_hash(
+                &self.message.rollup_id,
+                transaction_hash,
+                raw_transaction.clone(),
+                self.message.is_direct_sent,
+            )?;
+            RawTransactionModel::put(
+                &self.message.rollup_id,
+                self.message.rollup_block_height,
+                transaction_order,
+                raw_transaction.clone(),
+                self.message.is_direct_sent,
+            )?;
+
+            put_order_commitment(
+                &rollup,
+                self.message.rollup_block_height,
+                transaction_order,
+                OrderCommitment::Single(order_commitment),
+            )?;
+            TransactionOrderIndex::record(
+                &self.message.rollup_id,
+                TransactionKind::Raw,
+                transaction_hash,
+                self.message.rollup_block_height,
+                transaction_order,
+                true,
+            )?;
+        }
+
+        SenderNonce::follow(
+            &self.message.rollup_id,
+            self.message.raw_transaction_list.iter().try_fold(
+                Vec::new(),
+                |mut sender_nonce_list, raw_transaction| {
+                    sender_nonce_list.extend(raw_transaction.validate(None)?);
+                    Ok(sender_nonce_list)
+                },
+            ),
+        );
+
+        TransactionOrderStatus::record(
+            &self.message.rollup_id,
+            self.message.rollup_block_height,
+            self.message.first_transaction_order..next_transaction_order,
+        )?;
+
+        rollup_metadata.current_gas = rollup_metadata.current_gas.saturating_add(batch_gas_limit);
+        if rollup_metadata.transaction_order < next_transaction_order {
+            rollup_metadata.transaction_order = next_transaction_order;
+        }
+        rollup_metadata.update()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncSignatureScheme {
+    pub message: SyncSignatureSchemeMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncSignatureSchemeMessage {
+    pub rollup_id: String,
+    pub signature_scheme: SignatureScheme,
+}
+
+impl RpcParameter<AppState> for SyncSignatureScheme {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_signature_scheme"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync signature scheme - rollup id: {:?}, signature scheme: {:?}",
+            self.message.rollup_id,
+            self.message.signature_scheme
+        );
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.message.rollup_id)?;
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        // Any sequencer of the cluster may update the signature scheme.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
+        }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.signature_scheme = self.message.signature_scheme;
+        locked_rollup.update()?;
+
+        Ok(())
+    }
+}
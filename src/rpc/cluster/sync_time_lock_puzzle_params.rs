@@ -0,0 +1,53 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncTimeLockPuzzleParams {
+    pub message: SyncTimeLockPuzzleParamsMessage,
+    pub envelope: ClusterEnvelope,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncTimeLockPuzzleParamsMessage {
+    pub rollup_id: String,
+    pub time_lock_puzzle_params: TimeLockPuzzleParams,
+}
+
+impl RpcParameter<AppState> for SyncTimeLockPuzzleParams {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_time_lock_puzzle_params"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::debug!(
+            "Sync time-lock puzzle params - rollup id: {:?}, time-lock puzzle params: {:?}",
+            self.message.rollup_id,
+            self.message.time_lock_puzzle_params
+        );
+
+        let rollup_metadata = RollupMetadata::get(&self.message.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.message.rollup_id)?;
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        // Any sequencer of the cluster may update the time-lock puzzle params.
+        if !cluster
+            .get_sequencer_address_list()
+            .contains(&self.envelope.signer)
+        {
+            return Err(Error::UnauthorizedClusterMessage.into());
+        }
+        self.envelope
+            .open(locked_rollup.platform.into(), Self::method(), &self.message)?;
+
+        locked_rollup.time_lock_puzzle_params = Some(self.message.time_lock_puzzle_params);
+        locked_rollup.update()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+
+use super::{ethereum_rollup_id, proxy, server_error};
+use crate::rpc::prelude::*;
+
+pub async fn eth_chain_id(
+    params: Params<'static>,
+    context: Arc<AppState>,
+) -> Result<serde_json::Value, ErrorObjectOwned> {
+    let rollup = Rollup::get(ethereum_rollup_id(&context)?).map_err(server_error)?;
+
+    match rollup.chain_id {
+        Some(chain_id) => Ok(serde_json::Value::String(format!("{:#x}", chain_id))),
+        // Left to the execution client if the rollup does not set one.
+        None => proxy("eth_chainId", params, context).await,
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use ethers_core::types as eth_types;
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+
+use super::{ethereum_rollup_id, server_error};
+use crate::rpc::{
+    external::{get_transaction_status, TransactionStatus},
+    prelude::*,
+};
+
+/// Returns the transaction as stored by the sequencer. Once it is in a
+/// block, the block fields are the sequencer's: the rollup block height, the
+/// index in the block's raw transactions and the block commitment as the
+/// block hash.
+pub async fn eth_get_transaction_by_hash(
+    params: Params<'static>,
+    context: Arc<AppState>,
+) -> Result<Option<eth_types::Transaction>, ErrorObjectOwned> {
+    let (transaction_hash,): (eth_types::H256,) = params.parse()?;
+    let transaction_hash = const_hex::encode_prefixed(transaction_hash);
+
+    get_transaction(ethereum_rollup_id(&context)?, &transaction_hash).map_err(server_error)
+}
+
+fn get_transaction(
+    rollup_id: &str,
+    transaction_hash: &str,
+) -> Result<Option<eth_types::Transaction>, Error> {
+    let (raw_transaction, _) =
+        match RawTransactionModel::get_with_transaction_hash(rollup_id, transaction_hash) {
+            Ok(raw_transaction) => raw_transaction,
+            Err(error) if error.is_none_type() => return Ok(None),
+            Err(error) => return Err(Error::Database(error)),
+        };
+
+    // A bundle has no Ethereum counterpart.
+    let RawTransaction::Eth(eth_raw_transaction) = raw_transaction else {
+        return Ok(None);
+    };
+    let mut transaction = eth_raw_transaction.validate(None)?;
+
+    match get_transaction_status(rollup_id, transaction_hash)? {
+        TransactionStatus::IncludedInBlock {
+            rollup_block_height,
+            transaction_index,
+            ..
+        }
+        | TransactionStatus::CommitmentSubmitted {
+            rollup_block_height,
+            transaction_index,
+            ..
+        } => {
+            let block = Block::get(rollup_id, rollup_block_height).map_err(Error::Database)?;
+
+            transaction.block_hash = block
+                .block_commitment
+                .as_bytes()
+                .ok()
+                .filter(|block_commitment| block_commitment.len() == 32)
+                .map(|block_commitment| eth_types::H256::from_slice(&block_commitment));
+            transaction.block_number = Some(rollup_block_height.into());
+            transaction.transaction_index = Some(transaction_index.into());
+        }
+        _ => {}
+    }
+
+    Ok(Some(transaction))
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+    use super::*;
+    use crate::types::{init_test_database, sign_eth_transaction, BlockCommitmentMessage};
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn put_block(
+        rollup_id: &str,
+        rollup_block_height: u64,
+        raw_transaction_list: Vec<RawTransaction>,
+    ) {
+        let signer = PrivateKeySigner::from_str(ChainType::Ethereum, SIGNING_KEY).unwrap();
+        let signature = SignatureScheme::Json
+            .sign(
+                &signer,
+                SIGNING_KEY,
+                &BlockCommitmentMessage {
+                    rollup_id,
+                    rollup_block_height,
+                    block_commitment: [7; 32],
+                },
+            )
+            .unwrap();
+        let transaction_hash_list = raw_transaction_list
+            .iter()
+            .map(|raw_transaction| raw_transaction.raw_transaction_hash().unwrap())
+            .collect();
+
+        let block = Block::new(
+            rollup_block_height,
+            vec![None; raw_transaction_list.len()],
+            raw_transaction_list,
+            transaction_hash_list,
+            Vec::new(),
+            signature,
+            BlockCommitment::from([7; 32]),
+            Address::from_slice(ChainType::Ethereum, &[0xcc; 20]).unwrap(),
+            MerkleHashScheme::default(),
+            SignatureScheme::Json,
+            MerkleRootVersion::unversioned(),
+        );
+        Block::put(&block, rollup_id, rollup_block_height).unwrap();
+    }
+
+    #[test]
+    fn unknown_transaction_is_none() {
+        init_test_database();
+
+        assert!(get_transaction(
+            "eth_get_transaction_by_hash_unknown",
+            &const_hex::encode_prefixed([1; 32])
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn block_fields_are_filled_once_the_transaction_is_in_a_block() {
+        init_test_database();
+        let rollup_id = "eth_get_transaction_by_hash_included";
+        let preceding_transaction =
+            RawTransaction::from(sign_eth_transaction(SIGNING_KEY, Some(1), 0));
+        let raw_transaction = RawTransaction::from(sign_eth_transaction(SIGNING_KEY, Some(1), 1));
+        let transaction_hash = raw_transaction.raw_transaction_hash().unwrap();
+        RawTransactionModel::put_with_transaction_hash(
+            rollup_id,
+            &transaction_hash,
+            raw_transaction.clone(),
+            true,
+        )
+        .unwrap();
+        TransactionOrderIndex::record(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash,
+            2,
+            1,
+            true,
+        )
+        .unwrap();
+
+        let ordered_transaction = get_transaction(rollup_id, transaction_hash.as_ref())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            const_hex::encode_prefixed(ordered_transaction.hash),
+            AsRef::<str>::as_ref(&transaction_hash)
+        );
+        assert!(ordered_transaction.block_hash.is_none());
+        assert!(ordered_transaction.block_number.is_none());
+
+        put_block(rollup_id, 2, vec![preceding_transaction, raw_transaction]);
+
+        let included_transaction = get_transaction(rollup_id, transaction_hash.as_ref())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            included_transaction.block_hash,
+            Some(eth_types::H256::repeat_byte(7))
+        );
+        assert_eq!(included_transaction.block_number, Some(2.into()));
+        assert_eq!(included_transaction.transaction_index, Some(1.into()));
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+
+use super::{ethereum_rollup_id, server_error};
+use crate::rpc::{external::SendRawTransaction, prelude::*};
+
+/// Orders the transaction through [`SendRawTransaction`] and returns its
+/// hash in place of the order commitment.
+pub async fn eth_send_raw_transaction(
+    params: Params<'static>,
+    context: Arc<AppState>,
+) -> Result<String, ErrorObjectOwned> {
+    let (raw_transaction,): (String,) = params.parse()?;
+
+    let parameter = send_raw_transaction(ethereum_rollup_id(&context)?, raw_transaction);
+    let raw_transaction = parameter.raw_transaction.clone();
+    parameter
+        .handler((*context).clone())
+        .await
+        .map_err(server_error)?;
+
+    // Hashed only once the handler has validated the transaction.
+    Ok(raw_transaction
+        .raw_transaction_hash()
+        .map_err(server_error)?
+        .as_string())
+}
+
+fn send_raw_transaction(rollup_id: &str, raw_transaction: String) -> SendRawTransaction {
+    SendRawTransaction {
+        rollup_id: rollup_id.to_owned(),
+        raw_transaction: RawTransaction::Eth(EthRawTransaction(raw_transaction)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::sign_eth_transaction;
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn transaction_is_sent_to_the_sequencer_of_the_rollup() {
+        let EthRawTransaction(raw_transaction) = sign_eth_transaction(SIGNING_KEY, Some(1), 0);
+
+        let parameter = send_raw_transaction("ethereum_rollup", raw_transaction.clone());
+
+        assert_eq!(SendRawTransaction::method(), "send_raw_transaction");
+        assert_eq!(parameter.rollup_id, "ethereum_rollup");
+        assert!(matches!(
+            parameter.raw_transaction,
+            RawTransaction::Eth(EthRawTransaction(sent_raw_transaction))
+                if sent_raw_transaction == raw_transaction
+        ));
+    }
+
+    #[test]
+    fn transaction_hash_is_the_ethereum_transaction_hash() {
+        let EthRawTransaction(raw_transaction) = sign_eth_transaction(SIGNING_KEY, Some(1), 0);
+        let ethereum_transaction_hash = const_hex::encode_prefixed(ethers_core::utils::keccak256(
+            const_hex::decode(&raw_transaction).unwrap(),
+        ));
+
+        let parameter = send_raw_transaction("ethereum_rollup", raw_transaction);
+
+        assert_eq!(
+            parameter
+                .raw_transaction
+                .raw_transaction_hash()
+                .unwrap()
+                .as_string(),
+            ethereum_transaction_hash
+        );
+    }
+}
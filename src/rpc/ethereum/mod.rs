@@ -0,0 +1,103 @@
+mod eth_chain_id;
+mod eth_get_transaction_by_hash;
+mod eth_send_raw_transaction;
+mod proxy;
+
+pub use eth_chain_id::*;
+pub use eth_get_transaction_by_hash::*;
+pub use eth_send_raw_transaction::*;
+use jsonrpsee::{
+    server::{Server, ServerHandle},
+    types::ErrorObjectOwned,
+    RpcModule,
+};
+pub use proxy::*;
+
+use crate::{error::Error, state::AppState};
+
+/// Generic server error code of the Ethereum JSON-RPC specification.
+const SERVER_ERROR_CODE: i32 = -32000;
+
+/// Ethereum JSON-RPC compatibility server for the rollup set in
+/// `ethereum_rollup_id`, so that wallets and tooling can send transactions to
+/// the sequencer directly.
+pub struct EthereumServer {
+    rpc_module: RpcModule<AppState>,
+}
+
+impl EthereumServer {
+    pub fn new(context: AppState) -> Result<Self, Error> {
+        let mut rpc_module = RpcModule::new(context);
+
+        rpc_module
+            .register_async_method("eth_chainId", eth_chain_id)
+            .map_err(|error| Error::Internal(error.into()))?;
+        rpc_module
+            .register_async_method("eth_sendRawTransaction", eth_send_raw_transaction)
+            .map_err(|error| Error::Internal(error.into()))?;
+        rpc_module
+            .register_async_method("eth_getTransactionByHash", eth_get_transaction_by_hash)
+            .map_err(|error| Error::Internal(error.into()))?;
+
+        for method in PROXY_METHOD_LIST {
+            rpc_module
+                .register_async_method(method, move |params, context| {
+                    proxy(method, params, context)
+                })
+                .map_err(|error| Error::Internal(error.into()))?;
+        }
+
+        Ok(Self { rpc_module })
+    }
+
+    pub async fn init(self, rpc_url: impl AsRef<str>) -> Result<ServerHandle, Error> {
+        let server = Server::builder().build(rpc_url.as_ref()).await?;
+
+        Ok(server.start(self.rpc_module))
+    }
+}
+
+fn ethereum_rollup_id(context: &AppState) -> Result<&str, ErrorObjectOwned> {
+    context
+        .config()
+        .ethereum_rollup_id
+        .as_deref()
+        .ok_or_else(|| server_error(Error::RollupNotFound))
+}
+
+fn server_error(error: impl std::fmt::Debug) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(SERVER_ERROR_CODE, format!("{:?}", error), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_methods_are_not_served_locally() {
+        for method in [
+            "eth_chainId",
+            "eth_sendRawTransaction",
+            "eth_getTransactionByHash",
+        ] {
+            assert!(!PROXY_METHOD_LIST.contains(&method));
+        }
+    }
+
+    #[test]
+    fn proxy_methods_are_unique() {
+        let mut method_list = PROXY_METHOD_LIST.to_vec();
+        method_list.sort_unstable();
+        method_list.dedup();
+
+        assert_eq!(method_list.len(), PROXY_METHOD_LIST.len());
+    }
+
+    #[test]
+    fn server_error_uses_the_generic_server_error_code() {
+        let error = server_error(Error::RollupNotFound);
+
+        assert_eq!(error.code(), SERVER_ERROR_CODE);
+        assert_eq!(error.message(), "RollupNotFound");
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+
+use super::server_error;
+use crate::rpc::prelude::*;
+
+/// Read methods answered by the execution RPC of the rollup.
+pub const PROXY_METHOD_LIST: [&str; 16] = [
+    "eth_blockNumber",
+    "eth_call",
+    "eth_estimateGas",
+    "eth_feeHistory",
+    "eth_gasPrice",
+    "eth_getBalance",
+    "eth_getBlockByHash",
+    "eth_getBlockByNumber",
+    "eth_getCode",
+    "eth_getLogs",
+    "eth_getStorageAt",
+    "eth_getTransactionCount",
+    "eth_getTransactionReceipt",
+    "eth_maxPriorityFeePerGas",
+    "net_version",
+    "web3_clientVersion",
+];
+
+pub async fn proxy(
+    method: &'static str,
+    params: Params<'static>,
+    context: Arc<AppState>,
+) -> Result<serde_json::Value, ErrorObjectOwned> {
+    let execution_rpc_url = context
+        .config()
+        .ethereum_execution_rpc_url
+        .as_ref()
+        .ok_or_else(|| server_error(Error::EmptyExecutionRpcUrl))?;
+
+    let params = params
+        .parse::<Option<serde_json::Value>>()?
+        .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+
+    context
+        .rpc_client()
+        .request(execution_rpc_url, method, &params, Id::Null)
+        .await
+        .map_err(server_error)
+}
@@ -12,6 +12,10 @@ pub struct GetBlockResponse {
 
     pub encrypted_transaction_list: Vec<Option<EncryptedTransaction>>,
     pub raw_transaction_list: Vec<RawTransaction>,
+    pub transaction_hash_list: Vec<RawTransactionHash>,
+
+    #[serde(default)]
+    pub invalid_transaction_list: Vec<InvalidTransaction>,
 
     #[serde(serialize_with = "serialize_address")]
     pub block_creator_address: Address,
@@ -30,13 +34,21 @@ impl RpcParameter<AppState> for GetBlock {
     async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
         let block = Block::get(&self.rollup_id, self.rollup_block_height)?;
 
-        Ok(GetBlockResponse {
+        Ok(block.into())
+    }
+}
+
+impl From<Block> for GetBlockResponse {
+    fn from(block: Block) -> Self {
+        Self {
             block_height: block.block_height,
             encrypted_transaction_list: block.encrypted_transaction_list,
             raw_transaction_list: block.raw_transaction_list,
+            transaction_hash_list: block.transaction_hash_list,
+            invalid_transaction_list: block.invalid_transaction_list,
             block_creator_address: block.block_creator_address,
             signature: block.signature.as_hex_string(),
             block_commitment: block.block_commitment,
-        })
+        }
     }
 }
@@ -0,0 +1,21 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockBuildJob {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+}
+
+impl RpcParameter<AppState> for GetBlockBuildJob {
+    type Response = BlockBuildJob;
+
+    fn method() -> &'static str {
+        "get_block_build_job"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let block_build_job = BlockBuildJob::get(&self.rollup_id, self.rollup_block_height)?;
+
+        Ok(block_build_job)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockMismatch {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+}
+
+impl RpcParameter<AppState> for GetBlockMismatch {
+    type Response = BlockMismatch;
+
+    fn method() -> &'static str {
+        "get_block_mismatch"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let block_mismatch = BlockMismatch::get(&self.rollup_id, self.rollup_block_height)?;
+
+        Ok(block_mismatch)
+    }
+}
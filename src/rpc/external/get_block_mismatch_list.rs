@@ -0,0 +1,28 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockMismatchList {
+    pub rollup_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockMismatchListResponse {
+    pub rollup_block_height_list: Vec<u64>,
+}
+
+impl RpcParameter<AppState> for GetBlockMismatchList {
+    type Response = GetBlockMismatchListResponse;
+
+    fn method() -> &'static str {
+        "get_block_mismatch_list"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let block_mismatch_list =
+            BlockMismatchList::get_or(&self.rollup_id, BlockMismatchList::default)?;
+
+        Ok(GetBlockMismatchListResponse {
+            rollup_block_height_list: block_mismatch_list.iter().copied().collect(),
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEquivocationEvidence {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+    pub transaction_order: u64,
+}
+
+impl RpcParameter<AppState> for GetEquivocationEvidence {
+    type Response = EquivocationEvidence;
+
+    fn method() -> &'static str {
+        "get_equivocation_evidence"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let equivocation_evidence = EquivocationEvidence::get(
+            &self.rollup_id,
+            self.rollup_block_height,
+            self.transaction_order,
+        )?;
+
+        Ok(equivocation_evidence)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEquivocationEvidenceList {
+    pub rollup_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEquivocationEvidenceListResponse {
+    /// `(rollup_block_height, transaction_order)` of each evidence.
+    pub equivocation_evidence_list: Vec<(u64, u64)>,
+}
+
+impl RpcParameter<AppState> for GetEquivocationEvidenceList {
+    type Response = GetEquivocationEvidenceListResponse;
+
+    fn method() -> &'static str {
+        "get_equivocation_evidence_list"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let equivocation_evidence_list =
+            EquivocationEvidenceList::get_or(&self.rollup_id, EquivocationEvidenceList::default)?;
+
+        Ok(GetEquivocationEvidenceListResponse {
+            equivocation_evidence_list: equivocation_evidence_list.iter().copied().collect(),
+        })
+    }
+}
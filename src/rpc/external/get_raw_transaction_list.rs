@@ -24,9 +24,10 @@ impl RpcParameter<AppState> for GetRawTransactionList {
         let raw_transaction_list: Vec<String> = block
             .raw_transaction_list
             .into_iter()
-            .map(|transaction| match transaction {
-                RawTransaction::Eth(EthRawTransaction(data)) => data,
-                RawTransaction::EthBundle(EthRawBundleTransaction(data)) => data,
+            .flat_map(RawTransaction::expand)
+            .filter_map(|transaction| match transaction {
+                RawTransaction::Eth(EthRawTransaction(data)) => Some(data),
+                RawTransaction::EthBundle(_) => None,
             })
             .collect();
 
@@ -0,0 +1,34 @@
+use ethers_core::types as eth_types;
+
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetSenderNonce {
+    pub rollup_id: String,
+    pub sender: eth_types::Address,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetSenderNonceResponse {
+    /// `None` if no transaction of the sender has been ordered.
+    pub next_nonce: Option<u64>,
+    pub nonce_policy: NoncePolicy,
+}
+
+impl RpcParameter<AppState> for GetSenderNonce {
+    type Response = GetSenderNonceResponse;
+
+    fn method() -> &'static str {
+        "get_sender_nonce"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup = Rollup::get(&self.rollup_id)?;
+        let sender_nonce = SenderNonce::get_by_sender(&self.rollup_id, &self.sender)?;
+
+        Ok(GetSenderNonceResponse {
+            next_nonce: sender_nonce.map(|sender_nonce| sender_nonce.next_nonce),
+            nonce_policy: rollup.nonce_policy,
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionOrderStatus {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionOrderStatusResponse {
+    pub contiguous_transaction_count: u64,
+    pub transaction_count: u64,
+    pub missing_transaction_count: u64,
+}
+
+impl RpcParameter<AppState> for GetTransactionOrderStatus {
+    type Response = GetTransactionOrderStatusResponse;
+
+    fn method() -> &'static str {
+        "get_transaction_order_status"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let transaction_order_status = TransactionOrderStatus::get_or(
+            &self.rollup_id,
+            self.rollup_block_height,
+            TransactionOrderStatus::default,
+        )?;
+
+        Ok(GetTransactionOrderStatusResponse {
+            contiguous_transaction_count: transaction_order_status.contiguous_transaction_count,
+            transaction_count: transaction_order_status.transaction_count,
+            missing_transaction_count: transaction_order_status.missing_transaction_count(),
+        })
+    }
+}
@@ -0,0 +1,175 @@
+use super::get_transaction_range::optional;
+use crate::rpc::prelude::*;
+
+/// Either `transaction_order` or `transaction_hash` selects the transaction.
+/// The hash of a transaction of a bundle selects the leaf of its bundle.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionProof {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+
+    pub transaction_order: Option<u64>,
+    pub transaction_hash: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionProofResponse {
+    pub transaction_order: u64,
+    /// Hash committed by the leaf, which is the bundle hash for a bundle.
+    pub transaction_hash: RawTransactionHash,
+    /// Hashes of the transactions of a bundle in execution order, which the
+    /// bundle hash is the keccak256 of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_transaction_hash_list: Option<Vec<RawTransactionHash>>,
+
+    pub leaf: String,
+    /// Sibling of the node on each level from the leaf up. Whether the sibling
+    /// is on the left follows from the bits of `transaction_order`.
+    #[serde(
+        serialize_with = "serialize_merkle_path",
+        deserialize_with = "deserialize_merkle_path"
+    )]
+    pub merkle_path: Vec<[u8; 32]>,
+    pub merkle_root: BlockCommitment,
+    pub merkle_hash_scheme: MerkleHashScheme,
+    /// How the root was completed from the leaves, see [`MerkleRootVersion`].
+    pub merkle_root_version: MerkleRootVersion,
+}
+
+impl RpcParameter<AppState> for GetTransactionProof {
+    type Response = GetTransactionProofResponse;
+
+    fn method() -> &'static str {
+        "get_transaction_proof"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let block = Block::get(&self.rollup_id, self.rollup_block_height)?;
+        let merkle_leaf_list = block.merkle_leaf_list()?;
+
+        let transaction_order = match (self.transaction_order, &self.transaction_hash) {
+            (Some(transaction_order), _) => transaction_order,
+            (None, Some(transaction_hash)) => {
+                match merkle_leaf_list
+                    .iter()
+                    .position(|leaf| AsRef::<str>::as_ref(leaf) == transaction_hash.as_str())
+                {
+                    Some(transaction_order) => transaction_order as u64,
+                    None => {
+                        find_bundle_transaction_order(&self.rollup_id, &block, transaction_hash)?
+                            .ok_or(Error::TransactionNotFound)?
+                    }
+                }
+            }
+            (None, None) => return Err(Error::TransactionNotFound.into()),
+        };
+
+        let transaction_hash = merkle_leaf_list
+            .get(transaction_order as usize)
+            .cloned()
+            .ok_or(Error::TransactionNotFound)?;
+        let bundle_transaction_hash_list =
+            get_bundle_transaction_hash_list(&self.rollup_id, &block, transaction_order)?;
+
+        if let Some(expected_transaction_hash) = &self.transaction_hash {
+            let is_expected_transaction =
+                AsRef::<str>::as_ref(&transaction_hash) == expected_transaction_hash.as_str()
+                    || bundle_transaction_hash_list.iter().flatten().any(
+                        |bundle_transaction_hash| {
+                            AsRef::<str>::as_ref(bundle_transaction_hash)
+                                == expected_transaction_hash.as_str()
+                        },
+                    );
+            if !is_expected_transaction {
+                return Err(Error::TransactionNotFound.into());
+            }
+        }
+
+        let merkle_tree = MerkleTree::with_hash_scheme(block.merkle_hash_scheme);
+        for leaf in merkle_leaf_list.iter() {
+            merkle_tree.add_data(leaf.as_ref()).await;
+        }
+        // Completed the way the block was built.
+        merkle_tree.finalize_tree(block.merkle_root_version).await;
+
+        let merkle_root = BlockCommitment::from(merkle_tree.get_merkle_root().await);
+        if merkle_root != block.block_commitment {
+            // The stored commitment was computed over a differently built tree.
+            tracing::error!(
+                "Rebuilt Merkle root does not match the block commitment - rollup_id: {:?} / rollup_block_height: {:?}",
+                self.rollup_id,
+                self.rollup_block_height
+            );
+            return Err(Error::BlockMismatch.into());
+        }
+
+        Ok(GetTransactionProofResponse {
+            transaction_order,
+            transaction_hash: transaction_hash.clone(),
+            bundle_transaction_hash_list,
+            leaf: const_hex::encode_prefixed(
+                block
+                    .merkle_hash_scheme
+                    .hasher()
+                    .hash_leaf(transaction_hash.as_ref()),
+            ),
+            merkle_path: merkle_tree
+                .get_merkle_path(transaction_order as usize)
+                .await,
+            merkle_root,
+            merkle_hash_scheme: block.merkle_hash_scheme,
+            merkle_root_version: block.merkle_root_version,
+        })
+    }
+}
+
+/// Returns the order of the bundle of the block holding the transaction.
+fn find_bundle_transaction_order(
+    rollup_id: &str,
+    block: &Block,
+    transaction_hash: &str,
+) -> Result<Option<u64>, Error> {
+    for transaction_order in 0..block.merkle_leaf_list()?.len() as u64 {
+        let is_bundle_transaction =
+            get_bundle_transaction_hash_list(rollup_id, block, transaction_order)?
+                .iter()
+                .flatten()
+                .any(|bundle_transaction_hash| {
+                    AsRef::<str>::as_ref(bundle_transaction_hash) == transaction_hash
+                });
+        if is_bundle_transaction {
+            return Ok(Some(transaction_order));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the transaction hashes of the bundle at the order, or `None` if
+/// the order holds a single transaction or was left out of the block.
+fn get_bundle_transaction_hash_list(
+    rollup_id: &str,
+    block: &Block,
+    transaction_order: u64,
+) -> Result<Option<Vec<RawTransactionHash>>, Error> {
+    let is_invalid = block
+        .invalid_transaction_list
+        .iter()
+        .any(|invalid_transaction| invalid_transaction.transaction_order == transaction_order);
+    if is_invalid {
+        return Ok(None);
+    }
+
+    match optional(RawTransactionModel::get(
+        rollup_id,
+        block.block_height,
+        transaction_order,
+    ))
+    .map_err(Error::Database)?
+    {
+        Some((RawTransaction::EthBundle(eth_bundle), _)) => {
+            eth_bundle.transaction_hash_list().map(Some)
+        }
+        _ => Ok(None),
+    }
+}
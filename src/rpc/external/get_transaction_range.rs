@@ -0,0 +1,99 @@
+use radius_sdk::kvstore::KvStoreError;
+
+use crate::rpc::prelude::*;
+
+/// Maximum number of transaction orders returned by a single request.
+pub const MAX_TRANSACTION_RANGE_LENGTH: u64 = 128;
+
+/// Returns the stored transactions of a rollup block with an order in
+/// `start_transaction_order..end_transaction_order`. Orders that are not
+/// stored are left out.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionRange {
+    pub rollup_id: String,
+    pub rollup_block_height: u64,
+    pub start_transaction_order: u64,
+    pub end_transaction_order: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionRangeResponse {
+    pub transaction_list: Vec<TransactionRangeEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TransactionRangeEntry {
+    pub transaction_order: u64,
+    pub encrypted_transaction: Option<EncryptedTransaction>,
+    pub raw_transaction: Option<RawTransaction>,
+    pub is_direct_sent: bool,
+    pub order_commitment: Option<OrderCommitment>,
+}
+
+impl RpcParameter<AppState> for GetTransactionRange {
+    type Response = GetTransactionRangeResponse;
+
+    fn method() -> &'static str {
+        "get_transaction_range"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let end_transaction_order = self.end_transaction_order.min(
+            self.start_transaction_order
+                .saturating_add(MAX_TRANSACTION_RANGE_LENGTH),
+        );
+
+        let mut transaction_list = Vec::new();
+        for transaction_order in self.start_transaction_order..end_transaction_order {
+            let encrypted_transaction = optional(EncryptedTransactionModel::get(
+                &self.rollup_id,
+                self.rollup_block_height,
+                transaction_order,
+            ))?;
+
+            let raw_transaction_info = optional(RawTransactionModel::get(
+                &self.rollup_id,
+                self.rollup_block_height,
+                transaction_order,
+            ))?;
+
+            if encrypted_transaction.is_none() && raw_transaction_info.is_none() {
+                continue;
+            }
+
+            let order_commitment = optional(OrderCommitment::get(
+                &self.rollup_id,
+                self.rollup_block_height,
+                transaction_order,
+            ))?;
+
+            let (raw_transaction, is_direct_sent) = match raw_transaction_info {
+                Some((raw_transaction, is_direct_sent)) => (Some(raw_transaction), is_direct_sent),
+                None => (None, false),
+            };
+
+            transaction_list.push(TransactionRangeEntry {
+                transaction_order,
+                encrypted_transaction,
+                raw_transaction,
+                is_direct_sent,
+                order_commitment,
+            });
+        }
+
+        Ok(GetTransactionRangeResponse { transaction_list })
+    }
+}
+
+pub(crate) fn optional<T>(result: Result<T, KvStoreError>) -> Result<Option<T>, KvStoreError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => {
+            if error.is_none_type() {
+                Ok(None)
+            } else {
+                Err(error)
+            }
+        }
+    }
+}
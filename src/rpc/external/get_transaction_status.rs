@@ -0,0 +1,392 @@
+use super::get_transaction_range::optional;
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetTransactionStatus {
+    pub rollup_id: String,
+    pub transaction_hash: String,
+}
+
+/// Furthest stage of a transaction known to this sequencer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// Stored, but not ordered by this sequencer.
+    Received,
+    Ordered {
+        rollup_block_height: u64,
+        transaction_order: u64,
+        order_commitment: Option<OrderCommitment>,
+    },
+    /// Ordered and sent to, or received from, the other sequencers.
+    Synced {
+        rollup_block_height: u64,
+        transaction_order: u64,
+        order_commitment: Option<OrderCommitment>,
+    },
+    /// The encrypted transaction was decrypted for the block being built.
+    Decrypted {
+        rollup_block_height: u64,
+        transaction_order: u64,
+    },
+    /// `transaction_order` is the Merkle leaf of the transaction, while
+    /// `transaction_index` is the position of its first transaction in the
+    /// block's `raw_transaction_list`.
+    IncludedInBlock {
+        rollup_block_height: u64,
+        transaction_order: u64,
+        transaction_index: u64,
+    },
+    /// Ordered in the block, but left out of its raw transactions.
+    Invalid {
+        rollup_block_height: u64,
+        transaction_order: u64,
+        reason: InvalidTransactionReason,
+    },
+    CommitmentSubmitted {
+        rollup_block_height: u64,
+        transaction_order: u64,
+        transaction_index: u64,
+        commitment_transaction_hash: String,
+    },
+    Rejected {
+        reason: String,
+    },
+}
+
+impl RpcParameter<AppState> for GetTransactionStatus {
+    type Response = TransactionStatus;
+
+    fn method() -> &'static str {
+        "get_transaction_status"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        Ok(get_transaction_status(
+            &self.rollup_id,
+            &self.transaction_hash,
+        )?)
+    }
+}
+
+pub fn get_transaction_status(
+    rollup_id: &str,
+    transaction_hash: &str,
+) -> Result<TransactionStatus, Error> {
+    // A raw transaction takes precedence over an encrypted one claiming its
+    // hash.
+    for transaction_kind in [TransactionKind::Raw, TransactionKind::Encrypted] {
+        if let Some(transaction_lifecycle) = optional(TransactionLifecycle::get(
+            rollup_id,
+            transaction_kind,
+            transaction_hash,
+        ))
+        .map_err(Error::Database)?
+        {
+            return get_ordered_transaction_status(
+                rollup_id,
+                transaction_hash,
+                transaction_lifecycle,
+            );
+        }
+    }
+
+    if let Some(rejected_transaction) =
+        optional(RejectedTransaction::get(rollup_id, transaction_hash)).map_err(Error::Database)?
+    {
+        return Ok(TransactionStatus::Rejected {
+            reason: rejected_transaction.reason,
+        });
+    }
+
+    let is_received = optional(EncryptedTransactionModel::get_with_transaction_hash(
+        rollup_id,
+        transaction_hash,
+    ))
+    .map_err(Error::Database)?
+    .is_some()
+        || optional(RawTransactionModel::get_with_transaction_hash(
+            rollup_id,
+            transaction_hash,
+        ))
+        .map_err(Error::Database)?
+        .is_some();
+    if is_received {
+        return Ok(TransactionStatus::Received);
+    }
+
+    Err(Error::TransactionNotFound)
+}
+
+fn get_ordered_transaction_status(
+    rollup_id: &str,
+    transaction_hash: &str,
+    transaction_lifecycle: TransactionLifecycle,
+) -> Result<TransactionStatus, Error> {
+    let rollup_block_height = transaction_lifecycle.rollup_block_height;
+    let transaction_order = transaction_lifecycle.transaction_order;
+
+    let block = optional(Block::get(rollup_id, rollup_block_height)).map_err(Error::Database)?;
+    // A block holding another transaction at the order was not built from the
+    // one indexed, so the status falls back to the order.
+    let is_included = match &block {
+        Some(block) => block
+            .merkle_leaf_list()?
+            .get(transaction_order as usize)
+            .is_some_and(|leaf| AsRef::<str>::as_ref(leaf) == transaction_hash),
+        None => false,
+    };
+
+    if let (Some(block), true) = (block, is_included) {
+        if let Some(invalid_transaction) = block
+            .invalid_transaction_list
+            .iter()
+            .find(|invalid_transaction| invalid_transaction.transaction_order == transaction_order)
+        {
+            return Ok(TransactionStatus::Invalid {
+                rollup_block_height,
+                transaction_order,
+                reason: invalid_transaction.reason.clone(),
+            });
+        }
+
+        let transaction_index = get_transaction_index(
+            rollup_id,
+            rollup_block_height,
+            &block.raw_transaction_list,
+            transaction_hash,
+            transaction_order,
+        )?;
+
+        let commitment_transaction_hash =
+            optional(BlockBuildJob::get(rollup_id, rollup_block_height))
+                .map_err(Error::Database)?
+                .and_then(|block_build_job| block_build_job.commitment_transaction_hash);
+
+        return Ok(match commitment_transaction_hash {
+            Some(commitment_transaction_hash) => TransactionStatus::CommitmentSubmitted {
+                rollup_block_height,
+                transaction_order,
+                transaction_index,
+                commitment_transaction_hash,
+            },
+            None => TransactionStatus::IncludedInBlock {
+                rollup_block_height,
+                transaction_order,
+                transaction_index,
+            },
+        });
+    }
+
+    // The block builder stores the decryption of an encrypted transaction as
+    // the raw transaction of the same order.
+    let is_encrypted = optional(EncryptedTransactionModel::get(
+        rollup_id,
+        rollup_block_height,
+        transaction_order,
+    ))
+    .map_err(Error::Database)?
+    .is_some();
+    if is_encrypted
+        && optional(RawTransactionModel::get(
+            rollup_id,
+            rollup_block_height,
+            transaction_order,
+        ))
+        .map_err(Error::Database)?
+        .is_some()
+    {
+        return Ok(TransactionStatus::Decrypted {
+            rollup_block_height,
+            transaction_order,
+        });
+    }
+
+    let order_commitment = optional(OrderCommitment::get(
+        rollup_id,
+        rollup_block_height,
+        transaction_order,
+    ))
+    .map_err(Error::Database)?;
+
+    if transaction_lifecycle.is_synced {
+        Ok(TransactionStatus::Synced {
+            rollup_block_height,
+            transaction_order,
+            order_commitment,
+        })
+    } else {
+        Ok(TransactionStatus::Ordered {
+            rollup_block_height,
+            transaction_order,
+            order_commitment,
+        })
+    }
+}
+
+/// Returns the position of the first transaction of `transaction_order` in the
+/// block's `raw_transaction_list`, which leaves out invalid orders and expands
+/// bundles.
+fn get_transaction_index(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    raw_transaction_list: &[RawTransaction],
+    transaction_hash: &str,
+    transaction_order: u64,
+) -> Result<u64, Error> {
+    // Only a bundle is stored under a hash other than the one of its first
+    // transaction.
+    let first_transaction_hash =
+        match find_transaction_index(raw_transaction_list, transaction_hash)? {
+            Some(transaction_index) => return Ok(transaction_index),
+            None => {
+                let (raw_transaction, _) =
+                    RawTransactionModel::get(rollup_id, rollup_block_height, transaction_order)
+                        .map_err(Error::Database)?;
+                match raw_transaction.expand().first() {
+                    Some(first_transaction) => first_transaction.raw_transaction_hash()?,
+                    None => return Err(Error::TransactionNotFound),
+                }
+            }
+        };
+
+    find_transaction_index(raw_transaction_list, first_transaction_hash.as_ref())?
+        .ok_or(Error::TransactionNotFound)
+}
+
+fn find_transaction_index(
+    raw_transaction_list: &[RawTransaction],
+    transaction_hash: &str,
+) -> Result<Option<u64>, Error> {
+    for (transaction_index, raw_transaction) in raw_transaction_list.iter().enumerate() {
+        if AsRef::<str>::as_ref(&raw_transaction.raw_transaction_hash()?) == transaction_hash {
+            return Ok(Some(transaction_index as u64));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{init_test_database, sign_eth_transaction};
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn raw_transaction(nonce: u64) -> RawTransaction {
+        RawTransaction::from(sign_eth_transaction(SIGNING_KEY, Some(1), nonce))
+    }
+
+    fn transaction_hash(raw_transaction: &RawTransaction) -> String {
+        AsRef::<str>::as_ref(&raw_transaction.raw_transaction_hash().unwrap()).to_owned()
+    }
+
+    #[test]
+    fn transaction_index_is_the_position_in_the_block() {
+        let raw_transaction_list: Vec<RawTransaction> = (0..3).map(raw_transaction).collect();
+
+        for (transaction_order, raw_transaction) in raw_transaction_list.iter().enumerate() {
+            assert_eq!(
+                get_transaction_index(
+                    "get_transaction_status_single",
+                    0,
+                    &raw_transaction_list,
+                    &transaction_hash(raw_transaction),
+                    transaction_order as u64,
+                )
+                .unwrap(),
+                transaction_order as u64
+            );
+        }
+    }
+
+    #[test]
+    fn transaction_index_of_a_bundle_is_its_first_transaction() {
+        init_test_database();
+        let rollup_id = "get_transaction_status_bundle";
+        let bundle = RawTransaction::from(EthRawBundleTransaction::from(
+            (1..4)
+                .map(|nonce| sign_eth_transaction(SIGNING_KEY, Some(1), nonce))
+                .collect::<Vec<_>>(),
+        ));
+        RawTransactionModel::put(rollup_id, 0, 2, bundle.clone(), false).unwrap();
+        // The transaction of order 1 was left out of the block.
+        let raw_transaction_list: Vec<RawTransaction> = std::iter::once(raw_transaction(0))
+            .chain(bundle.clone().expand())
+            .chain(std::iter::once(raw_transaction(4)))
+            .collect();
+
+        assert_eq!(
+            get_transaction_index(
+                rollup_id,
+                0,
+                &raw_transaction_list,
+                &transaction_hash(&bundle),
+                2
+            )
+            .unwrap(),
+            1
+        );
+        assert_eq!(
+            get_transaction_index(
+                rollup_id,
+                0,
+                &raw_transaction_list,
+                &transaction_hash(&raw_transaction(4)),
+                3
+            )
+            .unwrap(),
+            4
+        );
+    }
+
+    #[test]
+    fn transaction_index_requires_the_transaction_in_the_block() {
+        init_test_database();
+        let rollup_id = "get_transaction_status_missing";
+        RawTransactionModel::put(rollup_id, 0, 1, raw_transaction(1), false).unwrap();
+
+        assert!(matches!(
+            get_transaction_index(
+                rollup_id,
+                0,
+                &[raw_transaction(0)],
+                &transaction_hash(&raw_transaction(1)),
+                1
+            ),
+            Err(Error::TransactionNotFound)
+        ));
+    }
+
+    #[test]
+    fn status_outlives_the_pruned_order_index() {
+        init_test_database();
+        let rollup_id = "get_transaction_status_pruned";
+        let pruned_transaction = raw_transaction(0);
+        let transaction_hash = pruned_transaction.raw_transaction_hash().unwrap();
+        RawTransactionModel::put(rollup_id, 0, 0, pruned_transaction, false).unwrap();
+        TransactionOrderIndex::record(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash,
+            0,
+            0,
+            true,
+        )
+        .unwrap();
+
+        TransactionOrderIndex::delete(rollup_id, TransactionKind::Raw, transaction_hash.as_ref())
+            .unwrap();
+
+        assert!(matches!(
+            get_transaction_status(rollup_id, transaction_hash.as_ref()),
+            Ok(TransactionStatus::Synced {
+                rollup_block_height: 0,
+                transaction_order: 0,
+                ..
+            })
+        ));
+    }
+}
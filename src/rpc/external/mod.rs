@@ -1,29 +1,55 @@
 mod get_block;
+mod get_block_build_job;
 mod get_block_height;
+mod get_block_mismatch;
+mod get_block_mismatch_list;
 mod get_encrypted_transaction_list;
 mod get_encrypted_transaction_with_order_commitment;
 mod get_encrypted_transaction_with_transaction_hash;
+mod get_equivocation_evidence;
+mod get_equivocation_evidence_list;
 mod get_order_commitment;
 mod get_raw_transaction_list;
 mod get_raw_transaction_with_order_commitment;
 mod get_raw_transaction_with_transaction_hash;
 mod get_rollup;
 mod get_rollup_metadata;
+mod get_sender_nonce;
+mod get_transaction_order_status;
+mod get_transaction_proof;
+mod get_transaction_range;
+mod get_transaction_status;
 mod get_version;
 mod send_encrypted_transaction;
+mod send_encrypted_transaction_batch;
 mod send_raw_transaction;
+mod send_raw_transaction_batch;
+mod verify_order_commitment;
 
 pub use get_block::*;
+pub use get_block_build_job::*;
 pub use get_block_height::*;
+pub use get_block_mismatch::*;
+pub use get_block_mismatch_list::*;
 pub use get_encrypted_transaction_list::*;
 pub use get_encrypted_transaction_with_order_commitment::*;
 pub use get_encrypted_transaction_with_transaction_hash::*;
+pub use get_equivocation_evidence::*;
+pub use get_equivocation_evidence_list::*;
 pub use get_order_commitment::*;
 pub use get_raw_transaction_list::*;
 pub use get_raw_transaction_with_order_commitment::*;
 pub use get_raw_transaction_with_transaction_hash::*;
 pub use get_rollup::*;
 pub use get_rollup_metadata::*;
+pub use get_sender_nonce::*;
+pub use get_transaction_order_status::*;
+pub use get_transaction_proof::*;
+pub use get_transaction_range::*;
+pub use get_transaction_status::*;
 pub use get_version::*;
 pub use send_encrypted_transaction::*;
+pub use send_encrypted_transaction_batch::*;
 pub use send_raw_transaction::*;
+pub use send_raw_transaction_batch::*;
+pub use verify_order_commitment::*;
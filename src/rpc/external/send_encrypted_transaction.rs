@@ -1,8 +1,11 @@
+use std::collections::HashSet;
+
 use crate::{
     rpc::{
         cluster::{SyncEncryptedTransaction, SyncEncryptedTransactionMessage},
         prelude::*,
     },
+    task::check_skde_key_id,
     types::*,
 };
 
@@ -26,6 +29,7 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
         check_supported_encrypted_transaction(&rollup, &self.encrypted_transaction)?;
 
         let transaction_gas_limit = self.encrypted_transaction.get_transaction_gas_limit()?;
+        let sender_nonce_list = self.encrypted_transaction.sender_nonce_list()?;
 
         // 2. Check is leader
         let mut rollup_metadata = RollupMetadata::get_mut(&self.rollup_id)?;
@@ -41,11 +45,51 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
             let transaction_order = rollup_metadata.transaction_order;
             let transaction_hash = self.encrypted_transaction.raw_transaction_hash();
 
+            if let Some(order_commitment) = get_duplicate_order_commitment(
+                &context,
+                &self.rollup_id,
+                TransactionKind::Encrypted,
+                &transaction_hash,
+                rollup_block_height,
+            )? {
+                return Ok(order_commitment);
+            }
+
+            // The order commitment only binds the sequencer if the
+            // transaction cannot be decrypted yet.
+            if let Some(key_id) = self.encrypted_transaction.key_id() {
+                check_skde_key_id(&context, key_id).await.map_err(|error| {
+                    RejectedTransaction::record(
+                        &self.rollup_id,
+                        std::slice::from_ref(&transaction_hash),
+                        error,
+                    )
+                })?;
+            }
+
+            // Checked before the transaction is stored or takes its order, and recorded
+            // once it is stored.
+            let next_nonce_map =
+                SenderNonce::check(&self.rollup_id, rollup.nonce_policy, &sender_nonce_list)
+                    .map_err(|error| {
+                        RejectedTransaction::record(
+                            &self.rollup_id,
+                            std::slice::from_ref(&transaction_hash),
+                            error,
+                        )
+                    })?;
+
             if rollup_metadata.max_gas_limit != 0
-                && rollup_metadata.current_gas + transaction_gas_limit
+                && rollup_metadata
+                    .current_gas
+                    .saturating_add(transaction_gas_limit)
                     > rollup_metadata.max_gas_limit
             {
-                return Err(Error::ExceedMaxGasLimit)?;
+                return Err(RejectedTransaction::record(
+                    &self.rollup_id,
+                    std::slice::from_ref(&transaction_hash),
+                    Error::ExceedMaxGasLimit,
+                ))?;
             }
 
             EncryptedTransactionModel::put_with_transaction_hash(
@@ -53,34 +97,53 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
                 &transaction_hash,
                 &self.encrypted_transaction,
             )?;
-
             EncryptedTransactionModel::put(
                 &self.rollup_id,
                 rollup_block_height,
                 transaction_order,
                 &self.encrypted_transaction,
             )?;
+            SenderNonce::record_next_nonce(&self.rollup_id, &next_nonce_map)?;
 
             let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
+
+            let merkle_hash_scheme = merkle_tree.hash_scheme();
             let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
 
-            rollup_metadata.current_gas += transaction_gas_limit;
+            rollup_metadata.current_gas = rollup_metadata
+                .current_gas
+                .saturating_add(transaction_gas_limit);
             rollup_metadata.transaction_order += 1;
             rollup_metadata.update()?;
             drop(merkle_tree);
 
             let order_commitment = issue_order_commitment(
                 context.clone(),
-                rollup.platform,
-                self.rollup_id.clone(),
-                rollup.order_commitment_type,
-                transaction_hash,
+                &rollup,
+                transaction_hash.clone(),
                 rollup_block_height,
                 transaction_order,
                 pre_merkle_path,
+                merkle_hash_scheme,
+                self.encrypted_transaction.key_id(),
             )
             .await?;
             order_commitment.put(&self.rollup_id, rollup_block_height, transaction_order)?;
+            TransactionOrderIndex::record(
+                &self.rollup_id,
+                TransactionKind::Encrypted,
+                &transaction_hash,
+                rollup_block_height,
+                transaction_order,
+                false,
+            )?;
+            context.subscription_manager().publish_order_commitment(
+                &self.rollup_id,
+                rollup_block_height,
+                transaction_order,
+                &transaction_hash,
+                &order_commitment,
+            );
 
             // Sync Transaction
             sync_encrypted_transaction(
@@ -126,12 +189,21 @@ impl RpcParameter<AppState> for SendEncryptedTransaction {
     }
 }
 
-fn check_supported_encrypted_transaction(
+pub fn check_supported_encrypted_transaction(
     rollup: &Rollup,
     encrypted_transaction: &EncryptedTransaction,
 ) -> Result<(), Error> {
     match rollup.encrypted_transaction_type {
-        EncryptedTransactionType::Pvde => {}
+        EncryptedTransactionType::Pvde => {
+            let EncryptedTransaction::Pvde(pvde_encrypted_transaction) = encrypted_transaction
+            else {
+                return Err(Error::UnsupportedEncryptedMempool);
+            };
+
+            pvde_encrypted_transaction
+                .time_lock_puzzle
+                .check_params(rollup.time_lock_puzzle_params.as_ref())?;
+        }
         EncryptedTransactionType::Skde => {
             if !matches!(encrypted_transaction, EncryptedTransaction::Skde(_)) {
                 return Err(Error::UnsupportedEncryptedMempool);
@@ -155,6 +227,8 @@ pub fn sync_encrypted_transaction(
     order_commitment: OrderCommitment,
 ) {
     tokio::spawn(async move {
+        let synced_rollup_id = rollup_id.clone();
+        let transaction_hash_list = vec![encrypted_transaction.raw_transaction_hash()];
         let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
 
         if !other_cluster_rpc_url_list.is_empty() {
@@ -165,13 +239,11 @@ pub fn sync_encrypted_transaction(
                 encrypted_transaction,
                 order_commitment,
             };
-            let signature = context
-                .get_signer(platform)
-                .await
-                .unwrap()
-                .sign_message(&message)
-                .unwrap();
-            let rpc_self = SyncEncryptedTransaction { message, signature };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncEncryptedTransaction::method(), &message)
+                    .unwrap();
+            let rpc_self = SyncEncryptedTransaction { message, envelope };
 
             context
                 .rpc_client()
@@ -184,38 +256,115 @@ pub fn sync_encrypted_transaction(
                 .await
                 .unwrap();
         }
+
+        TransactionOrderIndex::mark_synced(
+            &synced_rollup_id,
+            TransactionKind::Encrypted,
+            &transaction_hash_list,
+        );
     });
 }
 
+/// Returns the order commitment of a transaction already ordered within the
+/// last `duplicate_transaction_retention` rollup blocks.
+pub fn get_duplicate_order_commitment(
+    context: &AppState,
+    rollup_id: &str,
+    transaction_kind: TransactionKind,
+    transaction_hash: &RawTransactionHash,
+    rollup_block_height: u64,
+) -> Result<Option<OrderCommitment>, Error> {
+    let transaction_order_index = match TransactionOrderIndex::get_within(
+        rollup_id,
+        transaction_kind,
+        transaction_hash,
+        rollup_block_height,
+        context.config().duplicate_transaction_retention,
+    )
+    .map_err(Error::Database)?
+    {
+        Some(transaction_order_index) => transaction_order_index,
+        None => return Ok(None),
+    };
+
+    OrderCommitment::get(
+        rollup_id,
+        transaction_order_index.rollup_block_height,
+        transaction_order_index.transaction_order,
+    )
+    .map(Some)
+    .map_err(Error::Database)
+}
+
+/// Rejects a batch containing a transaction twice or one already ordered
+/// within the last `duplicate_transaction_retention` rollup blocks.
+pub fn check_duplicate_transaction_list(
+    rollup_id: &str,
+    transaction_kind: TransactionKind,
+    transaction_hash_list: &[RawTransactionHash],
+    rollup_block_height: u64,
+    duplicate_transaction_retention: u64,
+) -> Result<(), Error> {
+    let mut transaction_hash_set = HashSet::new();
+    for transaction_hash in transaction_hash_list {
+        if !transaction_hash_set.insert(transaction_hash)
+            || TransactionOrderIndex::get_within(
+                rollup_id,
+                transaction_kind,
+                transaction_hash,
+                rollup_block_height,
+                duplicate_transaction_retention,
+            )
+            .map_err(Error::Database)?
+            .is_some()
+        {
+            return Err(Error::DuplicateTransaction);
+        }
+    }
+
+    Ok(())
+}
+
+/// `key_id` is the key id an SKDE transaction is encrypted under, which a
+/// signed order commitment records.
 #[allow(clippy::too_many_arguments)]
 pub async fn issue_order_commitment(
     context: AppState,
-    platform: Platform,
-    rollup_id: String,
-    order_commitment_type: OrderCommitmentType,
+    rollup: &Rollup,
     transaction_hash: RawTransactionHash,
     rollup_block_height: u64,
     transaction_order: u64,
     pre_merkle_path: Vec<[u8; 32]>,
+    merkle_hash_scheme: MerkleHashScheme,
+    key_id: Option<u64>,
 ) -> Result<OrderCommitment, RpcError> {
-    match order_commitment_type {
+    match rollup.order_commitment_type {
         OrderCommitmentType::TransactionHash => Ok(OrderCommitment::Single(
             SingleOrderCommitment::TransactionHash(TransactionHashOrderCommitment::new(
                 transaction_hash.as_string(),
             )),
         )),
         OrderCommitmentType::Sign => {
-            let signer = context.get_signer(platform).await?;
+            let signer = context.get_signer(rollup.platform).await?;
+            let signature_scheme = rollup.get_signature_scheme(rollup_block_height)?;
             let order_commitment_data = OrderCommitmentData {
-                rollup_id,
+                rollup_id: rollup.rollup_id.clone(),
                 block_height: rollup_block_height,
                 transaction_hash: transaction_hash.as_string(),
                 transaction_order,
                 pre_merkle_path: pre_merkle_path,
+                merkle_hash_scheme,
+                key_id,
+                signature_scheme: signature_scheme.clone(),
             };
+            let signature = signature_scheme.sign(
+                &signer,
+                &context.config().signing_key,
+                &order_commitment_data,
+            )?;
             let order_commitment = SignOrderCommitment {
-                data: order_commitment_data.clone(),
-                signature: signer.sign_message(&order_commitment_data)?.as_hex_string(),
+                data: order_commitment_data,
+                signature: signature.as_hex_string(),
             };
 
             Ok(OrderCommitment::Single(SingleOrderCommitment::Sign(
@@ -224,3 +373,76 @@ pub async fn issue_order_commitment(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    #[test]
+    fn batch_with_a_transaction_twice_is_rejected() {
+        init_test_database();
+        let transaction_hash_list = [
+            RawTransactionHash::from([1; 32]),
+            RawTransactionHash::from([2; 32]),
+            RawTransactionHash::from([1; 32]),
+        ];
+
+        assert!(matches!(
+            check_duplicate_transaction_list(
+                "duplicate_transaction_in_batch",
+                TransactionKind::Raw,
+                &transaction_hash_list,
+                0,
+                10,
+            ),
+            Err(Error::DuplicateTransaction)
+        ));
+    }
+
+    #[test]
+    fn batch_with_an_ordered_transaction_is_rejected_within_the_retention() {
+        init_test_database();
+        let rollup_id = "duplicate_transaction_ordered";
+        let transaction_hash_list = [
+            RawTransactionHash::from([1; 32]),
+            RawTransactionHash::from([2; 32]),
+        ];
+        TransactionOrderIndex::record(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash_list[1],
+            3,
+            0,
+            false,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            check_duplicate_transaction_list(
+                rollup_id,
+                TransactionKind::Raw,
+                &transaction_hash_list,
+                4,
+                2,
+            ),
+            Err(Error::DuplicateTransaction)
+        ));
+        assert!(check_duplicate_transaction_list(
+            rollup_id,
+            TransactionKind::Encrypted,
+            &transaction_hash_list,
+            4,
+            2,
+        )
+        .is_ok());
+        assert!(check_duplicate_transaction_list(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash_list,
+            5,
+            2,
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{
+    rpc::{
+        cluster::{SyncEncryptedTransactionBatch, SyncEncryptedTransactionBatchMessage},
+        external::{
+            check_duplicate_transaction_list, check_supported_encrypted_transaction,
+            issue_order_commitment,
+        },
+        prelude::*,
+    },
+    task::check_skde_key_id,
+    types::*,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SendEncryptedTransactionBatch {
+    pub rollup_id: String,
+    pub encrypted_transaction_list: Vec<EncryptedTransaction>,
+}
+
+impl RpcParameter<AppState> for SendEncryptedTransactionBatch {
+    type Response = BundleOrderCommitment;
+
+    fn method() -> &'static str {
+        "send_encrypted_transaction_batch"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        if self.encrypted_transaction_list.is_empty() {
+            return Err(Error::EmptyTransactionList)?;
+        }
+
+        let rollup = Rollup::get(&self.rollup_id)?;
+
+        // 1. Check supported encrypted transactions
+        for encrypted_transaction in self.encrypted_transaction_list.iter() {
+            check_supported_encrypted_transaction(&rollup, encrypted_transaction)?;
+        }
+        let batch_gas_limit = get_batch_gas_limit(
+            self.encrypted_transaction_list
+                .iter()
+                .map(EncryptedTransaction::get_transaction_gas_limit),
+        )?;
+        let mut sender_nonce_list = Vec::new();
+        for encrypted_transaction in self.encrypted_transaction_list.iter() {
+            sender_nonce_list.extend(encrypted_transaction.sender_nonce_list()?);
+        }
+
+        // 2. Check is leader
+        let mut rollup_metadata = RollupMetadata::get_mut(&self.rollup_id)?;
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.service_provider,
+            &rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+        let rollup_block_height = rollup_metadata.rollup_block_height;
+
+        if rollup_metadata.is_leader {
+            let transaction_hash_list: Vec<RawTransactionHash> = self
+                .encrypted_transaction_list
+                .iter()
+                .map(|encrypted_transaction| encrypted_transaction.raw_transaction_hash())
+                .collect();
+            check_duplicate_transaction_list(
+                &self.rollup_id,
+                TransactionKind::Encrypted,
+                &transaction_hash_list,
+                rollup_block_height,
+                context.config().duplicate_transaction_retention,
+            )?;
+
+            // The order commitments only bind the sequencer if the
+            // transactions cannot be decrypted yet.
+            for key_id in self
+                .encrypted_transaction_list
+                .iter()
+                .filter_map(|encrypted_transaction| encrypted_transaction.key_id())
+            {
+                check_skde_key_id(&context, key_id).await.map_err(|error| {
+                    RejectedTransaction::record(&self.rollup_id, &transaction_hash_list, error)
+                })?;
+            }
+
+            // Checked before the batch is stored or takes its orders, and recorded once
+            // it is stored.
+            let next_nonce_map =
+                SenderNonce::check(&self.rollup_id, rollup.nonce_policy, &sender_nonce_list)
+                    .map_err(|error| {
+                        RejectedTransaction::record(&self.rollup_id, &transaction_hash_list, error)
+                    })?;
+
+            // The whole batch takes consecutive orders under a single lock.
+            let first_transaction_order = rollup_metadata
+                .take_transaction_order(
+                    self.encrypted_transaction_list.len() as u64,
+                    batch_gas_limit,
+                )
+                .map_err(|error| {
+                    RejectedTransaction::record(&self.rollup_id, &transaction_hash_list, error)
+                })?;
+            let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
+            let merkle_hash_scheme = merkle_tree.hash_scheme();
+            let mut ordered_transaction_list =
+                Vec::with_capacity(self.encrypted_transaction_list.len());
+
+            for (index, encrypted_transaction) in self.encrypted_transaction_list.iter().enumerate()
+            {
+                let transaction_order = first_transaction_order + index as u64;
+                let transaction_hash = encrypted_transaction.raw_transaction_hash();
+
+                EncryptedTransactionModel::put_with_transaction_hash(
+                    &self.rollup_id,
+                    &transaction_hash,
+                    encrypted_transaction,
+                )?;
+                EncryptedTransactionModel::put(
+                    &self.rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    encrypted_transaction,
+                )?;
+
+                let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
+                ordered_transaction_list.push((
+                    transaction_order,
+                    transaction_hash,
+                    pre_merkle_path,
+                    encrypted_transaction.key_id(),
+                ));
+            }
+            SenderNonce::record_next_nonce(&self.rollup_id, &next_nonce_map)?;
+
+            rollup_metadata.update()?;
+            drop(merkle_tree);
+
+            let mut order_commitment_list = Vec::with_capacity(ordered_transaction_list.len());
+            for (transaction_order, transaction_hash, pre_merkle_path, key_id) in
+                ordered_transaction_list
+            {
+                let order_commitment = issue_order_commitment(
+                    context.clone(),
+                    &rollup,
+                    transaction_hash.clone(),
+                    rollup_block_height,
+                    transaction_order,
+                    pre_merkle_path,
+                    merkle_hash_scheme,
+                    key_id,
+                )
+                .await?;
+                order_commitment.put(&self.rollup_id, rollup_block_height, transaction_order)?;
+                TransactionOrderIndex::record(
+                    &self.rollup_id,
+                    TransactionKind::Encrypted,
+                    &transaction_hash,
+                    rollup_block_height,
+                    transaction_order,
+                    false,
+                )?;
+                context.subscription_manager().publish_order_commitment(
+                    &self.rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    &transaction_hash,
+                    &order_commitment,
+                );
+
+                if let OrderCommitment::Single(single_order_commitment) = order_commitment {
+                    order_commitment_list.push(single_order_commitment);
+                }
+            }
+
+            let bundle_order_commitment = issue_bundle_order_commitment(
+                context.clone(),
+                &rollup,
+                rollup_block_height,
+                first_transaction_order,
+                order_commitment_list,
+            )
+            .await?;
+
+            // Sync the batch
+            sync_encrypted_transaction_batch(
+                cluster,
+                context.clone(),
+                rollup.platform,
+                self.rollup_id.clone(),
+                rollup_block_height,
+                first_transaction_order,
+                self.encrypted_transaction_list,
+                bundle_order_commitment.clone(),
+            );
+
+            Ok(bundle_order_commitment)
+        } else {
+            let leader_external_rpc_url = rollup_metadata
+                .leader_sequencer_rpc_info
+                .external_rpc_url
+                .clone()
+                .ok_or(Error::EmptyLeaderClusterRpcUrl)?;
+            drop(rollup_metadata);
+
+            match context
+                .rpc_client()
+                .request(
+                    leader_external_rpc_url,
+                    SendEncryptedTransactionBatch::method(),
+                    &self,
+                    Id::Null,
+                )
+                .await
+            {
+                Ok(response) => Ok(response),
+                Err(error) => {
+                    tracing::error!(
+                        "Send encrypted transaction batch - leader external rpc error: {:?}",
+                        error
+                    );
+                    Err(error.into())
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sync_encrypted_transaction_batch(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    rollup_block_height: u64,
+    first_transaction_order: u64,
+    encrypted_transaction_list: Vec<EncryptedTransaction>,
+    bundle_order_commitment: BundleOrderCommitment,
+) {
+    tokio::spawn(async move {
+        let synced_rollup_id = rollup_id.clone();
+        let transaction_hash_list: Vec<RawTransactionHash> = encrypted_transaction_list
+            .iter()
+            .map(|encrypted_transaction| encrypted_transaction.raw_transaction_hash())
+            .collect();
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncEncryptedTransactionBatchMessage {
+                rollup_id,
+                rollup_block_height,
+                first_transaction_order,
+                encrypted_transaction_list,
+                bundle_order_commitment,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncEncryptedTransactionBatch::method(), &message)
+                    .unwrap();
+            let rpc_self = SyncEncryptedTransactionBatch { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncEncryptedTransactionBatch::method(),
+                    &rpc_self,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+
+        TransactionOrderIndex::mark_synced(
+            &synced_rollup_id,
+            TransactionKind::Encrypted,
+            &transaction_hash_list,
+        );
+    });
+}
+
+/// Signs the list of order commitments issued for a batch as a whole, with
+/// the signature scheme of the block.
+pub async fn issue_bundle_order_commitment(
+    context: AppState,
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    first_transaction_order: u64,
+    order_commitment_list: Vec<SingleOrderCommitment>,
+) -> Result<BundleOrderCommitment, RpcError> {
+    let signer = context.get_signer(rollup.platform).await?;
+    let signature_scheme = rollup.get_signature_scheme(rollup_block_height)?;
+
+    Ok(BundleOrderCommitment::sign(
+        &signature_scheme,
+        &signer,
+        &context.config().signing_key,
+        &rollup.rollup_id,
+        rollup_block_height,
+        first_transaction_order,
+        order_commitment_list,
+    )?)
+}
@@ -1,7 +1,7 @@
 use crate::{
     rpc::{
         cluster::{SyncRawTransaction, SyncRawTransactionMessage},
-        external::issue_order_commitment,
+        external::{get_duplicate_order_commitment, issue_order_commitment},
         prelude::*,
     },
     types::*,
@@ -30,6 +30,9 @@ impl RpcParameter<AppState> for SendRawTransaction {
         let transaction_gas_limit = self.raw_transaction.get_transaction_gas_limit()?;
 
         let rollup = Rollup::get(&self.rollup_id)?;
+        // Rejected before the transaction takes an order and gas budget.
+        let sender_nonce_list = self.raw_transaction.validate(rollup.chain_id)?;
+
         let mut rollup_metadata = RollupMetadata::get_mut(&self.rollup_id)?;
         let cluster = Cluster::get(
             rollup.platform,
@@ -47,13 +50,41 @@ impl RpcParameter<AppState> for SendRawTransaction {
 
         if rollup_metadata.is_leader {
             let transaction_order = rollup_metadata.transaction_order;
-            let transaction_hash = self.raw_transaction.raw_transaction_hash();
+            let transaction_hash = self.raw_transaction.raw_transaction_hash()?;
+
+            if let Some(order_commitment) = get_duplicate_order_commitment(
+                &context,
+                &self.rollup_id,
+                TransactionKind::Raw,
+                &transaction_hash,
+                rollup_block_height,
+            )? {
+                return Ok(order_commitment);
+            }
+
+            // Checked before the transaction is stored or takes its order, and recorded
+            // once it is stored.
+            let next_nonce_map =
+                SenderNonce::check(&self.rollup_id, rollup.nonce_policy, &sender_nonce_list)
+                    .map_err(|error| {
+                        RejectedTransaction::record(
+                            &self.rollup_id,
+                            std::slice::from_ref(&transaction_hash),
+                            error,
+                        )
+                    })?;
 
             if rollup_metadata.max_gas_limit != 0
-                && rollup_metadata.current_gas + transaction_gas_limit
+                && rollup_metadata
+                    .current_gas
+                    .saturating_add(transaction_gas_limit)
                     > rollup_metadata.max_gas_limit
             {
-                return Err(Error::ExceedMaxGasLimit)?;
+                return Err(RejectedTransaction::record(
+                    &self.rollup_id,
+                    std::slice::from_ref(&transaction_hash),
+                    Error::ExceedMaxGasLimit,
+                ))?;
             }
 
             RawTransactionModel::put_with_transaction_hash(
@@ -62,7 +93,6 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 self.raw_transaction.clone(),
                 true,
             )?;
-
             RawTransactionModel::put(
                 &self.rollup_id,
                 rollup_block_height,
@@ -71,7 +101,11 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 true,
             )?;
 
+            SenderNonce::record_next_nonce(&self.rollup_id, &next_nonce_map)?;
+
             let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
+
+            let merkle_hash_scheme = merkle_tree.hash_scheme();
             let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
 
             tracing::debug!(
@@ -81,24 +115,41 @@ impl RpcParameter<AppState> for SendRawTransaction {
                 rollup_metadata.transaction_order
             );
 
-            rollup_metadata.current_gas += transaction_gas_limit;
+            rollup_metadata.current_gas = rollup_metadata
+                .current_gas
+                .saturating_add(transaction_gas_limit);
             rollup_metadata.transaction_order += 1;
             rollup_metadata.update()?;
             drop(merkle_tree);
 
             let order_commitment = issue_order_commitment(
                 context.clone(),
-                rollup.platform,
-                self.rollup_id.clone(),
-                rollup.order_commitment_type,
+                &rollup,
                 transaction_hash.clone(),
                 rollup_block_height,
                 transaction_order,
                 pre_merkle_path,
+                merkle_hash_scheme,
+                None,
             )
             .await?;
 
             order_commitment.put(&self.rollup_id, rollup_block_height, transaction_order)?;
+            TransactionOrderIndex::record(
+                &self.rollup_id,
+                TransactionKind::Raw,
+                &transaction_hash,
+                rollup_block_height,
+                transaction_order,
+                false,
+            )?;
+            context.subscription_manager().publish_order_commitment(
+                &self.rollup_id,
+                rollup_block_height,
+                transaction_order,
+                &transaction_hash,
+                &order_commitment,
+            );
 
             sync_raw_transaction(
                 cluster,
@@ -170,6 +221,10 @@ pub fn sync_raw_transaction(
     is_direct_sent: bool,
 ) {
     tokio::spawn(async move {
+        let synced_rollup_id = rollup_id.clone();
+        // Hashed by the handler already, so this does not drop the hash.
+        let transaction_hash_list: Vec<RawTransactionHash> =
+            raw_transaction.raw_transaction_hash().into_iter().collect();
         let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
 
         if !other_cluster_rpc_url_list.is_empty() {
@@ -181,13 +236,10 @@ pub fn sync_raw_transaction(
                 order_commitment: Some(order_commitment),
                 is_direct_sent,
             };
-            let signature = context
-                .get_signer(platform)
-                .await
-                .unwrap()
-                .sign_message(&message)
-                .unwrap();
-            let rpc_self = SyncRawTransaction { message, signature };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncRawTransaction::method(), &message).unwrap();
+            let rpc_self = SyncRawTransaction { message, envelope };
 
             context
                 .rpc_client()
@@ -200,5 +252,11 @@ pub fn sync_raw_transaction(
                 .await
                 .unwrap();
         }
+
+        TransactionOrderIndex::mark_synced(
+            &synced_rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash_list,
+        );
     });
 }
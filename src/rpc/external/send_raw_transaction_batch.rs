@@ -0,0 +1,261 @@
+use crate::{
+    rpc::{
+        cluster::{SyncRawTransactionBatch, SyncRawTransactionBatchMessage},
+        external::{
+            check_duplicate_transaction_list, issue_bundle_order_commitment, issue_order_commitment,
+        },
+        prelude::*,
+    },
+    types::*,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SendRawTransactionBatch {
+    pub rollup_id: String,
+    pub raw_transaction_list: Vec<RawTransaction>,
+}
+
+impl RpcParameter<AppState> for SendRawTransactionBatch {
+    type Response = BundleOrderCommitment;
+
+    fn method() -> &'static str {
+        "send_raw_transaction_batch"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        if self.raw_transaction_list.is_empty() {
+            return Err(Error::EmptyTransactionList)?;
+        }
+
+        let batch_gas_limit = get_batch_gas_limit(
+            self.raw_transaction_list
+                .iter()
+                .map(RawTransaction::get_transaction_gas_limit),
+        )?;
+
+        let rollup = Rollup::get(&self.rollup_id)?;
+        // Rejected before the batch takes orders and gas budget.
+        let mut sender_nonce_list = Vec::new();
+        for raw_transaction in self.raw_transaction_list.iter() {
+            sender_nonce_list.extend(raw_transaction.validate(rollup.chain_id)?);
+        }
+
+        let mut rollup_metadata = RollupMetadata::get_mut(&self.rollup_id)?;
+        let cluster = Cluster::get(
+            rollup.platform,
+            rollup.service_provider,
+            &rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+        let rollup_block_height = rollup_metadata.rollup_block_height;
+
+        if rollup_metadata.is_leader {
+            let transaction_hash_list = self
+                .raw_transaction_list
+                .iter()
+                .map(|raw_transaction| raw_transaction.raw_transaction_hash())
+                .collect::<Result<Vec<RawTransactionHash>, Error>>()?;
+            check_duplicate_transaction_list(
+                &self.rollup_id,
+                TransactionKind::Raw,
+                &transaction_hash_list,
+                rollup_block_height,
+                context.config().duplicate_transaction_retention,
+            )?;
+
+            // Checked before the batch is stored or takes its orders, and recorded once
+            // it is stored.
+            let next_nonce_map =
+                SenderNonce::check(&self.rollup_id, rollup.nonce_policy, &sender_nonce_list)
+                    .map_err(|error| {
+                        RejectedTransaction::record(&self.rollup_id, &transaction_hash_list, error)
+                    })?;
+
+            // The whole batch takes consecutive orders under a single lock.
+            let first_transaction_order = rollup_metadata
+                .take_transaction_order(self.raw_transaction_list.len() as u64, batch_gas_limit)
+                .map_err(|error| {
+                    RejectedTransaction::record(&self.rollup_id, &transaction_hash_list, error)
+                })?;
+            for (index, raw_transaction) in self.raw_transaction_list.iter().enumerate() {
+                let transaction_order = first_transaction_order + index as u64;
+
+                RawTransactionModel::put_with_transaction_hash(
+                    &self.rollup_id,
+                    &transaction_hash_list[index],
+                    raw_transaction.clone(),
+                    true,
+                )?;
+                RawTransactionModel::put(
+                    &self.rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    raw_transaction.clone(),
+                    true,
+                )?;
+            }
+
+            SenderNonce::record_next_nonce(&self.rollup_id, &next_nonce_map)?;
+
+            let merkle_tree = context.merkle_tree_manager().get(&self.rollup_id).await?;
+            let merkle_hash_scheme = merkle_tree.hash_scheme();
+
+            let mut ordered_transaction_list = Vec::with_capacity(self.raw_transaction_list.len());
+            for (index, transaction_hash) in transaction_hash_list.iter().enumerate() {
+                let transaction_order = first_transaction_order + index as u64;
+                let transaction_hash = transaction_hash.clone();
+
+                let (_, pre_merkle_path) = merkle_tree.add_data(transaction_hash.as_ref()).await;
+                ordered_transaction_list.push((
+                    transaction_order,
+                    transaction_hash,
+                    pre_merkle_path,
+                ));
+            }
+
+            rollup_metadata.update()?;
+            drop(merkle_tree);
+
+            let mut order_commitment_list = Vec::with_capacity(ordered_transaction_list.len());
+            for (transaction_order, transaction_hash, pre_merkle_path) in ordered_transaction_list {
+                let order_commitment = issue_order_commitment(
+                    context.clone(),
+                    &rollup,
+                    transaction_hash.clone(),
+                    rollup_block_height,
+                    transaction_order,
+                    pre_merkle_path,
+                    merkle_hash_scheme,
+                    None,
+                )
+                .await?;
+                order_commitment.put(&self.rollup_id, rollup_block_height, transaction_order)?;
+                TransactionOrderIndex::record(
+                    &self.rollup_id,
+                    TransactionKind::Raw,
+                    &transaction_hash,
+                    rollup_block_height,
+                    transaction_order,
+                    false,
+                )?;
+                context.subscription_manager().publish_order_commitment(
+                    &self.rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    &transaction_hash,
+                    &order_commitment,
+                );
+
+                if let OrderCommitment::Single(single_order_commitment) = order_commitment {
+                    order_commitment_list.push(single_order_commitment);
+                }
+            }
+
+            let bundle_order_commitment = issue_bundle_order_commitment(
+                context.clone(),
+                &rollup,
+                rollup_block_height,
+                first_transaction_order,
+                order_commitment_list,
+            )
+            .await?;
+
+            sync_raw_transaction_batch(
+                cluster,
+                context.clone(),
+                rollup.platform,
+                self.rollup_id.clone(),
+                rollup_block_height,
+                first_transaction_order,
+                self.raw_transaction_list,
+                bundle_order_commitment.clone(),
+                true,
+            );
+
+            Ok(bundle_order_commitment)
+        } else {
+            let leader_external_rpc_url = rollup_metadata
+                .leader_sequencer_rpc_info
+                .external_rpc_url
+                .clone()
+                .ok_or(Error::EmptyLeaderClusterRpcUrl)?;
+            drop(rollup_metadata);
+
+            match context
+                .rpc_client()
+                .request(
+                    leader_external_rpc_url,
+                    SendRawTransactionBatch::method(),
+                    &self,
+                    Id::Null,
+                )
+                .await
+            {
+                Ok(response) => Ok(response),
+                Err(error) => {
+                    tracing::error!(
+                        "Send raw transaction batch - leader external rpc error: {:?}",
+                        error
+                    );
+                    Err(error.into())
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sync_raw_transaction_batch(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    rollup_block_height: u64,
+    first_transaction_order: u64,
+    raw_transaction_list: Vec<RawTransaction>,
+    bundle_order_commitment: BundleOrderCommitment,
+    is_direct_sent: bool,
+) {
+    tokio::spawn(async move {
+        let synced_rollup_id = rollup_id.clone();
+        // Hashed by the handler already, so this does not drop any hash.
+        let transaction_hash_list: Vec<RawTransactionHash> = raw_transaction_list
+            .iter()
+            .filter_map(|raw_transaction| raw_transaction.raw_transaction_hash().ok())
+            .collect();
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncRawTransactionBatchMessage {
+                rollup_id,
+                rollup_block_height,
+                first_transaction_order,
+                raw_transaction_list,
+                bundle_order_commitment,
+                is_direct_sent,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncRawTransactionBatch::method(), &message)
+                    .unwrap();
+            let rpc_self = SyncRawTransactionBatch { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncRawTransactionBatch::method(),
+                    &rpc_self,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+
+        TransactionOrderIndex::mark_synced(
+            &synced_rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash_list,
+        );
+    });
+}
@@ -0,0 +1,83 @@
+use crate::{
+    rpc::prelude::*,
+    task::get_block_cluster,
+    verifier::{recover_order_commitment_signer, verify_block_inclusion},
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VerifyOrderCommitment {
+    pub order_commitment: SignOrderCommitment,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VerifyOrderCommitmentResponse {
+    /// Sequencer that signed the order commitment, if it is known.
+    pub signer_address: Option<String>,
+    pub is_signed_by_leader: bool,
+    /// `None` until the block of the order commitment is built.
+    pub is_included: Option<bool>,
+}
+
+impl RpcParameter<AppState> for VerifyOrderCommitment {
+    type Response = VerifyOrderCommitmentResponse;
+
+    fn method() -> &'static str {
+        "verify_order_commitment"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let order_commitment_data = &self.order_commitment.data;
+
+        let rollup = Rollup::get(&order_commitment_data.rollup_id)?;
+        let rollup_metadata = RollupMetadata::get(&order_commitment_data.rollup_id)?;
+        let cluster = get_block_cluster(&rollup, rollup_metadata.platform_block_height)?;
+
+        let block_leader = match BlockLeader::get(
+            &order_commitment_data.rollup_id,
+            order_commitment_data.block_height,
+        ) {
+            Ok(block_leader) => block_leader,
+            Err(error) if error.is_none_type() => BlockLeader::default(),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut candidate_address_list = cluster.get_sequencer_address_list();
+        candidate_address_list.extend(block_leader.leader_address_list.iter().cloned());
+
+        let signature_scheme = rollup.get_signature_scheme(order_commitment_data.block_height)?;
+        let signer_address = recover_order_commitment_signer(
+            rollup.platform.into(),
+            &signature_scheme,
+            &self.order_commitment,
+            candidate_address_list.iter(),
+        )
+        .ok();
+        let is_signed_by_leader = signer_address
+            .as_ref()
+            .is_some_and(|signer_address| block_leader.contains(signer_address));
+
+        let is_included = match Block::get(
+            &order_commitment_data.rollup_id,
+            order_commitment_data.block_height,
+        ) {
+            Ok(block) => Some(
+                verify_block_inclusion(
+                    order_commitment_data,
+                    &block.merkle_leaf_list()?,
+                    &block.block_commitment,
+                    block.merkle_root_version,
+                )
+                .await
+                .is_ok(),
+            ),
+            Err(error) if error.is_none_type() => None,
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(VerifyOrderCommitmentResponse {
+            signer_address: signer_address.map(|address| address.as_hex_string()),
+            is_signed_by_leader,
+            is_included,
+        })
+    }
+}
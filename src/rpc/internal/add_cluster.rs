@@ -50,7 +50,16 @@ impl RpcParameter<AppState> for AddCluster {
                 cluster_id_list.update()?;
             }
             Platform::Holesky => unimplemented!("Holesky client needs to be implemented."),
-            Platform::Local => unimplemented!("Local client needs to be implemented."),
+            Platform::Local => {
+                let liveness_client: liveness::local::LivenessClient = context
+                    .get_liveness_client(self.platform, self.service_provider)
+                    .await?;
+
+                let cluster_info = liveness_client.find_cluster_info(&self.cluster_id)?;
+                liveness_client
+                    .initialize_cluster(&context, &cluster_info)
+                    .await?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,35 @@
+use crate::{client::liveness::local::LocalClusterInfo, rpc::prelude::*};
+
+/// Adds or updates a cluster of the local liveness client without going
+/// through its cluster file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AddLocalCluster {
+    pub service_provider: ServiceProvider,
+    pub cluster_info: LocalClusterInfo,
+}
+
+impl RpcParameter<AppState> for AddLocalCluster {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "add_local_cluster"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        tracing::info!(
+            "Add local cluster - service provider: {:?}, cluster id: {:?}",
+            self.service_provider,
+            self.cluster_info.cluster_id
+        );
+
+        let liveness_client: liveness::local::LivenessClient = context
+            .get_liveness_client(Platform::Local, self.service_provider)
+            .await?;
+
+        liveness_client
+            .initialize_cluster(&context, &self.cluster_info)
+            .await?;
+
+        Ok(())
+    }
+}
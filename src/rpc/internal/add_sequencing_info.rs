@@ -38,8 +38,13 @@ impl RpcParameter<AppState> for AddSequencingInfo {
                     payload.clone(),
                 );
             }
-            SequencingInfoPayload::Local(_payload) => {
-                todo!("Implement 'LivenessClient' for local sequencing.");
+            SequencingInfoPayload::Local(payload) => {
+                liveness::local::LivenessClient::initialize(
+                    context.clone(),
+                    self.platform,
+                    self.service_provider,
+                    payload.clone(),
+                );
             }
         }
 
@@ -45,7 +45,12 @@ impl RpcParameter<AppState> for Deregister {
                 cluster_id_list.update()?;
             }
             Platform::Holesky => unimplemented!("Holesky client needs to be implemented."),
-            Platform::Local => unimplemented!("Local client needs to be implemented."),
+            Platform::Local => {
+                let mut cluster_id_list =
+                    ClusterIdList::get_mut(self.platform, self.service_provider)?;
+                cluster_id_list.remove(&self.cluster_id);
+                cluster_id_list.update()?;
+            }
         }
 
         Ok(())
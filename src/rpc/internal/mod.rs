@@ -1,4 +1,5 @@
 mod add_cluster;
+mod add_local_cluster;
 mod add_sequencing_info;
 mod add_validation_info;
 mod deregister;
@@ -6,9 +7,15 @@ mod get_cluster;
 mod get_cluster_id_list;
 mod get_sequencing_info;
 mod get_sequencing_infos;
+mod set_chain_id;
 mod set_max_gas_limit;
+mod set_merkle_hash_scheme;
+mod set_nonce_policy;
+mod set_signature_scheme;
+mod set_time_lock_puzzle_params;
 
 pub use add_cluster::*;
+pub use add_local_cluster::*;
 pub use add_sequencing_info::*;
 pub use add_validation_info::*;
 pub use deregister::*;
@@ -16,4 +23,9 @@ pub use get_cluster::*;
 pub use get_cluster_id_list::*;
 pub use get_sequencing_info::*;
 pub use get_sequencing_infos::*;
+pub use set_chain_id::*;
 pub use set_max_gas_limit::*;
+pub use set_merkle_hash_scheme::*;
+pub use set_nonce_policy::*;
+pub use set_signature_scheme::*;
+pub use set_time_lock_puzzle_params::*;
@@ -0,0 +1,79 @@
+use crate::rpc::{
+    cluster::{SyncChainId, SyncChainIdMessage},
+    prelude::*,
+};
+
+/// Sets the chain id raw transactions of the rollup must be signed for, or
+/// disables the check with `null`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetChainId {
+    pub rollup_id: String,
+    pub chain_id: Option<u64>,
+}
+
+impl RpcParameter<AppState> for SetChainId {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_chain_id"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let platform = locked_rollup.platform;
+
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        locked_rollup.chain_id = self.chain_id;
+        locked_rollup.update()?;
+
+        sync_set_chain_id(
+            cluster,
+            context.clone(),
+            platform,
+            self.rollup_id.clone(),
+            self.chain_id,
+        );
+
+        Ok(())
+    }
+}
+
+pub fn sync_set_chain_id(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    chain_id: Option<u64>,
+) {
+    tokio::spawn(async move {
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncChainIdMessage {
+                rollup_id,
+                chain_id,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope = ClusterEnvelope::seal(&signer, SyncChainId::method(), &message).unwrap();
+            let params = SyncChainId { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncChainId::method(),
+                    &params,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+    });
+}
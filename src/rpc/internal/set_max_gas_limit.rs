@@ -59,13 +59,10 @@ pub fn sync_set_max_gas_limit(
                 rollup_id,
                 max_gas_limit,
             };
-            let signature = context
-                .get_signer(platform)
-                .await
-                .unwrap()
-                .sign_message(&message)
-                .unwrap();
-            let params = SyncMaxGasLimit { message, signature };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncMaxGasLimit::method(), &message).unwrap();
+            let params = SyncMaxGasLimit { message, envelope };
 
             context
                 .rpc_client()
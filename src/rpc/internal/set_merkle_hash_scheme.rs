@@ -0,0 +1,81 @@
+use crate::rpc::{
+    cluster::{SyncMerkleHashScheme, SyncMerkleHashSchemeMessage},
+    prelude::*,
+};
+
+/// Switches the Merkle hasher of the rollup. The current block keeps its
+/// hasher, the new one applies from the block opened by the next
+/// `finalize_block`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetMerkleHashScheme {
+    pub rollup_id: String,
+    pub merkle_hash_scheme: MerkleHashScheme,
+}
+
+impl RpcParameter<AppState> for SetMerkleHashScheme {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_merkle_hash_scheme"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let platform = locked_rollup.platform;
+
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        locked_rollup.merkle_hash_scheme = self.merkle_hash_scheme;
+        locked_rollup.update()?;
+
+        sync_set_merkle_hash_scheme(
+            cluster,
+            context.clone(),
+            platform,
+            self.rollup_id.clone(),
+            self.merkle_hash_scheme,
+        );
+
+        Ok(())
+    }
+}
+
+pub fn sync_set_merkle_hash_scheme(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    merkle_hash_scheme: MerkleHashScheme,
+) {
+    tokio::spawn(async move {
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncMerkleHashSchemeMessage {
+                rollup_id,
+                merkle_hash_scheme,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncMerkleHashScheme::method(), &message).unwrap();
+            let params = SyncMerkleHashScheme { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncMerkleHashScheme::method(),
+                    &params,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+    });
+}
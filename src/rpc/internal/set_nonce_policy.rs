@@ -0,0 +1,80 @@
+use crate::rpc::{
+    cluster::{SyncNoncePolicy, SyncNoncePolicyMessage},
+    prelude::*,
+};
+
+/// Sets whether the leader rejects raw transactions whose nonce is not the next
+/// one expected from their sender.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetNoncePolicy {
+    pub rollup_id: String,
+    pub nonce_policy: NoncePolicy,
+}
+
+impl RpcParameter<AppState> for SetNoncePolicy {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_nonce_policy"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let platform = locked_rollup.platform;
+
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        locked_rollup.nonce_policy = self.nonce_policy;
+        locked_rollup.update()?;
+
+        sync_set_nonce_policy(
+            cluster,
+            context.clone(),
+            platform,
+            self.rollup_id.clone(),
+            self.nonce_policy,
+        );
+
+        Ok(())
+    }
+}
+
+pub fn sync_set_nonce_policy(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    nonce_policy: NoncePolicy,
+) {
+    tokio::spawn(async move {
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncNoncePolicyMessage {
+                rollup_id,
+                nonce_policy,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncNoncePolicy::method(), &message).unwrap();
+            let params = SyncNoncePolicy { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncNoncePolicy::method(),
+                    &params,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+    });
+}
@@ -0,0 +1,81 @@
+use crate::rpc::{
+    cluster::{SyncSignatureScheme, SyncSignatureSchemeMessage},
+    prelude::*,
+};
+
+/// Switches the rollup between the JSON and the EIP-712 signature scheme.
+/// The scheme applies from the block opened by the next `finalize_block`,
+/// so that the signatures of earlier blocks keep verifying.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetSignatureScheme {
+    pub rollup_id: String,
+    pub signature_scheme: SignatureScheme,
+}
+
+impl RpcParameter<AppState> for SetSignatureScheme {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_signature_scheme"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let platform = locked_rollup.platform;
+
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        locked_rollup.signature_scheme = self.signature_scheme.clone();
+        locked_rollup.update()?;
+
+        sync_set_signature_scheme(
+            cluster,
+            context.clone(),
+            platform,
+            self.rollup_id.clone(),
+            self.signature_scheme.clone(),
+        );
+
+        Ok(())
+    }
+}
+
+pub fn sync_set_signature_scheme(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    signature_scheme: SignatureScheme,
+) {
+    tokio::spawn(async move {
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncSignatureSchemeMessage {
+                rollup_id,
+                signature_scheme,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncSignatureScheme::method(), &message).unwrap();
+            let params = SyncSignatureScheme { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncSignatureScheme::method(),
+                    &params,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+    });
+}
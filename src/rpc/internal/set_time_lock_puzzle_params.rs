@@ -0,0 +1,81 @@
+use crate::rpc::{
+    cluster::{SyncTimeLockPuzzleParams, SyncTimeLockPuzzleParamsMessage},
+    prelude::*,
+};
+
+/// Sets the time-lock puzzle parameters PVDE transactions of the rollup must be
+/// encrypted under.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SetTimeLockPuzzleParams {
+    pub rollup_id: String,
+    pub time_lock_puzzle_params: TimeLockPuzzleParams,
+}
+
+impl RpcParameter<AppState> for SetTimeLockPuzzleParams {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "set_time_lock_puzzle_params"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let rollup_metadata = RollupMetadata::get(&self.rollup_id)?;
+        let mut locked_rollup = Rollup::get_mut(&self.rollup_id)?;
+        let platform = locked_rollup.platform;
+
+        let cluster = Cluster::get(
+            locked_rollup.platform,
+            locked_rollup.service_provider,
+            &locked_rollup.cluster_id,
+            rollup_metadata.platform_block_height,
+        )?;
+
+        locked_rollup.time_lock_puzzle_params = Some(self.time_lock_puzzle_params.clone());
+        locked_rollup.update()?;
+
+        sync_set_time_lock_puzzle_params(
+            cluster,
+            context.clone(),
+            platform,
+            self.rollup_id.clone(),
+            self.time_lock_puzzle_params,
+        );
+
+        Ok(())
+    }
+}
+
+pub fn sync_set_time_lock_puzzle_params(
+    cluster: Cluster,
+    context: AppState,
+    platform: Platform,
+    rollup_id: String,
+    time_lock_puzzle_params: TimeLockPuzzleParams,
+) {
+    tokio::spawn(async move {
+        let other_cluster_rpc_url_list: Vec<String> = cluster.get_others_cluster_rpc_url_list();
+
+        if !other_cluster_rpc_url_list.is_empty() {
+            let message = SyncTimeLockPuzzleParamsMessage {
+                rollup_id,
+                time_lock_puzzle_params,
+            };
+            let signer = context.get_signer(platform).await.unwrap();
+            let envelope =
+                ClusterEnvelope::seal(&signer, SyncTimeLockPuzzleParams::method(), &message)
+                    .unwrap();
+            let params = SyncTimeLockPuzzleParams { message, envelope };
+
+            context
+                .rpc_client()
+                .multicast(
+                    other_cluster_rpc_url_list,
+                    SyncTimeLockPuzzleParams::method(),
+                    &params,
+                    Id::Null,
+                )
+                .await
+                .unwrap();
+        }
+    });
+}
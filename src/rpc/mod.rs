@@ -1,6 +1,8 @@
 pub mod cluster;
+pub mod ethereum;
 pub mod external;
 pub mod internal;
+pub mod subscription;
 pub(crate) mod prelude {
     pub use radius_sdk::{
         json_rpc::{
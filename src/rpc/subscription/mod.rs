@@ -0,0 +1,95 @@
+mod subscribe_blocks;
+mod subscribe_order_commitments;
+mod subscribe_transaction;
+
+use std::future::Future;
+
+use jsonrpsee::{
+    core::SubscriptionResult,
+    server::{Server, ServerHandle},
+    RpcModule, SubscriptionMessage, SubscriptionSink,
+};
+use serde::{de::DeserializeOwned, Serialize};
+pub use subscribe_blocks::*;
+pub use subscribe_order_commitments::*;
+pub use subscribe_transaction::*;
+
+use crate::{error::Error, state::AppState};
+
+/// A subscription served over WebSocket, the streaming counterpart of
+/// `RpcParameter`.
+pub trait SubscriptionParameter: DeserializeOwned + Send + 'static {
+    fn method() -> &'static str;
+
+    fn notification_method() -> &'static str;
+
+    fn unsubscribe_method() -> &'static str;
+
+    /// Streams notifications to `sink` until the subscriber leaves or the
+    /// subscription is complete.
+    fn handler(
+        self,
+        sink: SubscriptionSink,
+        context: AppState,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+pub struct SubscriptionServer {
+    rpc_module: RpcModule<AppState>,
+}
+
+impl SubscriptionServer {
+    pub fn new(context: AppState) -> Self {
+        Self {
+            rpc_module: RpcModule::new(context),
+        }
+    }
+
+    pub fn register_subscription<T>(mut self) -> Result<Self, Error>
+    where
+        T: SubscriptionParameter,
+    {
+        self.rpc_module
+            .register_subscription(
+                T::method(),
+                T::notification_method(),
+                T::unsubscribe_method(),
+                |params, pending, context| async move {
+                    let parameter: T = match params.parse() {
+                        Ok(parameter) => parameter,
+                        Err(error) => {
+                            pending.reject(error).await;
+                            return SubscriptionResult::Ok(());
+                        }
+                    };
+
+                    let Ok(sink) = pending.accept().await else {
+                        return Ok(());
+                    };
+
+                    Ok(parameter.handler(sink, (*context).clone()).await?)
+                },
+            )
+            .map_err(|error| Error::Internal(error.into()))?;
+
+        Ok(self)
+    }
+
+    pub async fn init(self, rpc_url: impl AsRef<str>) -> Result<ServerHandle, Error> {
+        let server = Server::builder().build(rpc_url.as_ref()).await?;
+
+        Ok(server.start(self.rpc_module))
+    }
+}
+
+/// Sends `item` to the subscriber. Returns `false` once the subscriber is
+/// gone.
+async fn notify<T>(sink: &SubscriptionSink, item: &T) -> Result<bool, Error>
+where
+    T: Serialize,
+{
+    let message =
+        SubscriptionMessage::from_json(item).map_err(|error| Error::Internal(error.into()))?;
+
+    Ok(sink.send(message).await.is_ok())
+}
@@ -0,0 +1,78 @@
+use jsonrpsee::SubscriptionSink;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{notify, SubscriptionParameter};
+use crate::{
+    rpc::{external::GetBlockResponse, prelude::*},
+    subscription_manager::SubscriptionEvent,
+};
+
+/// Streams the blocks of a rollup as they are built. A subscriber resuming
+/// after a disconnection passes `from_rollup_block_height` to receive the
+/// stored blocks from that height first.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscribeBlocks {
+    pub rollup_id: String,
+
+    #[serde(default)]
+    pub from_rollup_block_height: Option<u64>,
+}
+
+impl SubscriptionParameter for SubscribeBlocks {
+    fn method() -> &'static str {
+        "subscribe_blocks"
+    }
+
+    fn notification_method() -> &'static str {
+        "block"
+    }
+
+    fn unsubscribe_method() -> &'static str {
+        "unsubscribe_blocks"
+    }
+
+    async fn handler(self, sink: SubscriptionSink, context: AppState) -> Result<(), Error> {
+        // Subscribe before reading the stored blocks so that none built in
+        // between is missed.
+        let mut receiver = context.subscription_manager().subscribe();
+
+        let mut next_rollup_block_height = 0;
+        if let Some(from_rollup_block_height) = self.from_rollup_block_height {
+            next_rollup_block_height = from_rollup_block_height;
+
+            loop {
+                let block = match Block::get(&self.rollup_id, next_rollup_block_height) {
+                    Ok(block) => block,
+                    Err(error) if error.is_none_type() => break,
+                    Err(error) => return Err(Error::Database(error)),
+                };
+
+                if !notify(&sink, &GetBlockResponse::from(block)).await? {
+                    return Ok(());
+                }
+                next_rollup_block_height += 1;
+            }
+        }
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => return Ok(()),
+                event = receiver.recv() => event,
+            };
+
+            match event {
+                Ok(SubscriptionEvent::Block { rollup_id, block })
+                    if rollup_id == self.rollup_id
+                        && block.block_height >= next_rollup_block_height =>
+                {
+                    if !notify(&sink, &GetBlockResponse::from((*block).clone())).await? {
+                        return Ok(());
+                    }
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(_)) => return Err(Error::SubscriptionLagged),
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+}
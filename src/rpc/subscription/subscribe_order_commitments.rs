@@ -0,0 +1,110 @@
+use jsonrpsee::SubscriptionSink;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{notify, SubscriptionParameter};
+use crate::{rpc::prelude::*, subscription_manager::SubscriptionEvent};
+
+/// Streams the order commitments the leader issues for a rollup. A
+/// subscriber resuming after a disconnection passes
+/// `from_rollup_block_height` to receive the stored order commitments from
+/// that height first.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscribeOrderCommitments {
+    pub rollup_id: String,
+
+    #[serde(default)]
+    pub from_rollup_block_height: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderCommitmentNotification {
+    pub rollup_block_height: u64,
+    pub transaction_order: u64,
+    pub order_commitment: OrderCommitment,
+}
+
+impl SubscriptionParameter for SubscribeOrderCommitments {
+    fn method() -> &'static str {
+        "subscribe_order_commitments"
+    }
+
+    fn notification_method() -> &'static str {
+        "order_commitment"
+    }
+
+    fn unsubscribe_method() -> &'static str {
+        "unsubscribe_order_commitments"
+    }
+
+    async fn handler(self, sink: SubscriptionSink, context: AppState) -> Result<(), Error> {
+        // Subscribe before reading the stored order commitments so that none
+        // issued in between is missed.
+        let mut receiver = context.subscription_manager().subscribe();
+
+        // Order commitments before this position were sent from the database.
+        let mut replayed_until = (0, 0);
+        if let Some(from_rollup_block_height) = self.from_rollup_block_height {
+            let current_rollup_block_height = RollupMetadata::get(&self.rollup_id)
+                .map_err(Error::Database)?
+                .rollup_block_height;
+
+            for rollup_block_height in from_rollup_block_height..=current_rollup_block_height {
+                let mut transaction_order = 0;
+                loop {
+                    let order_commitment = match OrderCommitment::get(
+                        &self.rollup_id,
+                        rollup_block_height,
+                        transaction_order,
+                    ) {
+                        Ok(order_commitment) => order_commitment,
+                        Err(error) if error.is_none_type() => break,
+                        Err(error) => return Err(Error::Database(error)),
+                    };
+
+                    let notification = OrderCommitmentNotification {
+                        rollup_block_height,
+                        transaction_order,
+                        order_commitment,
+                    };
+                    if !notify(&sink, &notification).await? {
+                        return Ok(());
+                    }
+                    transaction_order += 1;
+                }
+
+                replayed_until = (rollup_block_height, transaction_order);
+            }
+        }
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => return Ok(()),
+                event = receiver.recv() => event,
+            };
+
+            match event {
+                Ok(SubscriptionEvent::OrderCommitment {
+                    rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    order_commitment,
+                    ..
+                }) if rollup_id == self.rollup_id
+                    && (rollup_block_height, transaction_order) >= replayed_until =>
+                {
+                    let notification = OrderCommitmentNotification {
+                        rollup_block_height,
+                        transaction_order,
+                        order_commitment,
+                    };
+                    if !notify(&sink, &notification).await? {
+                        return Ok(());
+                    }
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(_)) => return Err(Error::SubscriptionLagged),
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use jsonrpsee::SubscriptionSink;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{notify, SubscriptionParameter};
+use crate::{
+    rpc::{
+        external::{get_transaction_status, TransactionStatus},
+        prelude::*,
+    },
+    subscription_manager::SubscriptionEvent,
+};
+
+/// Streams the status of a transaction, starting with its current one, until
+/// it is included in a block, found invalid or rejected.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscribeTransaction {
+    pub rollup_id: String,
+    pub transaction_hash: String,
+}
+
+impl SubscriptionParameter for SubscribeTransaction {
+    fn method() -> &'static str {
+        "subscribe_transaction"
+    }
+
+    fn notification_method() -> &'static str {
+        "transaction_status"
+    }
+
+    fn unsubscribe_method() -> &'static str {
+        "unsubscribe_transaction"
+    }
+
+    async fn handler(self, sink: SubscriptionSink, context: AppState) -> Result<(), Error> {
+        // Subscribe before reading the current status so that no change in
+        // between is missed.
+        let mut receiver = context.subscription_manager().subscribe();
+
+        let mut last_transaction_status = None;
+        loop {
+            // A transaction not received yet is waited for.
+            let transaction_status =
+                match get_transaction_status(&self.rollup_id, &self.transaction_hash) {
+                    Ok(transaction_status) => Some(transaction_status),
+                    Err(Error::TransactionNotFound) => None,
+                    Err(error) => return Err(error),
+                };
+
+            if let Some(transaction_status) = transaction_status {
+                let is_final = matches!(
+                    transaction_status,
+                    TransactionStatus::IncludedInBlock { .. }
+                        | TransactionStatus::CommitmentSubmitted { .. }
+                        | TransactionStatus::Invalid { .. }
+                        | TransactionStatus::Rejected { .. }
+                );
+
+                let transaction_status = serde_json::to_value(&transaction_status)
+                    .map_err(|error| Error::Internal(error.into()))?;
+                if last_transaction_status.as_ref() != Some(&transaction_status) {
+                    if !notify(&sink, &transaction_status).await? {
+                        return Ok(());
+                    }
+                    last_transaction_status = Some(transaction_status);
+                }
+
+                if is_final {
+                    return Ok(());
+                }
+            }
+
+            loop {
+                let event = tokio::select! {
+                    _ = sink.closed() => return Ok(()),
+                    event = receiver.recv() => event,
+                };
+
+                match event {
+                    Ok(SubscriptionEvent::OrderCommitment {
+                        rollup_id,
+                        transaction_hash,
+                        ..
+                    }) if rollup_id == self.rollup_id
+                        && AsRef::<str>::as_ref(&transaction_hash) == self.transaction_hash =>
+                    {
+                        break
+                    }
+                    Ok(SubscriptionEvent::Block { rollup_id, block })
+                        if rollup_id == self.rollup_id
+                            && block.merkle_leaf_list().is_ok_and(|merkle_leaf_list| {
+                                merkle_leaf_list
+                                    .iter()
+                                    .any(|leaf| AsRef::<str>::as_ref(leaf) == self.transaction_hash)
+                            }) =>
+                    {
+                        break
+                    }
+                    Ok(_) => continue,
+                    // The status is read again from the database.
+                    Err(RecvError::Lagged(_)) => break,
+                    Err(RecvError::Closed) => return Ok(()),
+                }
+            }
+        }
+    }
+}
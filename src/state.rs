@@ -13,6 +13,7 @@ use crate::{
     },
     merkle_tree_manager::MerkleTreeManager,
     profiler::Profiler,
+    subscription_manager::SubscriptionManager,
     types::*,
 };
 
@@ -31,6 +32,7 @@ struct AppStateInner {
     profiler: Option<Profiler>,
     rpc_client: RpcClient,
     merkle_tree_manager: MerkleTreeManager,
+    subscription_manager: SubscriptionManager,
 }
 
 impl Clone for AppState {
@@ -54,6 +56,7 @@ impl AppState {
         profiler: Option<Profiler>,
         rpc_client: RpcClient,
         merkle_tree_manager: MerkleTreeManager,
+        subscription_manager: SubscriptionManager,
     ) -> Self {
         let inner = AppStateInner {
             config,
@@ -66,6 +69,7 @@ impl AppState {
             profiler,
             rpc_client,
             merkle_tree_manager,
+            subscription_manager,
         };
 
         Self {
@@ -100,6 +104,10 @@ impl AppState {
     pub fn merkle_tree_manager(&self) -> &MerkleTreeManager {
         &self.inner.merkle_tree_manager
     }
+
+    pub fn subscription_manager(&self) -> &SubscriptionManager {
+        &self.inner.subscription_manager
+    }
 }
 
 /// Validation client functions
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate::types::*;
+
+/// Number of events kept for a lagging subscriber. A subscriber that falls
+/// further behind skips the missed events and catches up from the database.
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Clone, Debug)]
+pub enum SubscriptionEvent {
+    Block {
+        rollup_id: String,
+        block: Arc<Block>,
+    },
+    OrderCommitment {
+        rollup_id: String,
+        rollup_block_height: u64,
+        transaction_order: u64,
+        transaction_hash: RawTransactionHash,
+        order_commitment: OrderCommitment,
+    },
+}
+
+pub struct SubscriptionManager {
+    sender: broadcast::Sender<SubscriptionEvent>,
+}
+
+impl Clone for SubscriptionManager {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl Default for SubscriptionManager {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY);
+
+        Self { sender }
+    }
+}
+
+impl SubscriptionManager {
+    pub fn subscribe(&self) -> broadcast::Receiver<SubscriptionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes a block persisted by the block builder.
+    pub fn publish_block(&self, rollup_id: &str, block: &Block) {
+        self.publish(SubscriptionEvent::Block {
+            rollup_id: rollup_id.to_owned(),
+            block: Arc::new(block.clone()),
+        });
+    }
+
+    /// Publishes an order commitment issued by the leader.
+    pub fn publish_order_commitment(
+        &self,
+        rollup_id: &str,
+        rollup_block_height: u64,
+        transaction_order: u64,
+        transaction_hash: &RawTransactionHash,
+        order_commitment: &OrderCommitment,
+    ) {
+        self.publish(SubscriptionEvent::OrderCommitment {
+            rollup_id: rollup_id.to_owned(),
+            rollup_block_height,
+            transaction_order,
+            transaction_hash: transaction_hash.clone(),
+            order_commitment: order_commitment.clone(),
+        });
+    }
+
+    fn publish(&self, event: SubscriptionEvent) {
+        // Sending only fails when nobody is subscribed.
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    use super::*;
+
+    #[test]
+    fn publish_without_subscriber_is_dropped() {
+        let subscription_manager = SubscriptionManager::default();
+
+        subscription_manager.publish_order_commitment(
+            "rollup",
+            1,
+            0,
+            &RawTransactionHash::from([1; 32]),
+            &OrderCommitment::default(),
+        );
+
+        assert!(matches!(
+            subscription_manager.subscribe().try_recv(),
+            Err(TryRecvError::Empty)
+        ));
+    }
+
+    #[test]
+    fn every_subscriber_receives_the_order_commitment() {
+        let subscription_manager = SubscriptionManager::default();
+        let mut receiver = subscription_manager.subscribe();
+        let mut other_receiver = subscription_manager.clone().subscribe();
+
+        subscription_manager.publish_order_commitment(
+            "rollup",
+            1,
+            2,
+            &RawTransactionHash::from([1; 32]),
+            &OrderCommitment::default(),
+        );
+
+        for receiver in [&mut receiver, &mut other_receiver] {
+            match receiver.try_recv() {
+                Ok(SubscriptionEvent::OrderCommitment {
+                    rollup_id,
+                    rollup_block_height,
+                    transaction_order,
+                    transaction_hash,
+                    ..
+                }) => {
+                    assert_eq!(rollup_id, "rollup");
+                    assert_eq!(rollup_block_height, 1);
+                    assert_eq!(transaction_order, 2);
+                    assert_eq!(transaction_hash, RawTransactionHash::from([1; 32]));
+                }
+                event => panic!("unexpected event: {:?}", event),
+            }
+        }
+    }
+
+    #[test]
+    fn lagging_subscriber_skips_the_missed_events() {
+        let subscription_manager = SubscriptionManager::default();
+        let mut receiver = subscription_manager.subscribe();
+
+        for transaction_order in 0..=SUBSCRIPTION_CHANNEL_CAPACITY as u64 {
+            subscription_manager.publish_order_commitment(
+                "rollup",
+                1,
+                transaction_order,
+                &RawTransactionHash::from([1; 32]),
+                &OrderCommitment::default(),
+            );
+        }
+
+        assert!(matches!(receiver.try_recv(), Err(TryRecvError::Lagged(1))));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(SubscriptionEvent::OrderCommitment {
+                transaction_order: 1,
+                ..
+            })
+        ));
+    }
+}
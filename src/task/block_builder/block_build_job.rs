@@ -0,0 +1,328 @@
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use super::{
+    build_block_from_store, decrypt_transaction_list, fetch_transaction_list, get_block_cluster,
+    submit_block_commitment, sync_block,
+};
+use crate::{error::Error, state::AppState, task::check_block_order_commitment, types::*};
+
+/// Jobs that keep failing are left in [`BlockBuildJobList`] and retried on
+/// the next startup.
+const MAX_RETRY_COUNT: u32 = 10;
+const MAX_BACKOFF_SECS: u64 = 60;
+
+const BLOCK_WAIT_COUNT: u32 = 60;
+const BLOCK_WAIT_INTERVAL_SECS: u64 = 2;
+
+/// Persists the job before spawning it, so that a block whose finalization
+/// was acknowledged is still built after a restart.
+pub fn enqueue_block_build_job(
+    context: AppState,
+    block_build_job: BlockBuildJob,
+) -> Result<(), Error> {
+    let rollup_id = block_build_job.rollup_id().to_owned();
+    let rollup_block_height = block_build_job.rollup_block_height();
+
+    block_build_job
+        .put(&rollup_id, rollup_block_height)
+        .map_err(Error::Database)?;
+
+    let mut block_build_job_list =
+        BlockBuildJobList::get_mut_or(BlockBuildJobList::default).map_err(Error::Database)?;
+    block_build_job_list.insert(&rollup_id, rollup_block_height);
+    block_build_job_list.update().map_err(Error::Database)?;
+
+    tokio::spawn(run_block_build_job(context, rollup_id, rollup_block_height));
+
+    Ok(())
+}
+
+/// Spawns every block build job that did not finish before the last shutdown.
+pub fn resume_block_build_jobs(context: AppState) -> Result<(), Error> {
+    let block_build_job_list =
+        BlockBuildJobList::get_or(BlockBuildJobList::default).map_err(Error::Database)?;
+
+    for (rollup_id, rollup_block_height) in block_build_job_list.iter() {
+        tracing::info!(
+            "Resume block build job - rollup_id: {:?} / rollup_block_height: {:?}",
+            rollup_id,
+            rollup_block_height
+        );
+
+        tokio::spawn(run_block_build_job(
+            context.clone(),
+            rollup_id.clone(),
+            *rollup_block_height,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Waits until the block is built locally and returns its commitment with
+/// the address of its creator. A job that gave up is started again, and a
+/// block whose leader signature did not match yields the local commitment
+/// recorded in its [`BlockMismatch`].
+pub async fn wait_for_block_commitment(
+    context: &AppState,
+    rollup_id: &str,
+    rollup_block_height: u64,
+) -> Result<(BlockCommitment, Address), Error> {
+    let mut is_restarted = false;
+
+    for _ in 0..BLOCK_WAIT_COUNT {
+        match Block::get(rollup_id, rollup_block_height) {
+            Ok(block) => return Ok((block.block_commitment, block.block_creator_address)),
+            Err(error) => {
+                if !error.is_none_type() {
+                    return Err(Error::Database(error));
+                }
+            }
+        }
+
+        if let Ok(block_mismatch) = BlockMismatch::get(rollup_id, rollup_block_height) {
+            return Ok((
+                block_mismatch.local_block_commitment,
+                block_mismatch.leader_sequencer_address,
+            ));
+        }
+
+        if !is_restarted {
+            if let Ok(mut block_build_job) = BlockBuildJob::get(rollup_id, rollup_block_height) {
+                if block_build_job.retry_count >= MAX_RETRY_COUNT {
+                    block_build_job.retry_count = 0;
+                    block_build_job
+                        .put(rollup_id, rollup_block_height)
+                        .map_err(Error::Database)?;
+
+                    tokio::spawn(run_block_build_job(
+                        context.clone(),
+                        rollup_id.to_owned(),
+                        rollup_block_height,
+                    ));
+                    is_restarted = true;
+                }
+            }
+        }
+
+        sleep(Duration::from_secs(BLOCK_WAIT_INTERVAL_SECS)).await;
+    }
+
+    Err(Error::BlockNotFound)
+}
+
+async fn run_block_build_job(context: AppState, rollup_id: String, rollup_block_height: u64) {
+    loop {
+        let mut block_build_job = match BlockBuildJob::get(&rollup_id, rollup_block_height) {
+            Ok(block_build_job) => block_build_job,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to get block build job - rollup_id: {:?} / rollup_block_height: {:?} / error: {:?}",
+                    rollup_id,
+                    rollup_block_height,
+                    error
+                );
+                return;
+            }
+        };
+
+        match process_block_build_job(&context, &mut block_build_job).await {
+            Ok(()) => {
+                if let Err(error) = remove_block_build_job(&rollup_id, rollup_block_height) {
+                    tracing::error!("Failed to remove block build job: {:?}", error);
+                }
+                return;
+            }
+            Err(error) => {
+                block_build_job.retry_count += 1;
+                block_build_job.last_error = Some(error.to_string());
+
+                if let Err(error) = block_build_job.put(&rollup_id, rollup_block_height) {
+                    tracing::error!("Failed to update block build job: {:?}", error);
+                }
+
+                // The evidence is already recorded and building again would
+                // rebuild the same commitment.
+                if matches!(error, Error::BlockMismatch) {
+                    if let Err(error) = remove_block_build_job(&rollup_id, rollup_block_height) {
+                        tracing::error!("Failed to remove block build job: {:?}", error);
+                    }
+                    return;
+                }
+
+                if block_build_job.retry_count >= MAX_RETRY_COUNT {
+                    tracing::error!(
+                        "Block build job failed - rollup_id: {:?} / rollup_block_height: {:?} / state: {:?} / error: {:?}",
+                        rollup_id,
+                        rollup_block_height,
+                        block_build_job.state,
+                        error
+                    );
+                    return;
+                }
+
+                let backoff = retry_backoff(block_build_job.retry_count);
+                tracing::warn!(
+                    "Retry block build job - rollup_id: {:?} / rollup_block_height: {:?} / state: {:?} / retry_count: {:?} / backoff: {:?} / error: {:?}",
+                    rollup_id,
+                    rollup_block_height,
+                    block_build_job.state,
+                    block_build_job.retry_count,
+                    backoff,
+                    error
+                );
+
+                sleep(backoff).await;
+            }
+        }
+    }
+}
+
+/// Doubles from 2 seconds with each retry, up to [`MAX_BACKOFF_SECS`].
+fn retry_backoff(retry_count: u32) -> Duration {
+    Duration::from_secs((1u64 << retry_count.min(6)).min(MAX_BACKOFF_SECS))
+}
+
+/// Runs the job from its current state. Fetching and decrypting skip the
+/// transactions that are already stored, so they are simply run again on
+/// retry.
+async fn process_block_build_job(
+    context: &AppState,
+    block_build_job: &mut BlockBuildJob,
+) -> Result<(), Error> {
+    let rollup_id = block_build_job.rollup_id().to_owned();
+    let rollup_block_height = block_build_job.rollup_block_height();
+    let transaction_count = block_build_job.transaction_count;
+
+    let rollup = Rollup::get(&rollup_id).map_err(|_| Error::RollupNotFound)?;
+    let cluster = get_block_cluster(
+        &rollup,
+        block_build_job.finalize_block_message.platform_block_height,
+    )?;
+
+    if block_build_job.state < BlockBuildJobState::Committed {
+        update_state(block_build_job, BlockBuildJobState::Fetching)?;
+        fetch_transaction_list(
+            context,
+            &cluster,
+            &rollup_id,
+            rollup_block_height,
+            transaction_count,
+        )
+        .await?;
+
+        update_state(block_build_job, BlockBuildJobState::Decrypting)?;
+        decrypt_transaction_list(
+            context,
+            block_build_job.encrypted_transaction_type,
+            &rollup_id,
+            rollup_block_height,
+            transaction_count,
+        )
+        .await?;
+
+        build_block_from_store(
+            context,
+            &cluster,
+            &rollup,
+            rollup_block_height,
+            transaction_count,
+            block_build_job.merkle_hash_scheme,
+            &block_build_job.signature_scheme,
+            block_build_job.merkle_root_version,
+            block_build_job
+                .finalize_block_message
+                .next_block_creator_address
+                .clone(),
+            block_build_job.leader_sequencer_signature.clone(),
+        )
+        .await?;
+
+        // A broken order commitment is evidence against the leader, not a
+        // reason to stop following the block.
+        if !block_build_job.is_leader() {
+            if let Err(error) = check_block_order_commitment(&rollup_id, rollup_block_height).await
+            {
+                tracing::error!("Failed to check order commitments: {:?}", error);
+            }
+        }
+        update_state(block_build_job, BlockBuildJobState::Committed)?;
+    }
+
+    if !block_build_job.is_leader() {
+        return Ok(());
+    }
+
+    let block = Block::get(&rollup_id, rollup_block_height).map_err(Error::Database)?;
+
+    if block_build_job.state < BlockBuildJobState::Synced {
+        // A follower that misses the block must not hold back the commitment
+        // submission, so the error is only recorded.
+        if let Err(error) = sync_block(
+            context,
+            &cluster,
+            rollup.platform,
+            block_build_job.finalize_block_message.clone(),
+            block_build_job.rollup_signature.clone(),
+            transaction_count,
+            block.signature.clone(),
+        )
+        .await
+        {
+            tracing::error!("Failed to synchronize block: {:?}", error);
+            block_build_job.last_error = Some(error.to_string());
+        }
+        update_state(block_build_job, BlockBuildJobState::Synced)?;
+    }
+
+    if block_build_job.state < BlockBuildJobState::Submitted {
+        block_build_job.commitment_transaction_hash = submit_block_commitment(
+            context,
+            &rollup,
+            rollup_block_height,
+            &block.block_commitment,
+        )
+        .await?;
+        update_state(block_build_job, BlockBuildJobState::Submitted)?;
+    }
+
+    Ok(())
+}
+
+fn update_state(
+    block_build_job: &mut BlockBuildJob,
+    state: BlockBuildJobState,
+) -> Result<(), Error> {
+    block_build_job.state = state;
+
+    block_build_job
+        .put(
+            block_build_job.rollup_id(),
+            block_build_job.rollup_block_height(),
+        )
+        .map_err(Error::Database)
+}
+
+fn remove_block_build_job(rollup_id: &str, rollup_block_height: u64) -> Result<(), Error> {
+    let mut block_build_job_list =
+        BlockBuildJobList::get_mut_or(BlockBuildJobList::default).map_err(Error::Database)?;
+    block_build_job_list.remove(rollup_id, rollup_block_height);
+    block_build_job_list.update().map_err(Error::Database)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_maximum() {
+        let backoff_list: Vec<u64> = (1..=8)
+            .map(|retry_count| retry_backoff(retry_count).as_secs())
+            .collect();
+
+        assert_eq!(backoff_list, [2, 4, 8, 16, 32, 60, 60, 60]);
+        assert_eq!(retry_backoff(u32::MAX).as_secs(), MAX_BACKOFF_SECS);
+    }
+}
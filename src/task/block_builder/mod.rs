@@ -1,31 +1,43 @@
+mod block_build_job;
+mod pvde_block_builder;
 mod skde_block_builder;
 mod validation;
 
+pub use block_build_job::*;
+use ethers_core::types as eth_types;
+use pvde_block_builder::*;
 use radius_sdk::{
     json_rpc::{client::Id, server::RpcParameter},
-    signature::Signature,
+    signature::{Address, Signature},
 };
 use skde_block_builder::*;
 use validation::*;
 
 use crate::{
-    rpc::cluster::{FinalizeBlockMessage, SyncBlock},
+    error::Error,
+    rpc::{
+        cluster::{FinalizeBlockMessage, SyncBlock, SyncBlockMessage},
+        external::{GetBlock, GetBlockResponse},
+    },
     state::AppState,
     types::*,
+    util::{fetch_encrypted_transaction, fetch_raw_transaction_info},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_block(
     context: AppState,
 
-    cluster: Cluster,
-
     finalize_block_message: FinalizeBlockMessage,
     rollup_signature: Signature,
 
     encrypted_transaction_type: EncryptedTransactionType,
 
     transaction_count: u64,
-) {
+    merkle_hash_scheme: MerkleHashScheme,
+    signature_scheme: SignatureScheme,
+    merkle_root_version: MerkleRootVersion,
+) -> Result<(), Error> {
     tracing::info!(
         "Build block - rollup id: {:?}, block number: {:?}, transaction count: {:?}",
         finalize_block_message.rollup_id,
@@ -33,81 +45,50 @@ pub fn build_block(
         transaction_count
     );
 
-    tokio::spawn(async move {
-        let leader_sequencer_address = finalize_block_message.next_block_creator_address.clone();
-
-        let block = match encrypted_transaction_type {
-            EncryptedTransactionType::Pvde => unimplemented!(),
-            EncryptedTransactionType::Skde => skde_build_block(
-                context.clone(),
-                &cluster,
-                finalize_block_message.rollup_id.clone(),
-                finalize_block_message.rollup_block_height.clone(),
-                transaction_count,
-                leader_sequencer_address,
-                None,
-            )
-            .await
-            .unwrap(),
-            EncryptedTransactionType::NotSupport => unimplemented!(),
-        };
-
-        let rollup = Rollup::get(&finalize_block_message.rollup_id).unwrap();
-
-        let validation_platform = rollup.validation_info.platform.clone();
-        let validation_service_provider =
-            rollup.validation_info.validation_service_provider.clone();
-        let validation_info =
-            ValidationInfo::get(validation_platform, validation_service_provider).unwrap();
-        let block_commitment = block.block_commitment;
-        let rollup_block_height = finalize_block_message.rollup_block_height;
+    let block_build_job = BlockBuildJob::new(
+        finalize_block_message,
+        rollup_signature,
+        None,
+        encrypted_transaction_type,
+        transaction_count,
+        merkle_hash_scheme,
+        signature_scheme,
+        merkle_root_version,
+    );
 
-        let _ = sync_block(
-            context.clone(),
-            cluster,
-            finalize_block_message,
-            rollup_signature,
-            transaction_count,
-            block.signature,
-        )
-        .await;
-
-        submit_block_commitment(
-            context.clone(),
-            &rollup,
-            validation_platform,
-            validation_service_provider,
-            validation_info,
-            rollup_block_height,
-            &block_commitment,
-        )
-        .await;
-    });
+    enqueue_block_build_job(context, block_build_job)
 }
 
 pub async fn sync_block(
-    context: AppState,
-    cluster: Cluster,
+    context: &AppState,
+    cluster: &Cluster,
+    platform: Platform,
     finalize_block_message: FinalizeBlockMessage,
     rollup_signature: Signature,
     transaction_count: u64,
     leader_sequencer_signature: Signature,
-) {
-    let parameter = SyncBlock {
-        finalize_block_message,
-        rollup_signature,
-        transaction_count,
-        leader_sequencer_signature,
-    };
-
+) -> Result<(), Error> {
     let others_cluster_rpc_url_list = cluster.get_others_cluster_rpc_url_list();
 
     if others_cluster_rpc_url_list.is_empty() {
         tracing::info!("No other cluster RPC URLs available for synchronization");
-        return;
+        return Ok(());
     }
 
-    match context
+    let message = SyncBlockMessage {
+        finalize_block_message,
+        rollup_signature,
+        transaction_count,
+        leader_sequencer_signature,
+    };
+    let signer = context
+        .get_signer(platform)
+        .await
+        .map_err(|_| Error::SignerNotFound)?;
+    let envelope = ClusterEnvelope::seal(&signer, SyncBlock::method(), &message)?;
+    let parameter = SyncBlock { message, envelope };
+
+    context
         .rpc_client()
         .multicast(
             others_cluster_rpc_url_list.clone(),
@@ -115,28 +96,32 @@ pub async fn sync_block(
             &parameter,
             Id::Null,
         )
-        .await
-    {
-        Ok(_) => tracing::info!(
-            "Successfully synchronized block to {:?}",
-            others_cluster_rpc_url_list
-        ),
-        Err(e) => tracing::error!("Failed to synchronize block: {:?}", e),
-    }
+        .await?;
+
+    tracing::info!(
+        "Successfully synchronized block to {:?}",
+        others_cluster_rpc_url_list
+    );
+
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn follow_block(
     context: AppState,
 
-    cluster: Cluster,
-
     finalize_block_message: FinalizeBlockMessage,
+    rollup_signature: Signature,
+
     encrypted_transaction_type: EncryptedTransactionType,
 
     transaction_count: u64,
+    merkle_hash_scheme: MerkleHashScheme,
+    signature_scheme: SignatureScheme,
+    merkle_root_version: MerkleRootVersion,
 
-    signature: Signature,
-) {
+    leader_sequencer_signature: Signature,
+) -> Result<(), Error> {
     tracing::debug!(
         "Follow building block - rollup id: {:?}, block number: {:?}, transaction count: {:?}",
         finalize_block_message.rollup_id,
@@ -144,79 +129,925 @@ pub fn follow_block(
         transaction_count
     );
 
-    tokio::spawn(async move {
-        match encrypted_transaction_type {
-            EncryptedTransactionType::Pvde => unimplemented!(),
-            EncryptedTransactionType::Skde => {
-                skde_build_block(
-                    context,
-                    &cluster,
-                    finalize_block_message.rollup_id.clone(),
-                    finalize_block_message.rollup_block_height.clone(),
-                    transaction_count,
-                    finalize_block_message.next_block_creator_address.clone(),
-                    Some(signature),
-                )
-                .await
+    let block_build_job = BlockBuildJob::new(
+        finalize_block_message,
+        rollup_signature,
+        Some(leader_sequencer_signature),
+        encrypted_transaction_type,
+        transaction_count,
+        merkle_hash_scheme,
+        signature_scheme,
+        merkle_root_version,
+    );
+
+    enqueue_block_build_job(context, block_build_job)
+}
+
+/// Returns the cluster the block was finalized with, falling back to the
+/// latest one when it has already been pruned.
+pub fn get_block_cluster(rollup: &Rollup, platform_block_height: u64) -> Result<Cluster, Error> {
+    match Cluster::get(
+        rollup.platform,
+        rollup.service_provider,
+        &rollup.cluster_id,
+        platform_block_height,
+    ) {
+        Ok(cluster) => Ok(cluster),
+        Err(error) => {
+            if !error.is_none_type() {
+                return Err(Error::Database(error));
             }
-            EncryptedTransactionType::NotSupport => unimplemented!(),
+
+            let latest_cluster_block_height = LatestClusterBlockHeight::get(
+                rollup.platform,
+                rollup.service_provider,
+                &rollup.cluster_id,
+            )
+            .map_err(|_| Error::ClusterNotFound)?;
+
+            Cluster::get(
+                rollup.platform,
+                rollup.service_provider,
+                &rollup.cluster_id,
+                latest_cluster_block_height.get_block_height(),
+            )
+            .map_err(|_| Error::ClusterNotFound)
         }
-    });
+    }
 }
 
 pub fn get_encrypted_transaction_list(
     rollup_id: &str,
     rollup_block_height: u64,
     transaction_count: u64,
-) -> Vec<Option<EncryptedTransaction>> {
+) -> Result<Vec<Option<EncryptedTransaction>>, Error> {
     let mut encrypted_transaction_list =
         Vec::<Option<EncryptedTransaction>>::with_capacity(transaction_count as usize);
 
     for transaction_order in 0..transaction_count {
-        let encrypted_transaction = match EncryptedTransactionModel::get(
-            &rollup_id,
-            rollup_block_height,
-            transaction_order,
-        ) {
-            Ok(encrypted_transaction) => Some(encrypted_transaction),
-            Err(error) => {
-                if error.is_none_type() {
-                    None
-                } else {
-                    panic!("block_builder: {:?}", error);
+        let encrypted_transaction =
+            match EncryptedTransactionModel::get(rollup_id, rollup_block_height, transaction_order)
+            {
+                Ok(encrypted_transaction) => Some(encrypted_transaction),
+                Err(error) => {
+                    if error.is_none_type() {
+                        None
+                    } else {
+                        return Err(Error::Database(error));
+                    }
                 }
-            }
-        };
+            };
 
-        encrypted_transaction_list.push(encrypted_transaction.clone());
+        encrypted_transaction_list.push(encrypted_transaction);
     }
 
-    encrypted_transaction_list
+    Ok(encrypted_transaction_list)
 }
 
 pub fn get_raw_transaction_info_list(
     rollup_id: &str,
     rollup_block_height: u64,
     transaction_count: u64,
-) -> Vec<Option<(RawTransaction, bool)>> {
+) -> Result<Vec<Option<(RawTransaction, bool)>>, Error> {
     let mut raw_transaction_info_list =
         Vec::<Option<(RawTransaction, bool)>>::with_capacity(transaction_count as usize);
 
     for transaction_order in 0..transaction_count {
         let raw_transaction_info =
-            match RawTransactionModel::get(&rollup_id, rollup_block_height, transaction_order) {
+            match RawTransactionModel::get(rollup_id, rollup_block_height, transaction_order) {
                 Ok(raw_transaction_info) => Some(raw_transaction_info),
                 Err(error) => {
                     if error.is_none_type() {
                         None
                     } else {
-                        panic!("block_builder: {:?}", error);
+                        return Err(Error::Database(error));
                     }
                 }
             };
 
-        raw_transaction_info_list.push(raw_transaction_info.clone());
+        raw_transaction_info_list.push(raw_transaction_info);
+    }
+
+    Ok(raw_transaction_info_list)
+}
+
+/// Fetches every transaction of the block that is missing locally. Orders
+/// that are already stored are skipped, so this is safe to run again.
+pub async fn fetch_transaction_list(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_count: u64,
+) -> Result<(), Error> {
+    let encrypted_transaction_list =
+        get_encrypted_transaction_list(rollup_id, rollup_block_height, transaction_count)?;
+    let raw_transaction_info_list =
+        get_raw_transaction_info_list(rollup_id, rollup_block_height, transaction_count)?;
+
+    for (transaction_order, (encrypted_transaction, raw_transaction_info)) in
+        encrypted_transaction_list
+            .iter()
+            .zip(raw_transaction_info_list.iter())
+            .enumerate()
+    {
+        if encrypted_transaction.is_none() && raw_transaction_info.is_none() {
+            fetch_missing_transaction(
+                context,
+                cluster,
+                rollup_id,
+                rollup_block_height,
+                transaction_order as u64,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Decrypts the encrypted transactions of the block that have no raw
+/// transaction yet.
+pub async fn decrypt_transaction_list(
+    context: &AppState,
+    encrypted_transaction_type: EncryptedTransactionType,
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_count: u64,
+) -> Result<(), Error> {
+    match encrypted_transaction_type {
+        EncryptedTransactionType::Pvde => {
+            pvde_decrypt_transaction_list(rollup_id, rollup_block_height, transaction_count).await
+        }
+        EncryptedTransactionType::Skde => {
+            skde_decrypt_transaction_list(
+                context,
+                rollup_id,
+                rollup_block_height,
+                transaction_count,
+            )
+            .await
+        }
+        EncryptedTransactionType::NotSupport => Err(Error::UnsupportedEncryptedMempool),
+    }
+}
+
+/// Builds the block from the stored transactions once each order has its raw
+/// transaction or has been recorded as an [`InvalidTransaction`].
+#[allow(clippy::too_many_arguments)]
+pub async fn build_block_from_store(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    transaction_count: u64,
+    merkle_hash_scheme: MerkleHashScheme,
+    signature_scheme: &SignatureScheme,
+    merkle_root_version: MerkleRootVersion,
+    leader_sequencer_address: Address,
+    signature: Option<Signature>,
+) -> Result<Block, Error> {
+    let encrypted_transaction_list =
+        get_encrypted_transaction_list(&rollup.rollup_id, rollup_block_height, transaction_count)?;
+
+    let raw_transaction_list =
+        get_raw_transaction_info_list(&rollup.rollup_id, rollup_block_height, transaction_count)?
+            .into_iter()
+            .enumerate()
+            .map(|(transaction_order, raw_transaction_info)| {
+                let (raw_transaction, is_direct_sent) = match raw_transaction_info {
+                    Some(raw_transaction_info) => raw_transaction_info,
+                    None => {
+                        return get_invalid_transaction(
+                            &rollup.rollup_id,
+                            rollup_block_height,
+                            transaction_order as u64,
+                        )
+                        .map(|invalid_transaction| Err(invalid_transaction.reason));
+                    }
+                };
+
+                if is_direct_sent && encrypted_transaction_list[transaction_order].is_some() {
+                    tracing::error!("Raw transaction and encrypted transaction are both present.");
+                }
+
+                Ok(Ok(raw_transaction))
+            })
+            .collect::<Result<Vec<Result<RawTransaction, InvalidTransactionReason>>, Error>>()?;
+
+    commit_block(
+        context,
+        cluster,
+        rollup,
+        rollup_block_height,
+        encrypted_transaction_list,
+        raw_transaction_list,
+        merkle_hash_scheme,
+        signature_scheme,
+        merkle_root_version,
+        leader_sequencer_address,
+        signature,
+    )
+    .await
+}
+
+/// Stores the outcome of decrypting the transaction of an order. A transaction
+/// that cannot be decrypted or deserialized is recorded as an
+/// [`InvalidTransaction`] instead of failing the whole block.
+pub fn put_decrypted_transaction(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_order: u64,
+    decrypted_transaction: Result<RawTransaction, InvalidTransactionReason>,
+) -> Result<(), Error> {
+    match decrypted_transaction {
+        Ok(raw_transaction) => RawTransactionModel::put(
+            rollup_id,
+            rollup_block_height,
+            transaction_order,
+            raw_transaction,
+            false,
+        )
+        .map_err(Error::Database),
+        Err(reason) => {
+            tracing::warn!(
+                "Invalid transaction - rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?} / reason: {:?}",
+                rollup_id,
+                rollup_block_height,
+                transaction_order,
+                reason
+            );
+
+            InvalidTransaction {
+                transaction_order,
+                reason,
+            }
+            .put(rollup_id, rollup_block_height, transaction_order)
+            .map_err(Error::Database)
+        }
+    }
+}
+
+/// Returns `true` if the order was already recorded as an
+/// [`InvalidTransaction`].
+pub fn is_invalid_transaction(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_order: u64,
+) -> Result<bool, Error> {
+    match InvalidTransaction::get(rollup_id, rollup_block_height, transaction_order) {
+        Ok(_) => Ok(true),
+        Err(error) if error.is_none_type() => Ok(false),
+        Err(error) => Err(Error::Database(error)),
     }
+}
+
+/// Returns the recorded [`InvalidTransaction`] of an order without a raw
+/// transaction.
+fn get_invalid_transaction(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_order: u64,
+) -> Result<InvalidTransaction, Error> {
+    InvalidTransaction::get(rollup_id, rollup_block_height, transaction_order).map_err(|error| {
+        if error.is_none_type() {
+            tracing::error!(
+                "Raw transaction not found - rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?}",
+                rollup_id,
+                rollup_block_height,
+                transaction_order
+            );
+            Error::TransactionNotFound
+        } else {
+            Error::Database(error)
+        }
+    })
+}
+
+/// Fetches a transaction that is missing locally from the other sequencers in
+/// the cluster.
+///
+/// Transactions that were not sent directly also have their encrypted form
+/// fetched and stored, so that the block keeps the same
+/// `encrypted_transaction_list` as the one built by the leader.
+pub async fn fetch_missing_transaction(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_order: u64,
+) -> Result<(RawTransaction, bool), Error> {
+    let (raw_transaction, is_direct_sent) = fetch_raw_transaction_info(
+        context.rpc_client(),
+        cluster,
+        rollup_id,
+        rollup_block_height,
+        transaction_order,
+    )
+    .await?;
 
-    raw_transaction_info_list
+    if !is_direct_sent {
+        let encrypted_transaction = fetch_encrypted_transaction(
+            context.rpc_client(),
+            cluster,
+            rollup_id,
+            rollup_block_height,
+            transaction_order,
+        )
+        .await?;
+
+        EncryptedTransactionModel::put(
+            rollup_id,
+            rollup_block_height,
+            transaction_order,
+            &encrypted_transaction,
+        )
+        .map_err(Error::Database)?;
+    }
+
+    RawTransactionModel::put(
+        rollup_id,
+        rollup_block_height,
+        transaction_order,
+        raw_transaction.clone(),
+        is_direct_sent,
+    )
+    .map_err(Error::Database)?;
+
+    Ok((raw_transaction, is_direct_sent))
+}
+
+/// Rebuilds the raw transaction from the decrypted payload of an encrypted
+/// transaction.
+pub fn build_raw_transaction(
+    transaction_data: &TransactionData,
+    decrypted_data: &str,
+) -> Result<(RawTransaction, PlainData), Error> {
+    match transaction_data {
+        TransactionData::Eth(transaction_data) => {
+            let eth_plain_data: EthPlainData =
+                serde_json::from_str(decrypted_data).map_err(|e| {
+                    tracing::error!("Failed to parse decrypted data: {:?}", e);
+                    Error::Deserialize
+                })?;
+
+            let rollup_transaction = transaction_data
+                .open_data
+                .convert_to_rollup_transaction(&eth_plain_data);
+
+            let eth_raw_transaction = EthRawTransaction::from(to_raw_tx(rollup_transaction));
+            let raw_transaction = RawTransaction::from(eth_raw_transaction);
+
+            Ok((raw_transaction, PlainData::from(eth_plain_data)))
+        }
+        TransactionData::EthBundle(transaction_data) => {
+            let eth_bundle_plain_data: EthBundlePlainData = serde_json::from_str(decrypted_data)
+                .map_err(|e| {
+                    tracing::error!("Failed to parse decrypted bundle data: {:?}", e);
+                    Error::Deserialize
+                })?;
+
+            let rollup_transaction = RollupTransaction::EthBundle(
+                transaction_data
+                    .open_data
+                    .convert_to_rollup_transaction_list(&eth_bundle_plain_data)?,
+            );
+            let raw_transaction = rollup_transaction.to_raw_transaction()?;
+
+            Ok((raw_transaction, PlainData::from(eth_bundle_plain_data)))
+        }
+    }
+}
+
+/// Builds the Merkle tree over the ordered transactions, signs the block
+/// commitment unless the leader's signature is given and persists the block.
+/// A given leader signature must be over the locally rebuilt commitment.
+///
+/// Each transaction order is a single Merkle leaf, while bundles are expanded
+/// into the block's `raw_transaction_list`.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_block(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    encrypted_transaction_list: Vec<Option<EncryptedTransaction>>,
+    raw_transaction_list: Vec<Result<RawTransaction, InvalidTransactionReason>>,
+    merkle_hash_scheme: MerkleHashScheme,
+    signature_scheme: &SignatureScheme,
+    merkle_root_version: MerkleRootVersion,
+    leader_sequencer_address: Address,
+    signature: Option<Signature>,
+) -> Result<Block, Error> {
+    let (
+        transaction_hash_list,
+        raw_transaction_list,
+        invalid_transaction_list,
+        decrypted_sender_nonce_list,
+    ) = check_decrypted_transaction_list(
+        &rollup.rollup_id,
+        rollup_block_height,
+        &encrypted_transaction_list,
+        raw_transaction_list,
+    )?;
+
+    let merkle_tree = MerkleTree::with_hash_scheme(merkle_hash_scheme);
+    for transaction_hash in transaction_hash_list.iter() {
+        merkle_tree.add_data(transaction_hash.as_ref()).await;
+    }
+
+    merkle_tree.finalize_tree(merkle_root_version).await;
+    let block_commitment = merkle_tree.get_merkle_root().await;
+
+    let signature = match signature {
+        Some(signature) => {
+            verify_block_commitment(
+                context,
+                cluster,
+                rollup,
+                rollup_block_height,
+                block_commitment,
+                &transaction_hash_list,
+                signature_scheme,
+                &leader_sequencer_address,
+                &signature,
+            )
+            .await?;
+
+            signature
+        }
+        None => {
+            let signer = context
+                .get_signer(rollup.platform)
+                .await
+                .map_err(|_| Error::SignerNotFound)?;
+            signature_scheme.sign(
+                &signer,
+                &context.config().signing_key,
+                &BlockCommitmentMessage {
+                    rollup_id: &rollup.rollup_id,
+                    rollup_block_height,
+                    block_commitment,
+                },
+            )?
+        }
+    };
+
+    let raw_transaction_list = raw_transaction_list
+        .into_iter()
+        .flat_map(RawTransaction::expand)
+        .collect();
+
+    let block = Block::new(
+        rollup_block_height,
+        encrypted_transaction_list,
+        raw_transaction_list,
+        transaction_hash_list,
+        invalid_transaction_list,
+        signature,
+        BlockCommitment::from(block_commitment),
+        leader_sequencer_address,
+        merkle_hash_scheme,
+        signature_scheme.clone(),
+        merkle_root_version,
+    );
+
+    Block::put(&block, &rollup.rollup_id, rollup_block_height).map_err(Error::Database)?;
+    for sender_nonce_list in decrypted_sender_nonce_list {
+        SenderNonce::follow(&rollup.rollup_id, sender_nonce_list);
+    }
+    prune_transaction_order_index(context, &rollup.rollup_id, rollup_block_height);
+    context
+        .subscription_manager()
+        .publish_block(&rollup.rollup_id, &block);
+
+    tracing::info!(
+        "Block built - block_height: {:?} / transaction_count: {:?}",
+        block.block_height,
+        block.raw_transaction_list.len()
+    );
+
+    Ok(block)
+}
+
+/// Prunes the [`TransactionOrderIndex`] entries of the block that falls out
+/// of the `duplicate_transaction_retention` window once `rollup_block_height`
+/// is built.
+fn prune_transaction_order_index(context: &AppState, rollup_id: &str, rollup_block_height: u64) {
+    let pruned_block_height =
+        match rollup_block_height.checked_sub(context.config().duplicate_transaction_retention) {
+            Some(pruned_block_height) => pruned_block_height,
+            None => return,
+        };
+
+    match Block::get(rollup_id, pruned_block_height) {
+        Ok(block) => TransactionOrderIndex::prune(rollup_id, &block),
+        Err(error) if error.is_none_type() => {}
+        Err(error) => tracing::warn!(
+            "Failed to get block to prune - rollup_id: {:?} / rollup_block_height: {:?} / error: {:?}",
+            rollup_id,
+            pruned_block_height,
+            error
+        ),
+    }
+}
+
+/// Returns the Merkle leaves of the block along with its valid raw
+/// transactions, its invalid ones and the `(sender, nonce)` of each valid
+/// decrypted transaction.
+///
+/// The leaf of an encrypted transaction is the `raw_tx_hash` of its open data,
+/// which the order commitment was issued for. A decrypted transaction that
+/// does not hash to it is left out of the raw transactions, so that a user
+/// cannot commit to one transaction and execute another. Orders that failed
+/// to decrypt keep their committed leaf and are left out the same way.
+///
+/// The sender nonces of valid decrypted transactions are only tracked once the
+/// block is stored, as their senders cannot be trusted before decryption.
+#[allow(clippy::type_complexity)]
+fn check_decrypted_transaction_list(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    encrypted_transaction_list: &[Option<EncryptedTransaction>],
+    raw_transaction_list: Vec<Result<RawTransaction, InvalidTransactionReason>>,
+) -> Result<
+    (
+        Vec<RawTransactionHash>,
+        Vec<RawTransaction>,
+        Vec<InvalidTransaction>,
+        Vec<Result<Vec<(eth_types::Address, u64)>, Error>>,
+    ),
+    Error,
+> {
+    let mut transaction_hash_list = Vec::with_capacity(raw_transaction_list.len());
+    let mut valid_raw_transaction_list = Vec::with_capacity(raw_transaction_list.len());
+    let mut invalid_transaction_list = Vec::new();
+    let mut decrypted_sender_nonce_list = Vec::new();
+
+    for (transaction_order, raw_transaction) in raw_transaction_list.into_iter().enumerate() {
+        let encrypted_transaction = match encrypted_transaction_list.get(transaction_order) {
+            Some(Some(encrypted_transaction)) => Some(encrypted_transaction),
+            _ => None,
+        };
+
+        let raw_transaction = match (raw_transaction, encrypted_transaction) {
+            (Ok(raw_transaction), _) => raw_transaction,
+            (Err(reason), Some(encrypted_transaction)) => {
+                invalid_transaction_list.push(InvalidTransaction {
+                    transaction_order: transaction_order as u64,
+                    reason,
+                });
+                transaction_hash_list.push(encrypted_transaction.raw_transaction_hash());
+                continue;
+            }
+            // Only an encrypted transaction can fail to decrypt.
+            (Err(_), None) => return Err(Error::TransactionNotFound),
+        };
+        let raw_transaction_hash = raw_transaction.raw_transaction_hash()?;
+
+        let committed_transaction_hash = match encrypted_transaction {
+            Some(encrypted_transaction) => encrypted_transaction.raw_transaction_hash(),
+            None => raw_transaction_hash.clone(),
+        };
+
+        if committed_transaction_hash != raw_transaction_hash {
+            tracing::warn!(
+                "Decrypted transaction hash mismatch - rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?} / committed: {:?} / decrypted: {:?}",
+                rollup_id,
+                rollup_block_height,
+                transaction_order,
+                committed_transaction_hash,
+                raw_transaction_hash
+            );
+
+            invalid_transaction_list.push(InvalidTransaction {
+                transaction_order: transaction_order as u64,
+                reason: InvalidTransactionReason::TransactionHashMismatch {
+                    decrypted_transaction_hash: raw_transaction_hash,
+                },
+            });
+        } else {
+            if encrypted_transaction.is_some() {
+                decrypted_sender_nonce_list.push(raw_transaction.validate(None));
+            }
+            valid_raw_transaction_list.push(raw_transaction);
+        }
+
+        transaction_hash_list.push(committed_transaction_hash);
+    }
+
+    Ok((
+        transaction_hash_list,
+        valid_raw_transaction_list,
+        invalid_transaction_list,
+        decrypted_sender_nonce_list,
+    ))
+}
+
+/// Verifies the leader's signature over the locally rebuilt block commitment.
+/// On divergence, the leader's block is fetched to record a [`BlockMismatch`]
+/// with the differing leaves.
+#[allow(clippy::too_many_arguments)]
+async fn verify_block_commitment(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    block_commitment: [u8; 32],
+    transaction_hash_list: &[RawTransactionHash],
+    signature_scheme: &SignatureScheme,
+    leader_sequencer_address: &Address,
+    leader_sequencer_signature: &Signature,
+) -> Result<(), Error> {
+    let block_commitment_message = BlockCommitmentMessage {
+        rollup_id: &rollup.rollup_id,
+        rollup_block_height,
+        block_commitment,
+    };
+    if signature_scheme
+        .verify(
+            rollup.platform.into(),
+            leader_sequencer_signature,
+            &block_commitment_message,
+            leader_sequencer_address,
+        )
+        .is_ok()
+    {
+        return Ok(());
+    }
+
+    let signer = context
+        .get_signer(rollup.platform)
+        .await
+        .map_err(|_| Error::SignerNotFound)?;
+    let local_signature = signature_scheme.sign(
+        &signer,
+        &context.config().signing_key,
+        &block_commitment_message,
+    )?;
+
+    let (leader_block_commitment, transaction_hash_diff_list) = match fetch_leader_block(
+        context,
+        cluster,
+        &rollup.rollup_id,
+        rollup_block_height,
+        leader_sequencer_address,
+    )
+    .await
+    {
+        Ok(leader_block) => (
+            Some(leader_block.block_commitment),
+            BlockMismatch::diff_transaction_hash_list(
+                transaction_hash_list,
+                &leader_block.transaction_hash_list,
+            ),
+        ),
+        Err(error) => {
+            tracing::warn!("Failed to fetch the leader block: {:?}", error);
+            (None, Vec::new())
+        }
+    };
+
+    let block_mismatch = BlockMismatch {
+        rollup_block_height,
+        leader_sequencer_address: leader_sequencer_address.clone(),
+        leader_sequencer_signature: leader_sequencer_signature.clone(),
+        leader_block_commitment,
+        local_sequencer_address: signer.address().clone(),
+        local_signature,
+        local_block_commitment: BlockCommitment::from(block_commitment),
+        transaction_hash_diff_list,
+    };
+
+    tracing::error!(
+        "Block commitment mismatch - rollup_id: {:?} / rollup_block_height: {:?} / local: {:?} / leader: {:?} / diff count: {:?}",
+        rollup.rollup_id,
+        rollup_block_height,
+        block_mismatch.local_block_commitment,
+        block_mismatch.leader_block_commitment,
+        block_mismatch.transaction_hash_diff_list.len()
+    );
+
+    block_mismatch
+        .put(&rollup.rollup_id, rollup_block_height)
+        .map_err(Error::Database)?;
+
+    let mut block_mismatch_list =
+        BlockMismatchList::get_mut_or(&rollup.rollup_id, BlockMismatchList::default)
+            .map_err(Error::Database)?;
+    block_mismatch_list.insert(rollup_block_height);
+    block_mismatch_list.update().map_err(Error::Database)?;
+
+    Err(Error::BlockMismatch)
+}
+
+async fn fetch_leader_block(
+    context: &AppState,
+    cluster: &Cluster,
+    rollup_id: &str,
+    rollup_block_height: u64,
+    leader_sequencer_address: &Address,
+) -> Result<GetBlockResponse, Error> {
+    let leader_external_rpc_url = cluster
+        .get_sequencer_rpc_info(leader_sequencer_address)
+        .and_then(|sequencer_rpc_info| sequencer_rpc_info.external_rpc_url)
+        .ok_or(Error::EmptyLeaderClusterRpcUrl)?;
+
+    let parameter = GetBlock {
+        rollup_id: rollup_id.to_owned(),
+        rollup_block_height,
+    };
+
+    let leader_block: GetBlockResponse = context
+        .rpc_client()
+        .request(
+            leader_external_rpc_url,
+            GetBlock::method(),
+            &parameter,
+            Id::Null,
+        )
+        .await?;
+
+    Ok(leader_block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Well-known development keys.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const OTHER_SIGNING_KEY: &str =
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn signed_raw_transaction(signing_key: &str) -> RawTransaction {
+        RawTransaction::from(sign_eth_transaction(signing_key, Some(1), 0))
+    }
+
+    /// Encrypted transaction whose open data commits to `raw_transaction`.
+    fn encrypted_transaction(raw_transaction: &RawTransaction) -> EncryptedTransaction {
+        let raw_transaction_hash = raw_transaction.raw_transaction_hash().unwrap();
+        let open_data = EthOpenData::from(eth_types::Transaction {
+            hash: eth_types::H256::from(raw_transaction_hash.as_bytes().unwrap()),
+            ..Default::default()
+        });
+        let transaction_data =
+            EthTransactionData::new(EncryptedData::from("0x01".to_owned()), open_data);
+
+        EncryptedTransaction::Skde(SkdeEncryptedTransaction::new(transaction_data.into(), 0))
+    }
+
+    #[test]
+    fn decrypted_transaction_of_the_committed_hash_is_kept() {
+        init_test_database();
+        let rollup_id = "block_builder_kept";
+        let raw_transaction = signed_raw_transaction(SIGNING_KEY);
+        let encrypted_transaction_list = [Some(encrypted_transaction(&raw_transaction)), None];
+
+        let (
+            transaction_hash_list,
+            raw_transaction_list,
+            invalid_transaction_list,
+            decrypted_sender_nonce_list,
+        ) = check_decrypted_transaction_list(
+            rollup_id,
+            0,
+            &encrypted_transaction_list,
+            vec![
+                Ok(raw_transaction.clone()),
+                Ok(signed_raw_transaction(OTHER_SIGNING_KEY)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            transaction_hash_list,
+            vec![
+                raw_transaction.raw_transaction_hash().unwrap(),
+                signed_raw_transaction(OTHER_SIGNING_KEY)
+                    .raw_transaction_hash()
+                    .unwrap(),
+            ]
+        );
+        assert_eq!(raw_transaction_list.len(), 2);
+        assert!(invalid_transaction_list.is_empty());
+        // Only the decrypted transaction is followed, once the block is stored.
+        let sender_nonce_list = decrypted_sender_nonce_list
+            .into_iter()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+        assert_eq!(
+            sender_nonce_list,
+            vec![raw_transaction.validate(None).unwrap()]
+        );
+        let (sender, _) = sender_nonce_list[0][0];
+        assert!(SenderNonce::get_by_sender(rollup_id, &sender)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn decrypted_transaction_of_another_hash_is_left_out() {
+        init_test_database();
+        let committed_raw_transaction = signed_raw_transaction(SIGNING_KEY);
+        let decrypted_raw_transaction = signed_raw_transaction(OTHER_SIGNING_KEY);
+        let encrypted_transaction_list = [Some(encrypted_transaction(&committed_raw_transaction))];
+
+        let (transaction_hash_list, raw_transaction_list, invalid_transaction_list, _) =
+            check_decrypted_transaction_list(
+                "block_builder_mismatch",
+                0,
+                &encrypted_transaction_list,
+                vec![Ok(decrypted_raw_transaction.clone())],
+            )
+            .unwrap();
+
+        assert_eq!(
+            transaction_hash_list,
+            vec![committed_raw_transaction.raw_transaction_hash().unwrap()]
+        );
+        assert!(raw_transaction_list.is_empty());
+        assert_eq!(
+            invalid_transaction_list,
+            vec![InvalidTransaction {
+                transaction_order: 0,
+                reason: InvalidTransactionReason::TransactionHashMismatch {
+                    decrypted_transaction_hash: decrypted_raw_transaction
+                        .raw_transaction_hash()
+                        .unwrap(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn transaction_failing_to_decrypt_keeps_its_committed_leaf() {
+        init_test_database();
+        let committed_raw_transaction = signed_raw_transaction(SIGNING_KEY);
+        let encrypted_transaction_list = [Some(encrypted_transaction(&committed_raw_transaction))];
+
+        let (transaction_hash_list, raw_transaction_list, invalid_transaction_list, _) =
+            check_decrypted_transaction_list(
+                "block_builder_decryption_failure",
+                0,
+                &encrypted_transaction_list,
+                vec![Err(InvalidTransactionReason::DecryptionFailure)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            transaction_hash_list,
+            vec![committed_raw_transaction.raw_transaction_hash().unwrap()]
+        );
+        assert!(raw_transaction_list.is_empty());
+        assert_eq!(
+            invalid_transaction_list,
+            vec![InvalidTransaction {
+                transaction_order: 0,
+                reason: InvalidTransactionReason::DecryptionFailure,
+            }]
+        );
+    }
+
+    #[test]
+    fn raw_transaction_cannot_fail_to_decrypt() {
+        init_test_database();
+
+        assert!(matches!(
+            check_decrypted_transaction_list(
+                "block_builder_raw_failure",
+                0,
+                &[None],
+                vec![Err(InvalidTransactionReason::DeserializationFailure)],
+            ),
+            Err(Error::TransactionNotFound)
+        ));
+    }
+
+    #[test]
+    fn failed_decryption_is_recorded_for_a_retried_build() {
+        init_test_database();
+        let rollup_id = "block_builder_put_decrypted";
+
+        put_decrypted_transaction(rollup_id, 0, 0, Ok(signed_raw_transaction(SIGNING_KEY)))
+            .unwrap();
+        put_decrypted_transaction(
+            rollup_id,
+            0,
+            1,
+            Err(InvalidTransactionReason::DeserializationFailure),
+        )
+        .unwrap();
+
+        assert!(!is_invalid_transaction(rollup_id, 0, 0).unwrap());
+        assert!(is_invalid_transaction(rollup_id, 0, 1).unwrap());
+        assert_eq!(
+            get_invalid_transaction(rollup_id, 0, 1).unwrap().reason,
+            InvalidTransactionReason::DeserializationFailure
+        );
+        assert!(matches!(
+            get_invalid_transaction(rollup_id, 0, 2),
+            Err(Error::TransactionNotFound)
+        ));
+    }
 }
@@ -0,0 +1,196 @@
+use std::{collections::HashMap, str::FromStr};
+
+use num_bigint::BigUint;
+use pvde::{
+    encryption::poseidon_encryption::{decrypt, PoseidonEncryptionKey},
+    poseidon::hash,
+};
+
+use super::{
+    build_raw_transaction, get_encrypted_transaction_list, get_raw_transaction_info_list,
+    is_invalid_transaction, put_decrypted_transaction,
+};
+use crate::{error::Error, types::*};
+
+pub async fn pvde_decrypt_transaction_list(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_count: u64,
+) -> Result<(), Error> {
+    let rollup = Rollup::get(rollup_id).map_err(|_| Error::RollupNotFound)?;
+
+    // Solved keys keyed by the committed key hash of their time-lock puzzle.
+    let mut decryption_keys: HashMap<String, PoseidonEncryptionKey> = HashMap::new();
+
+    let encrypted_transaction_list =
+        get_encrypted_transaction_list(rollup_id, rollup_block_height, transaction_count)?;
+    let raw_transaction_info_list =
+        get_raw_transaction_info_list(rollup_id, rollup_block_height, transaction_count)?;
+
+    for (transaction_order, (encrypted_transaction, raw_transaction_info)) in
+        encrypted_transaction_list
+            .into_iter()
+            .zip(raw_transaction_info_list.iter())
+            .enumerate()
+    {
+        let encrypted_transaction = match (encrypted_transaction, raw_transaction_info) {
+            (Some(encrypted_transaction), None) => encrypted_transaction,
+            _ => continue,
+        };
+
+        if is_invalid_transaction(rollup_id, rollup_block_height, transaction_order as u64)? {
+            continue;
+        }
+
+        let pvde_encrypted_transaction = encrypted_transaction.try_into_pvde_transaction()?;
+
+        let decrypted_transaction = decrypt_pvde_transaction(
+            &pvde_encrypted_transaction,
+            rollup.time_lock_puzzle_params.as_ref(),
+            &mut decryption_keys,
+        )
+        .await?;
+
+        put_decrypted_transaction(
+            rollup_id,
+            rollup_block_height,
+            transaction_order as u64,
+            decrypted_transaction,
+        )?;
+    }
+
+    Ok(())
+}
+
+async fn decrypt_pvde_transaction(
+    pvde_encrypted_transaction: &PvdeEncryptedTransaction,
+    time_lock_puzzle_params: Option<&TimeLockPuzzleParams>,
+    decryption_keys: &mut HashMap<String, PoseidonEncryptionKey>,
+) -> Result<Result<RawTransaction, InvalidTransactionReason>, Error> {
+    let time_lock_puzzle = pvde_encrypted_transaction.time_lock_puzzle.clone();
+
+    // Checked again as the transaction may have been ordered by another
+    // leader.
+    match time_lock_puzzle.check_params(time_lock_puzzle_params) {
+        Ok(()) => {}
+        Err(Error::InvalidTimeLockPuzzle) => {
+            return Ok(Err(InvalidTransactionReason::DecryptionFailure));
+        }
+        Err(error) => return Err(error),
+    }
+
+    // Solve or reuse the decryption key
+    let decryption_key = match decryption_keys.entry(time_lock_puzzle.k_hash_value.clone()) {
+        std::collections::hash_map::Entry::Vacant(entry) => {
+            tracing::info!(
+                "Solving time-lock puzzle - t: {:?} / k_hash_value: {:?}",
+                time_lock_puzzle.t,
+                time_lock_puzzle.k_hash_value
+            );
+
+            // Solving the puzzle is sequential squaring by design, so keep it
+            // off the async runtime.
+            let decryption_key = match tokio::task::spawn_blocking(move || {
+                solve_time_lock_puzzle(&time_lock_puzzle)
+            })
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to solve time-lock puzzle: {:?}", e);
+                Error::InvalidTimeLockPuzzle
+            })? {
+                Ok(decryption_key) => decryption_key,
+                Err(_) => return Ok(Err(InvalidTransactionReason::DecryptionFailure)),
+            };
+
+            entry.insert(decryption_key).clone()
+        }
+        std::collections::hash_map::Entry::Occupied(entry) => entry.get().clone(),
+    };
+
+    let transaction_data = &pvde_encrypted_transaction.transaction_data;
+    let encrypted_data = transaction_data.encrypted_data().clone();
+
+    let decrypted_data = match decrypt(encrypted_data.as_ref(), &decryption_key) {
+        Ok(decrypted_data) => decrypted_data,
+        Err(e) => {
+            tracing::error!("Decryption failed for PVDE transaction: {:?}", e);
+            return Ok(Err(InvalidTransactionReason::DecryptionFailure));
+        }
+    };
+
+    Ok(build_raw_transaction(transaction_data, &decrypted_data)
+        .map(|(raw_transaction, _plain_data)| raw_transaction)
+        .map_err(|_| InvalidTransactionReason::DeserializationFailure))
+}
+
+/// Solves `k = o^(2^t) mod n` and verifies the solution against the key hash
+/// committed in the puzzle before deriving the decryption key from it.
+fn solve_time_lock_puzzle(
+    time_lock_puzzle: &TimeLockPuzzle,
+) -> Result<PoseidonEncryptionKey, Error> {
+    let o = BigUint::from_str(&time_lock_puzzle.o).map_err(|_| Error::InvalidTimeLockPuzzle)?;
+    let n = BigUint::from_str(&time_lock_puzzle.n).map_err(|_| Error::InvalidTimeLockPuzzle)?;
+    if n == BigUint::from(0u32) {
+        return Err(Error::InvalidTimeLockPuzzle);
+    }
+
+    let two = BigUint::from(2u32);
+    let k = (0..time_lock_puzzle.t).fold(o % &n, |k, _| k.modpow(&two, &n));
+
+    let k_hash = hash(&k);
+    let k_hash_value = const_hex::decode(&time_lock_puzzle.k_hash_value)
+        .map_err(|_| Error::InvalidTimeLockPuzzle)?;
+    if k_hash.as_slice() != k_hash_value.as_slice() {
+        tracing::error!(
+            "Time-lock puzzle solution does not match the committed key hash: {:?}",
+            time_lock_puzzle.k_hash_value
+        );
+        return Err(Error::InvalidTimeLockPuzzle);
+    }
+
+    Ok(PoseidonEncryptionKey::new(k_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_lock_puzzle(o: &str, n: &str, k_hash_value: &str) -> TimeLockPuzzle {
+        TimeLockPuzzle {
+            t: 2,
+            o: o.to_owned(),
+            n: n.to_owned(),
+            k_hash_value: k_hash_value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn malformed_time_lock_puzzle_is_not_solved() {
+        for time_lock_puzzle in [
+            time_lock_puzzle("not a number", "35", "0x00"),
+            time_lock_puzzle("2", "0", "0x00"),
+            time_lock_puzzle("2", "35", "not hex"),
+        ] {
+            assert!(matches!(
+                solve_time_lock_puzzle(&time_lock_puzzle),
+                Err(Error::InvalidTimeLockPuzzle)
+            ));
+        }
+    }
+
+    #[test]
+    fn solution_must_match_the_committed_key_hash() {
+        // 2^(2^2) mod 35 = 16
+        let k_hash = hash(&BigUint::from(16u32));
+        let mut k_hash_value = k_hash.as_slice().to_vec();
+        k_hash_value[0] ^= 1;
+
+        let time_lock_puzzle =
+            time_lock_puzzle("2", "35", &const_hex::encode_prefixed(k_hash_value));
+
+        assert!(matches!(
+            solve_time_lock_puzzle(&time_lock_puzzle),
+            Err(Error::InvalidTimeLockPuzzle)
+        ));
+    }
+}
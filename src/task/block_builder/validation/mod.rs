@@ -2,36 +2,63 @@ use std::time::Duration;
 
 use tokio::time::sleep;
 
-use super::{BlockCommitment, Platform, Rollup, ValidationInfo, ValidationServiceProvider};
-use crate::{client::validation, state::AppState};
+use super::{BlockCommitment, Rollup, ValidationInfo};
+use crate::{client::validation, error::Error, state::AppState};
 
+/// Returns the hash of the transaction that registered the block commitment,
+/// if it was submitted and the validation client reports one.
 pub async fn submit_block_commitment(
-    context: AppState,
+    context: &AppState,
     rollup: &Rollup,
-    validation_platform: Platform,
-    validation_service_provider: ValidationServiceProvider,
-    validation_info: ValidationInfo,
     rollup_block_height: u64,
     block_commitment: &BlockCommitment,
-) {
-    let block_commitment = block_commitment.as_bytes().unwrap();
-    if (rollup_block_height % 201600) == 0 {
-        tracing::info!(
-            "Submit block commitment - rollup_id: {:?}, rollup_block_height: {:?}, block_commitment: {:?}",
-            rollup.rollup_id,
-            rollup_block_height,
-            block_commitment
-        );
+) -> Result<Option<String>, Error> {
+    if (rollup_block_height % 201600) != 0 {
+        return Ok(None);
+    }
 
-        match validation_info {
-            // TODO: we have to manage the nonce for the register block commitment.
-            ValidationInfo::EigenLayer(_) => {
-                let validation_client: validation::eigenlayer::ValidationClient = context
-                    .get_validation_client(validation_platform, validation_service_provider)
-                    .await
-                    .unwrap();
+    let validation_platform = rollup.validation_info.platform;
+    let validation_service_provider = rollup.validation_info.validation_service_provider;
+    let validation_info = ValidationInfo::get(validation_platform, validation_service_provider)
+        .map_err(Error::Database)?;
+
+    let block_commitment = block_commitment.as_bytes().map_err(|_| Error::Convert)?;
+
+    tracing::info!(
+        "Submit block commitment - rollup_id: {:?}, rollup_block_height: {:?}, block_commitment: {:?}",
+        rollup.rollup_id,
+        rollup_block_height,
+        block_commitment
+    );
+
+    match validation_info {
+        // TODO: we have to manage the nonce for the register block commitment.
+        ValidationInfo::EigenLayer(_) => {
+            let validation_client: validation::eigenlayer::ValidationClient = context
+                .get_validation_client(validation_platform, validation_service_provider)
+                .await
+                .map_err(Error::CachedKvStore)?;
+
+            validation_client
+                .publisher()
+                .register_block_commitment(
+                    &rollup.cluster_id,
+                    &rollup.rollup_id,
+                    rollup_block_height,
+                    &block_commitment,
+                )
+                .await
+                .map_err(|error| Error::ValidationClient(error.to_string().into()))?;
+        }
+        ValidationInfo::Symbiotic(_) => {
+            let validation_client: validation::symbiotic::ValidationClient = context
+                .get_validation_client(validation_platform, validation_service_provider)
+                .await
+                .map_err(Error::CachedKvStore)?;
 
-                validation_client
+            let mut last_error = String::new();
+            for _ in 0..10 {
+                match validation_client
                     .publisher()
                     .register_block_commitment(
                         &rollup.cluster_id,
@@ -40,40 +67,26 @@ pub async fn submit_block_commitment(
                         &block_commitment,
                     )
                     .await
-                    .unwrap();
-            }
-            ValidationInfo::Symbiotic(_) => {
-                let validation_client: validation::symbiotic::ValidationClient = context
-                    .get_validation_client(validation_platform, validation_service_provider)
-                    .await
-                    .unwrap();
-
-                for _ in 0..10 {
-                    match validation_client
-                        .publisher()
-                        .register_block_commitment(
-                            &rollup.cluster_id,
-                            &rollup.rollup_id,
-                            rollup_block_height,
-                            &block_commitment,
-                        )
-                        .await
-                        .map_err(|error| error.to_string())
-                    {
-                        Ok(transaction_hash) => {
-                            tracing::info!(
-                                "Registered block commitment - transaction hash: {:?}",
-                                transaction_hash
-                            );
-                            break;
-                        }
-                        Err(error) => {
-                            tracing::warn!("{:?}", error);
-                            sleep(Duration::from_secs(2)).await;
-                        }
+                    .map_err(|error| error.to_string())
+                {
+                    Ok(transaction_hash) => {
+                        tracing::info!(
+                            "Registered block commitment - transaction hash: {:?}",
+                            transaction_hash
+                        );
+                        return Ok(Some(format!("{:?}", transaction_hash)));
+                    }
+                    Err(error) => {
+                        tracing::warn!("{:?}", error);
+                        last_error = error;
+                        sleep(Duration::from_secs(2)).await;
                     }
                 }
             }
+
+            return Err(Error::ValidationClient(last_error.into()));
         }
     }
+
+    Ok(None)
 }
@@ -0,0 +1,298 @@
+use radius_sdk::json_rpc::{client::Id, server::RpcParameter};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    error::Error,
+    rpc::external::{
+        GetTransactionRange, GetTransactionRangeResponse, MAX_TRANSACTION_RANGE_LENGTH,
+    },
+    state::AppState,
+    task::get_block_cluster,
+    types::*,
+    verifier::{verify_block_inclusion, verify_leader_sign_order_commitment},
+};
+
+const WATCH_INTERVAL_SECS: u64 = 10;
+
+/// Stores an order commitment received from another sequencer. A signed order
+/// commitment must be signed by a leader of the block. One that conflicts
+/// with the stored one is recorded as evidence and the stored one is kept.
+pub fn put_order_commitment(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    transaction_order: u64,
+    order_commitment: OrderCommitment,
+) -> Result<(), Error> {
+    let rollup_id = rollup.rollup_id.as_str();
+
+    if let Some(sign_order_commitment) = order_commitment.as_sign_order_commitment() {
+        verify_leader_sign_order_commitment(
+            rollup,
+            rollup_block_height,
+            transaction_order,
+            sign_order_commitment,
+        )
+        .map_err(|error| {
+            tracing::warn!(
+                "Unverified order commitment - rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?} / error: {:?}",
+                rollup_id,
+                rollup_block_height,
+                transaction_order,
+                error
+            );
+            error
+        })?;
+    }
+
+    match OrderCommitment::get(rollup_id, rollup_block_height, transaction_order) {
+        Ok(stored_order_commitment) => {
+            if let (Some(stored_sign_order_commitment), Some(sign_order_commitment)) = (
+                stored_order_commitment.as_sign_order_commitment(),
+                order_commitment.as_sign_order_commitment(),
+            ) {
+                // Both commitments must be signed by a leader for the
+                // evidence to hold. A stored one that is not is replaced.
+                let is_stored_verified = verify_leader_sign_order_commitment(
+                    rollup,
+                    rollup_block_height,
+                    transaction_order,
+                    stored_sign_order_commitment,
+                )
+                .is_ok();
+
+                if is_stored_verified
+                    && is_conflicting(
+                        &stored_sign_order_commitment.data,
+                        &sign_order_commitment.data,
+                    )
+                {
+                    return EquivocationEvidence::conflicting_order_commitment(
+                        stored_sign_order_commitment.clone(),
+                        sign_order_commitment.clone(),
+                    )
+                    .record()
+                    .map_err(Error::Database);
+                }
+            }
+        }
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(Error::Database(error)),
+    }
+
+    order_commitment
+        .put(rollup_id, rollup_block_height, transaction_order)
+        .map_err(Error::Database)
+}
+
+fn is_conflicting(
+    order_commitment_data: &OrderCommitmentData,
+    other_order_commitment_data: &OrderCommitmentData,
+) -> bool {
+    order_commitment_data.transaction_hash != other_order_commitment_data.transaction_hash
+        || order_commitment_data.pre_merkle_path != other_order_commitment_data.pre_merkle_path
+}
+
+/// Checks every signed order commitment stored for the block against the
+/// built block and records the ones it breaks.
+pub async fn check_block_order_commitment(
+    rollup_id: &str,
+    rollup_block_height: u64,
+) -> Result<(), Error> {
+    let rollup = Rollup::get(rollup_id).map_err(Error::Database)?;
+    let block = Block::get(rollup_id, rollup_block_height).map_err(Error::Database)?;
+    let merkle_leaf_list = block.merkle_leaf_list()?;
+
+    // Order commitments past the end of the block stand for dropped
+    // transactions.
+    let transaction_count = TransactionOrderStatus::get_or(
+        rollup_id,
+        rollup_block_height,
+        TransactionOrderStatus::default,
+    )
+    .map_err(Error::Database)?
+    .transaction_count
+    .max(merkle_leaf_list.len() as u64);
+
+    for transaction_order in 0..transaction_count {
+        let order_commitment =
+            match OrderCommitment::get(rollup_id, rollup_block_height, transaction_order) {
+                Ok(order_commitment) => order_commitment,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(Error::Database(error)),
+            };
+        let Some(sign_order_commitment) = order_commitment.as_sign_order_commitment() else {
+            continue;
+        };
+        if verify_leader_sign_order_commitment(
+            &rollup,
+            rollup_block_height,
+            transaction_order,
+            sign_order_commitment,
+        )
+        .is_err()
+        {
+            continue;
+        }
+
+        if verify_block_inclusion(
+            &sign_order_commitment.data,
+            &merkle_leaf_list,
+            &block.block_commitment,
+            block.merkle_root_version,
+        )
+        .await
+        .is_err()
+        {
+            EquivocationEvidence::block_order_mismatch(
+                sign_order_commitment.clone(),
+                &block,
+                merkle_leaf_list.clone(),
+            )
+            .record()
+            .map_err(Error::Database)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Watcher mode: cross-checks the order commitments the other sequencers hold
+/// for every built block with the local ones and with the block itself.
+pub fn watch_order_commitment(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(WATCH_INTERVAL_SECS)).await;
+
+            let rollup_id_list = match RollupIdList::get_or(RollupIdList::default) {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(error) => {
+                    tracing::error!("Failed to get rollup id list: {:?}", error);
+                    continue;
+                }
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = watch_rollup_order_commitment(&context, rollup_id).await {
+                    tracing::warn!(
+                        "Failed to watch order commitments - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn watch_rollup_order_commitment(context: &AppState, rollup_id: &str) -> Result<(), Error> {
+    let rollup = Rollup::get(rollup_id).map_err(Error::Database)?;
+    let rollup_metadata = RollupMetadata::get(rollup_id).map_err(Error::Database)?;
+    let cluster = get_block_cluster(&rollup, rollup_metadata.platform_block_height)?;
+    let rpc_url_list = cluster.get_others_external_rpc_url_list();
+
+    let watch_height = EquivocationWatchHeight::get_or(rollup_id, EquivocationWatchHeight::default)
+        .map_err(Error::Database)?;
+
+    for rollup_block_height in watch_height.0..rollup_metadata.rollup_block_height {
+        let block = match Block::get(rollup_id, rollup_block_height) {
+            Ok(block) => block,
+            // The block is still being built.
+            Err(error) if error.is_none_type() => break,
+            Err(error) => return Err(Error::Database(error)),
+        };
+        let transaction_count = block.merkle_leaf_list()?.len() as u64;
+
+        for rpc_url in rpc_url_list.iter() {
+            for start_transaction_order in
+                (0..transaction_count).step_by(MAX_TRANSACTION_RANGE_LENGTH as usize)
+            {
+                let parameter = GetTransactionRange {
+                    rollup_id: rollup_id.to_owned(),
+                    rollup_block_height,
+                    start_transaction_order,
+                    end_transaction_order: (start_transaction_order + MAX_TRANSACTION_RANGE_LENGTH)
+                        .min(transaction_count),
+                };
+
+                let response: GetTransactionRangeResponse = match context
+                    .rpc_client()
+                    .request(rpc_url, GetTransactionRange::method(), &parameter, Id::Null)
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(error) => {
+                        tracing::warn!(
+                            "Failed to fetch order commitments - rpc_url: {:?} / error: {:?}",
+                            rpc_url,
+                            error
+                        );
+                        break;
+                    }
+                };
+
+                for transaction in response.transaction_list {
+                    if let Some(order_commitment) = transaction.order_commitment {
+                        match put_order_commitment(
+                            &rollup,
+                            rollup_block_height,
+                            transaction.transaction_order,
+                            order_commitment,
+                        ) {
+                            Ok(()) | Err(Error::InvalidOrderCommitment) => {}
+                            Err(error) => return Err(error),
+                        }
+                    }
+                }
+            }
+        }
+
+        check_block_order_commitment(rollup_id, rollup_block_height).await?;
+
+        EquivocationWatchHeight(rollup_block_height + 1)
+            .put(rollup_id)
+            .map_err(Error::Database)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_commitment_data() -> OrderCommitmentData {
+        OrderCommitmentData {
+            transaction_hash: RawTransactionHash::from([1u8; 32]).as_string(),
+            pre_merkle_path: vec![[2u8; 32]],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_transaction_and_path_is_not_conflicting() {
+        // The signature covers the order commitment data, so a different
+        // signature alone is not an equivocation.
+        assert!(!is_conflicting(
+            &order_commitment_data(),
+            &order_commitment_data()
+        ));
+    }
+
+    #[test]
+    fn other_transaction_or_path_is_conflicting() {
+        let other_transaction = OrderCommitmentData {
+            transaction_hash: RawTransactionHash::from([3u8; 32]).as_string(),
+            ..order_commitment_data()
+        };
+        let other_pre_merkle_path = OrderCommitmentData {
+            pre_merkle_path: vec![[4u8; 32]],
+            ..order_commitment_data()
+        };
+
+        assert!(is_conflicting(&order_commitment_data(), &other_transaction));
+        assert!(is_conflicting(
+            &order_commitment_data(),
+            &other_pre_merkle_path
+        ));
+    }
+}
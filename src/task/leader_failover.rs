@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use radius_sdk::{
+    json_rpc::{client::Id, server::RpcParameter},
+    signature::Address,
+};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    client::liveness::seeder::SequencerRpcInfo,
+    error::Error,
+    rpc::cluster::{ChangeLeader, ChangeLeaderMessage},
+    state::AppState,
+    task::get_block_cluster,
+    types::*,
+    util::health_check,
+};
+
+const HEALTH_CHECK_INTERVAL_SECS: u64 = 5;
+/// Consecutive failed health checks before the leader is considered down.
+const MAX_HEALTH_CHECK_FAILURE_COUNT: u32 = 3;
+
+/// Periodically checks the leader of every rollup this sequencer follows and
+/// takes over when this sequencer is the first live fallback leader.
+pub fn monitor_leader(context: AppState) {
+    tokio::spawn(async move {
+        let mut failure_count_map: HashMap<String, u32> = HashMap::new();
+
+        loop {
+            sleep(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS)).await;
+
+            let rollup_id_list = match RollupIdList::get_or(RollupIdList::default) {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(error) => {
+                    tracing::error!("Failed to get rollup id list: {:?}", error);
+                    continue;
+                }
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = check_leader(&context, rollup_id, &mut failure_count_map).await
+                {
+                    tracing::warn!(
+                        "Failed to check the leader - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn check_leader(
+    context: &AppState,
+    rollup_id: &str,
+    failure_count_map: &mut HashMap<String, u32>,
+) -> Result<(), Error> {
+    let rollup_metadata = RollupMetadata::get(rollup_id).map_err(Error::Database)?;
+    if rollup_metadata.is_leader {
+        failure_count_map.remove(rollup_id);
+        return Ok(());
+    }
+
+    // No block has been finalized yet, so there is no leader to check.
+    let leader_external_rpc_url = match &rollup_metadata.leader_sequencer_rpc_info.external_rpc_url
+    {
+        Some(leader_external_rpc_url) => leader_external_rpc_url,
+        None => return Ok(()),
+    };
+
+    if health_check(leader_external_rpc_url).await.is_ok() {
+        failure_count_map.remove(rollup_id);
+        return Ok(());
+    }
+
+    let failure_count = failure_count_map.entry(rollup_id.to_owned()).or_default();
+    *failure_count += 1;
+    tracing::warn!(
+        "Leader health check failed - rollup_id: {:?} / leader: {:?} / failure count: {:?}",
+        rollup_id,
+        rollup_metadata
+            .leader_sequencer_rpc_info
+            .address
+            .as_hex_string(),
+        failure_count
+    );
+    if *failure_count < MAX_HEALTH_CHECK_FAILURE_COUNT {
+        return Ok(());
+    }
+    failure_count_map.remove(rollup_id);
+
+    let rollup = Rollup::get(rollup_id).map_err(Error::Database)?;
+    let cluster = get_block_cluster(&rollup, rollup_metadata.platform_block_height)?;
+
+    let previous_leader_address = rollup_metadata.leader_sequencer_rpc_info.address.clone();
+    let new_leader_rpc_info = select_fallback_leader(&cluster, &previous_leader_address)
+        .await
+        .ok_or(Error::EmptyLeader)?;
+
+    // Every follower picks the same fallback leader, which announces itself.
+    if new_leader_rpc_info.address != cluster.sequencer_address {
+        return Ok(());
+    }
+
+    take_over_leader(
+        context,
+        &rollup,
+        &cluster,
+        rollup_metadata.rollup_block_height,
+        previous_leader_address,
+    )
+    .await
+}
+
+/// Returns the first fallback leader that is alive, counting this sequencer
+/// as alive.
+pub async fn select_fallback_leader(
+    cluster: &Cluster,
+    leader_address: &Address,
+) -> Option<SequencerRpcInfo> {
+    for sequencer_rpc_info in cluster.get_fallback_leader_list(leader_address) {
+        if sequencer_rpc_info.address == cluster.sequencer_address {
+            return Some(sequencer_rpc_info);
+        }
+
+        if let Some(external_rpc_url) = &sequencer_rpc_info.external_rpc_url {
+            if health_check(external_rpc_url).await.is_ok() {
+                return Some(sequencer_rpc_info);
+            }
+        }
+    }
+
+    None
+}
+
+async fn take_over_leader(
+    context: &AppState,
+    rollup: &Rollup,
+    cluster: &Cluster,
+    rollup_block_height: u64,
+    previous_leader_address: Address,
+) -> Result<(), Error> {
+    let leader_sequencer_rpc_info = cluster
+        .get_sequencer_rpc_info(&cluster.sequencer_address)
+        .ok_or(Error::SequencerInfoNotFound)?;
+
+    let merkle_hash_scheme = RollupMetadata::get(&rollup.rollup_id)
+        .map_err(Error::Database)?
+        .merkle_hash_scheme;
+    let transaction_order = rebuild_merkle_tree(
+        context,
+        &rollup.rollup_id,
+        rollup_block_height,
+        merkle_hash_scheme,
+    )
+    .await?;
+
+    let mut rollup_metadata =
+        RollupMetadata::get_mut(&rollup.rollup_id).map_err(Error::Database)?;
+    if rollup_metadata.rollup_block_height != rollup_block_height
+        || rollup_metadata.leader_sequencer_rpc_info.address != previous_leader_address
+    {
+        // A block was finalized or the leader changed in the meantime.
+        return Ok(());
+    }
+    rollup_metadata.is_leader = true;
+    rollup_metadata.leader_sequencer_rpc_info = leader_sequencer_rpc_info;
+    rollup_metadata.transaction_order = transaction_order;
+    rollup_metadata.update().map_err(Error::Database)?;
+    // Released before signing and multicasting, which would otherwise hold
+    // back every transaction sent to the rollup.
+    drop(rollup_metadata);
+
+    BlockLeader::record(
+        &rollup.rollup_id,
+        rollup_block_height,
+        &cluster.sequencer_address,
+    )
+    .map_err(Error::Database)?;
+
+    tracing::info!(
+        "Took over as the leader - rollup_id: {:?} / rollup_block_height: {:?} / previous leader: {:?} / transaction_order: {:?}",
+        rollup.rollup_id,
+        rollup_block_height,
+        previous_leader_address.as_hex_string(),
+        transaction_order
+    );
+
+    let message = ChangeLeaderMessage {
+        rollup_id: rollup.rollup_id.clone(),
+        rollup_block_height,
+        previous_leader_address,
+        new_leader_address: cluster.sequencer_address.clone(),
+    };
+    let signer = context
+        .get_signer(rollup.platform)
+        .await
+        .map_err(|_| Error::SignerNotFound)?;
+    let envelope = ClusterEnvelope::seal(&signer, ChangeLeader::method(), &message)?;
+
+    let parameter = ChangeLeader { message, envelope };
+    let others_cluster_rpc_url_list = cluster.get_others_cluster_rpc_url_list();
+    if others_cluster_rpc_url_list.is_empty() {
+        return Ok(());
+    }
+
+    context
+        .rpc_client()
+        .multicast(
+            others_cluster_rpc_url_list,
+            ChangeLeader::method(),
+            &parameter,
+            Id::Null,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Rebuilds the Merkle tree of the current block from the transactions
+/// synced from the previous leader and returns the next transaction order.
+async fn rebuild_merkle_tree(
+    context: &AppState,
+    rollup_id: &str,
+    rollup_block_height: u64,
+    merkle_hash_scheme: MerkleHashScheme,
+) -> Result<u64, Error> {
+    let merkle_tree =
+        MerkleTree::with_checkpoint(rollup_id, rollup_block_height, merkle_hash_scheme);
+
+    let mut transaction_order = 0;
+    loop {
+        let transaction_hash =
+            match EncryptedTransactionModel::get(rollup_id, rollup_block_height, transaction_order)
+            {
+                Ok(encrypted_transaction) => encrypted_transaction.raw_transaction_hash(),
+                Err(error) => {
+                    if !error.is_none_type() {
+                        return Err(Error::Database(error));
+                    }
+
+                    match RawTransactionModel::get(
+                        rollup_id,
+                        rollup_block_height,
+                        transaction_order,
+                    ) {
+                        Ok((raw_transaction, _is_direct_sent)) => {
+                            raw_transaction.raw_transaction_hash()?
+                        }
+                        Err(error) => {
+                            if error.is_none_type() {
+                                break;
+                            }
+                            return Err(Error::Database(error));
+                        }
+                    }
+                }
+            };
+
+        merkle_tree.add_data(transaction_hash.as_ref()).await;
+        transaction_order += 1;
+    }
+
+    context
+        .merkle_tree_manager()
+        .insert(rollup_id, merkle_tree)
+        .await;
+
+    Ok(transaction_order)
+}
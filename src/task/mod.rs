@@ -1,3 +1,11 @@
 mod block_builder;
+mod equivocation;
+mod leader_failover;
+mod skde_key_tracker;
+mod transaction_backfill;
 
 pub use block_builder::*;
+pub use equivocation::*;
+pub use leader_failover::*;
+pub use skde_key_tracker::*;
+pub use transaction_backfill::*;
@@ -0,0 +1,88 @@
+use tokio::time::{sleep, Duration};
+
+use crate::{error::Error, state::AppState, types::*};
+
+const KEY_TRACKING_INTERVAL_SECS: u64 = 1;
+
+/// Periodically records the latest key id of the distributed key generation
+/// and the key ids whose decryption key has been released, so that the
+/// leader rejects SKDE transactions that are not time-locked anymore.
+///
+/// The distributed key generation is only polled while a rollup of this
+/// sequencer uses SKDE.
+pub fn track_skde_key(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(KEY_TRACKING_INTERVAL_SECS)).await;
+
+            match has_skde_rollup() {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(error) => {
+                    tracing::error!("Failed to get the rollups using SKDE: {:?}", error);
+                    continue;
+                }
+            }
+
+            if let Err(error) = update_skde_key_status(&context).await {
+                tracing::warn!("Failed to update the SKDE key status: {:?}", error);
+            }
+        }
+    });
+}
+
+/// Checks that a transaction encrypted under `key_id` cannot be decrypted
+/// yet. A key id newer than the tracked ones is checked against the
+/// distributed key generation before it is rejected.
+pub async fn check_skde_key_id(context: &AppState, key_id: u64) -> Result<(), Error> {
+    match SkdeKeyStatus::check_key_id(key_id) {
+        Err(Error::UnknownKeyId) => {}
+        result => return result,
+    }
+
+    update_skde_key_status(context).await?;
+    SkdeKeyStatus::check_key_id(key_id)
+}
+
+fn has_skde_rollup() -> Result<bool, Error> {
+    let rollup_id_list = RollupIdList::get_or(RollupIdList::default).map_err(Error::Database)?;
+
+    for rollup_id in rollup_id_list.iter() {
+        let rollup = Rollup::get(rollup_id).map_err(Error::Database)?;
+        if matches!(
+            rollup.encrypted_transaction_type,
+            EncryptedTransactionType::Skde
+        ) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+async fn update_skde_key_status(context: &AppState) -> Result<(), Error> {
+    let distributed_key_generation_client = context.distributed_key_generation_client();
+
+    let mut skde_key_status =
+        SkdeKeyStatus::get_or(SkdeKeyStatus::default).map_err(Error::Database)?;
+    skde_key_status.latest_key_id = distributed_key_generation_client
+        .get_latest_encryption_key()
+        .await?
+        .key_id;
+
+    // Decryption keys are released in key id order, so the first one not
+    // released yet bounds the expired key ids.
+    while skde_key_status.first_unexpired_key_id <= skde_key_status.latest_key_id {
+        if distributed_key_generation_client
+            .get_decryption_key(skde_key_status.first_unexpired_key_id)
+            .await
+            .is_err()
+        {
+            break;
+        }
+
+        skde_key_status.first_unexpired_key_id += 1;
+    }
+
+    skde_key_status.put().map_err(Error::Database)
+}
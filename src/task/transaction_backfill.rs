@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+
+use radius_sdk::json_rpc::{client::Id, server::RpcParameter};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    error::Error,
+    rpc::external::{
+        GetRollupMetadata, GetRollupMetadataResponse, GetTransactionRange,
+        GetTransactionRangeResponse, TransactionRangeEntry, MAX_TRANSACTION_RANGE_LENGTH,
+    },
+    state::AppState,
+    task::{get_block_cluster, put_order_commitment},
+    types::*,
+    verifier::verify_leader_order_commitment,
+};
+
+const BACKFILL_INTERVAL_SECS: u64 = 3;
+
+/// Periodically fills the holes in the transactions a follower holds for the
+/// current block, so that it is ready to build the block at any time.
+pub fn backfill_transaction(context: AppState) {
+    tokio::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(BACKFILL_INTERVAL_SECS)).await;
+
+            let rollup_id_list = match RollupIdList::get_or(RollupIdList::default) {
+                Ok(rollup_id_list) => rollup_id_list,
+                Err(error) => {
+                    tracing::error!("Failed to get rollup id list: {:?}", error);
+                    continue;
+                }
+            };
+
+            for rollup_id in rollup_id_list.iter() {
+                if let Err(error) = backfill_rollup_transaction(&context, rollup_id).await {
+                    tracing::warn!(
+                        "Failed to backfill transactions - rollup_id: {:?} / error: {:?}",
+                        rollup_id,
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn backfill_rollup_transaction(context: &AppState, rollup_id: &str) -> Result<(), Error> {
+    let rollup_metadata = RollupMetadata::get(rollup_id).map_err(Error::Database)?;
+    if rollup_metadata.is_leader {
+        return Ok(());
+    }
+
+    let leader_external_rpc_url = match &rollup_metadata.leader_sequencer_rpc_info.external_rpc_url
+    {
+        Some(leader_external_rpc_url) => leader_external_rpc_url.clone(),
+        None => return Ok(()),
+    };
+    let rollup_block_height = rollup_metadata.rollup_block_height;
+
+    // The leader's transaction order is the number of transactions it has
+    // ordered in the current block, which reveals a missing tail.
+    let leader_rollup_metadata: GetRollupMetadataResponse = context
+        .rpc_client()
+        .request(
+            &leader_external_rpc_url,
+            GetRollupMetadata::method(),
+            &GetRollupMetadata {
+                rollup_id: rollup_id.to_owned(),
+            },
+            Id::Null,
+        )
+        .await?;
+    if leader_rollup_metadata.rollup_metadata.rollup_block_height != rollup_block_height {
+        return Ok(());
+    }
+
+    let mut transaction_order_status = TransactionOrderStatus::get_mut_or(
+        rollup_id,
+        rollup_block_height,
+        TransactionOrderStatus::default,
+    )
+    .map_err(Error::Database)?;
+    transaction_order_status
+        .update_transaction_count(leader_rollup_metadata.rollup_metadata.transaction_order);
+    let missing_transaction_count = transaction_order_status.missing_transaction_count();
+    let range_list =
+        transaction_order_status.missing_transaction_order_range_list(MAX_TRANSACTION_RANGE_LENGTH);
+    transaction_order_status.update().map_err(Error::Database)?;
+
+    if range_list.is_empty() {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Backfill transactions - rollup_id: {:?} / rollup_block_height: {:?} / missing transaction count: {:?}",
+        rollup_id,
+        rollup_block_height,
+        missing_transaction_count
+    );
+
+    // Ask the leader first and fall back to the other sequencers.
+    let rollup = Rollup::get(rollup_id).map_err(Error::Database)?;
+    let cluster = get_block_cluster(&rollup, rollup_metadata.platform_block_height)?;
+    let mut rpc_url_list = vec![leader_external_rpc_url.clone()];
+    rpc_url_list.extend(
+        cluster
+            .get_others_external_rpc_url_list()
+            .into_iter()
+            .filter(|rpc_url| *rpc_url != leader_external_rpc_url),
+    );
+
+    for (start_transaction_order, end_transaction_order) in range_list {
+        let parameter = GetTransactionRange {
+            rollup_id: rollup_id.to_owned(),
+            rollup_block_height,
+            start_transaction_order,
+            end_transaction_order,
+        };
+
+        let response: GetTransactionRangeResponse = context
+            .rpc_client()
+            .fetch(
+                rpc_url_list.clone(),
+                GetTransactionRange::method(),
+                &parameter,
+                Id::Null,
+            )
+            .await?;
+
+        store_transaction_range(&rollup, rollup_block_height, &parameter, response)?;
+    }
+
+    Ok(())
+}
+
+/// Stores a range of transactions fetched from another sequencer. The range
+/// is rejected as a whole unless each transaction comes with an order
+/// commitment of the block leader for its order and hash.
+fn store_transaction_range(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    parameter: &GetTransactionRange,
+    response: GetTransactionRangeResponse,
+) -> Result<(), Error> {
+    let rollup_id = rollup.rollup_id.as_str();
+
+    let mut transaction_hash_list = Vec::with_capacity(response.transaction_list.len());
+    for transaction in response.transaction_list.iter() {
+        let transaction_hash =
+            verify_transaction_range_entry(rollup, rollup_block_height, parameter, transaction)?;
+        transaction_hash_list.push(transaction_hash);
+    }
+
+    let mut transaction_order_set = HashSet::new();
+    let mut backfilled_gas: u64 = 0;
+
+    for (transaction, transaction_hash) in response
+        .transaction_list
+        .into_iter()
+        .zip(transaction_hash_list)
+    {
+        if !is_transaction_stored(
+            rollup_id,
+            rollup_block_height,
+            transaction.transaction_order,
+        )? {
+            let transaction_gas_limit = match &transaction.encrypted_transaction {
+                Some(encrypted_transaction) => encrypted_transaction.get_transaction_gas_limit(),
+                None => transaction
+                    .raw_transaction
+                    .as_ref()
+                    .ok_or(Error::TransactionNotFound)?
+                    .get_transaction_gas_limit(),
+            }?;
+            backfilled_gas = backfilled_gas.saturating_add(transaction_gas_limit);
+        }
+
+        let transaction_kind = match transaction.encrypted_transaction {
+            Some(_) => TransactionKind::Encrypted,
+            None => TransactionKind::Raw,
+        };
+
+        if let Some(encrypted_transaction) = &transaction.encrypted_transaction {
+            EncryptedTransactionModel::put_with_transaction_hash(
+                rollup_id,
+                &transaction_hash,
+                encrypted_transaction,
+            )
+            .map_err(Error::Database)?;
+
+            EncryptedTransactionModel::put(
+                rollup_id,
+                rollup_block_height,
+                transaction.transaction_order,
+                encrypted_transaction,
+            )
+            .map_err(Error::Database)?;
+        }
+
+        if let Some(raw_transaction) = transaction.raw_transaction {
+            RawTransactionModel::put_with_transaction_hash(
+                rollup_id,
+                &transaction_hash,
+                raw_transaction.clone(),
+                transaction.is_direct_sent,
+            )
+            .map_err(Error::Database)?;
+
+            RawTransactionModel::put(
+                rollup_id,
+                rollup_block_height,
+                transaction.transaction_order,
+                raw_transaction,
+                transaction.is_direct_sent,
+            )
+            .map_err(Error::Database)?;
+        }
+
+        if let Some(order_commitment) = transaction.order_commitment {
+            put_order_commitment(
+                rollup,
+                rollup_block_height,
+                transaction.transaction_order,
+                order_commitment,
+            )?;
+        }
+
+        TransactionOrderIndex::record(
+            rollup_id,
+            transaction_kind,
+            &transaction_hash,
+            rollup_block_height,
+            transaction.transaction_order,
+            true,
+        )
+        .map_err(Error::Database)?;
+
+        transaction_order_set.insert(transaction.transaction_order);
+    }
+
+    if backfilled_gas != 0 {
+        let mut rollup_metadata = RollupMetadata::get_mut(rollup_id).map_err(Error::Database)?;
+        if rollup_metadata.rollup_block_height == rollup_block_height {
+            rollup_metadata.current_gas =
+                rollup_metadata.current_gas.saturating_add(backfilled_gas);
+            rollup_metadata.update().map_err(Error::Database)?;
+        }
+    }
+
+    TransactionOrderStatus::record(rollup_id, rollup_block_height, transaction_order_set)
+        .map_err(Error::Database)
+}
+
+/// Returns the committed hash of a fetched transaction once its order
+/// commitment checks out.
+fn verify_transaction_range_entry(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    parameter: &GetTransactionRange,
+    transaction: &TransactionRangeEntry,
+) -> Result<RawTransactionHash, Error> {
+    if transaction.transaction_order < parameter.start_transaction_order
+        || transaction.transaction_order >= parameter.end_transaction_order
+    {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    // The raw transaction of an encrypted one is its decryption, which must
+    // hash to the committed hash as well.
+    let transaction_hash = match (
+        &transaction.encrypted_transaction,
+        &transaction.raw_transaction,
+    ) {
+        (Some(encrypted_transaction), raw_transaction) => {
+            let transaction_hash = encrypted_transaction.raw_transaction_hash();
+            if let Some(raw_transaction) = raw_transaction {
+                if raw_transaction.raw_transaction_hash()? != transaction_hash {
+                    return Err(Error::InvalidOrderCommitment);
+                }
+            }
+
+            transaction_hash
+        }
+        (None, Some(raw_transaction)) => raw_transaction.raw_transaction_hash()?,
+        (None, None) => return Err(Error::TransactionNotFound),
+    };
+
+    let order_commitment = transaction
+        .order_commitment
+        .as_ref()
+        .ok_or(Error::InvalidOrderCommitment)?;
+    verify_leader_order_commitment(
+        rollup,
+        rollup_block_height,
+        transaction.transaction_order,
+        &transaction_hash,
+        order_commitment,
+    )?;
+
+    Ok(transaction_hash)
+}
+
+fn is_transaction_stored(
+    rollup_id: &str,
+    rollup_block_height: u64,
+    transaction_order: u64,
+) -> Result<bool, Error> {
+    for is_stored in [
+        EncryptedTransactionModel::get(rollup_id, rollup_block_height, transaction_order)
+            .map(|_| ()),
+        RawTransactionModel::get(rollup_id, rollup_block_height, transaction_order).map(|_| ()),
+    ] {
+        match is_stored {
+            Ok(()) => return Ok(true),
+            Err(error) if error.is_none_type() => {}
+            Err(error) => return Err(Error::Database(error)),
+        }
+    }
+
+    Ok(false)
+}
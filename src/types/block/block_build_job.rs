@@ -0,0 +1,150 @@
+use std::collections::btree_set::{self, BTreeSet};
+
+use crate::{rpc::cluster::FinalizeBlockMessage, types::prelude::*};
+
+/// Progress of a block build. Followers finish at `Committed`, while the
+/// leader goes on to sync the block and submit its commitment.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockBuildJobState {
+    Pending,
+    Fetching,
+    Decrypting,
+    Committed,
+    Synced,
+    Submitted,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64))]
+pub struct BlockBuildJob {
+    pub finalize_block_message: FinalizeBlockMessage,
+    pub rollup_signature: Signature,
+    pub leader_sequencer_signature: Option<Signature>,
+
+    pub encrypted_transaction_type: EncryptedTransactionType,
+    pub transaction_count: u64,
+    #[serde(default)]
+    pub merkle_hash_scheme: MerkleHashScheme,
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
+    #[serde(default = "MerkleRootVersion::unversioned")]
+    pub merkle_root_version: MerkleRootVersion,
+
+    pub state: BlockBuildJobState,
+    pub retry_count: u32,
+    pub last_error: Option<String>,
+
+    /// Transaction that registered the block commitment on the validation
+    /// platform.
+    #[serde(default)]
+    pub commitment_transaction_hash: Option<String>,
+}
+
+impl BlockBuildJob {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        finalize_block_message: FinalizeBlockMessage,
+        rollup_signature: Signature,
+        leader_sequencer_signature: Option<Signature>,
+        encrypted_transaction_type: EncryptedTransactionType,
+        transaction_count: u64,
+        merkle_hash_scheme: MerkleHashScheme,
+        signature_scheme: SignatureScheme,
+        merkle_root_version: MerkleRootVersion,
+    ) -> Self {
+        Self {
+            finalize_block_message,
+            rollup_signature,
+            leader_sequencer_signature,
+            encrypted_transaction_type,
+            transaction_count,
+            merkle_hash_scheme,
+            signature_scheme,
+            merkle_root_version,
+            state: BlockBuildJobState::Pending,
+            retry_count: 0,
+            last_error: None,
+            commitment_transaction_hash: None,
+        }
+    }
+
+    pub fn rollup_id(&self) -> &str {
+        &self.finalize_block_message.rollup_id
+    }
+
+    pub fn rollup_block_height(&self) -> u64 {
+        self.finalize_block_message.rollup_block_height
+    }
+
+    /// The leader builds and signs the block itself, whereas a follower
+    /// builds it with the signature it received from the leader.
+    pub fn is_leader(&self) -> bool {
+        self.leader_sequencer_signature.is_none()
+    }
+
+    pub fn final_state(&self) -> BlockBuildJobState {
+        if self.is_leader() {
+            BlockBuildJobState::Submitted
+        } else {
+            BlockBuildJobState::Committed
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state >= self.final_state()
+    }
+}
+
+/// Unfinished block build jobs, resumed on startup.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key())]
+pub struct BlockBuildJobList(BTreeSet<(String, u64)>);
+
+impl BlockBuildJobList {
+    pub fn insert(&mut self, rollup_id: impl AsRef<str>, rollup_block_height: u64) {
+        self.0
+            .insert((rollup_id.as_ref().to_owned(), rollup_block_height));
+    }
+
+    pub fn remove(&mut self, rollup_id: impl AsRef<str>, rollup_block_height: u64) {
+        self.0
+            .remove(&(rollup_id.as_ref().to_owned(), rollup_block_height));
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, (String, u64)> {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn states_are_ordered_by_progress() {
+        assert!(BlockBuildJobState::Pending < BlockBuildJobState::Fetching);
+        assert!(BlockBuildJobState::Fetching < BlockBuildJobState::Decrypting);
+        assert!(BlockBuildJobState::Decrypting < BlockBuildJobState::Committed);
+        assert!(BlockBuildJobState::Committed < BlockBuildJobState::Synced);
+        assert!(BlockBuildJobState::Synced < BlockBuildJobState::Submitted);
+    }
+
+    #[test]
+    fn job_list_is_ordered_and_deduplicated() {
+        let mut block_build_job_list = BlockBuildJobList::default();
+        block_build_job_list.insert("rollup_id", 2);
+        block_build_job_list.insert("rollup_id", 1);
+        block_build_job_list.insert("rollup_id", 2);
+        block_build_job_list.insert("other_rollup_id", 3);
+        block_build_job_list.remove("rollup_id", 1);
+
+        assert_eq!(
+            block_build_job_list.iter().cloned().collect::<Vec<_>>(),
+            [
+                ("other_rollup_id".to_owned(), 3),
+                ("rollup_id".to_owned(), 2)
+            ]
+        );
+    }
+}
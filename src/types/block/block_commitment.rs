@@ -33,8 +33,37 @@ impl BlockCommitment {
         const_hex::decode(&self.0)
     }
 
+    /// Whether the block commitment is the given 32 bytes, e.g. of a
+    /// validation task. A malformed block commitment matches nothing.
+    pub fn matches(&self, block_commitment: &[u8]) -> bool {
+        self.as_bytes()
+            .is_ok_and(|local_block_commitment| local_block_commitment == block_commitment)
+    }
+
     /// Returns the inner hex string representation.
     pub fn as_hex_string(&self) -> &str {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_commitment_matches_its_bytes() {
+        let block_commitment = BlockCommitment::from([1u8; 32]);
+
+        assert!(block_commitment.matches(&[1u8; 32]));
+        assert!(!block_commitment.matches(&[2u8; 32]));
+        assert!(!block_commitment.matches(&[1u8; 31]));
+    }
+
+    #[test]
+    fn malformed_block_commitment_matches_nothing() {
+        let block_commitment = BlockCommitment::from("not hex");
+
+        assert!(!block_commitment.matches(b"not hex"));
+        assert!(!block_commitment.matches(&[]));
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::btree_set::{self, BTreeSet};
+
+use crate::types::prelude::*;
+
+/// Leaf of a block that differs between the follower and the leader. A side
+/// is `None` when its block has no transaction at that order.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TransactionHashDiff {
+    pub transaction_order: u64,
+    pub local_transaction_hash: Option<RawTransactionHash>,
+    pub leader_transaction_hash: Option<RawTransactionHash>,
+}
+
+/// Evidence that the block commitment signed by the leader does not match
+/// the one rebuilt by this sequencer.
+///
+/// The leader's side is only known when its block could be fetched,
+/// otherwise the signature is the sole evidence.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64))]
+pub struct BlockMismatch {
+    pub rollup_block_height: u64,
+
+    pub leader_sequencer_address: Address,
+    pub leader_sequencer_signature: Signature,
+    pub leader_block_commitment: Option<BlockCommitment>,
+
+    pub local_sequencer_address: Address,
+    pub local_signature: Signature,
+    pub local_block_commitment: BlockCommitment,
+
+    pub transaction_hash_diff_list: Vec<TransactionHashDiff>,
+}
+
+impl BlockMismatch {
+    /// Compares the leaves of both blocks order by order.
+    pub fn diff_transaction_hash_list(
+        local_transaction_hash_list: &[RawTransactionHash],
+        leader_transaction_hash_list: &[RawTransactionHash],
+    ) -> Vec<TransactionHashDiff> {
+        let length = local_transaction_hash_list
+            .len()
+            .max(leader_transaction_hash_list.len());
+
+        (0..length)
+            .filter_map(|index| {
+                let local_transaction_hash = local_transaction_hash_list.get(index);
+                let leader_transaction_hash = leader_transaction_hash_list.get(index);
+
+                (local_transaction_hash != leader_transaction_hash).then(|| TransactionHashDiff {
+                    transaction_order: index as u64,
+                    local_transaction_hash: local_transaction_hash.cloned(),
+                    leader_transaction_hash: leader_transaction_hash.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str))]
+pub struct BlockMismatchList(BTreeSet<u64>);
+
+impl BlockMismatchList {
+    pub fn insert(&mut self, rollup_block_height: u64) {
+        self.0.insert(rollup_block_height);
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, u64> {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_hash(byte: u8) -> RawTransactionHash {
+        RawTransactionHash::from([byte; 32])
+    }
+
+    #[test]
+    fn equal_blocks_have_no_diff() {
+        let transaction_hash_list = [transaction_hash(1), transaction_hash(2)];
+
+        assert!(BlockMismatch::diff_transaction_hash_list(
+            &transaction_hash_list,
+            &transaction_hash_list
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn differing_leaves_are_listed_by_order() {
+        let local_transaction_hash_list = [transaction_hash(1), transaction_hash(2)];
+        let leader_transaction_hash_list = [
+            transaction_hash(1),
+            transaction_hash(3),
+            transaction_hash(4),
+        ];
+
+        let transaction_hash_diff_list = BlockMismatch::diff_transaction_hash_list(
+            &local_transaction_hash_list,
+            &leader_transaction_hash_list,
+        );
+
+        assert_eq!(
+            transaction_hash_diff_list,
+            [
+                TransactionHashDiff {
+                    transaction_order: 1,
+                    local_transaction_hash: Some(transaction_hash(2)),
+                    leader_transaction_hash: Some(transaction_hash(3)),
+                },
+                TransactionHashDiff {
+                    transaction_order: 2,
+                    local_transaction_hash: None,
+                    leader_transaction_hash: Some(transaction_hash(4)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn mismatch_list_is_ordered_and_deduplicated() {
+        let mut block_mismatch_list = BlockMismatchList::default();
+        block_mismatch_list.insert(7);
+        block_mismatch_list.insert(3);
+        block_mismatch_list.insert(7);
+
+        assert_eq!(
+            block_mismatch_list.iter().copied().collect::<Vec<_>>(),
+            [3, 7]
+        );
+    }
+}
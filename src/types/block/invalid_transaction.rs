@@ -0,0 +1,29 @@
+use crate::types::prelude::*;
+
+/// Transaction of a block left out of its `raw_transaction_list`. The order
+/// keeps the Merkle leaf it was committed with, so that the order commitments
+/// still verify against the block commitment.
+///
+/// Transactions that fail to decrypt or deserialize are recorded while the
+/// block is being built, so that a retried build job leaves them out again.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64, transaction_order: u64))]
+pub struct InvalidTransaction {
+    pub transaction_order: u64,
+    pub reason: InvalidTransactionReason,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InvalidTransactionReason {
+    /// The decrypted transaction does not hash to the `raw_tx_hash` of the
+    /// open data it was ordered with.
+    TransactionHashMismatch {
+        decrypted_transaction_hash: RawTransactionHash,
+    },
+    /// The encrypted data could not be decrypted with the key of the
+    /// transaction.
+    DecryptionFailure,
+    /// The decrypted data is not a valid plain data of the transaction.
+    DeserializationFailure,
+}
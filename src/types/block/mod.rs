@@ -1,7 +1,14 @@
+mod block_build_job;
 mod block_commitment;
+mod block_mismatch;
+mod invalid_transaction;
+
+pub use block_build_job::*;
 pub use block_commitment::*;
+pub use block_mismatch::*;
+pub use invalid_transaction::*;
 
-use crate::types::prelude::*;
+use crate::{error::Error, types::prelude::*};
 
 #[derive(Clone, Debug, Deserialize, Serialize, Model)]
 #[kvstore(key(rollup_id: &str, block_height: u64))]
@@ -11,28 +18,72 @@ pub struct Block {
     pub encrypted_transaction_list: Vec<Option<EncryptedTransaction>>,
     pub raw_transaction_list: Vec<RawTransaction>,
 
+    /// Merkle leaves of the block, one per transaction order. Bundles are
+    /// expanded in `raw_transaction_list` but keep a single leaf here.
+    #[serde(default)]
+    pub transaction_hash_list: Vec<RawTransactionHash>,
+
+    /// Transactions left out of `raw_transaction_list`, in order.
+    #[serde(default)]
+    pub invalid_transaction_list: Vec<InvalidTransaction>,
+
     pub signature: Signature,
 
     pub block_commitment: BlockCommitment,
     pub block_creator_address: Address,
+
+    #[serde(default)]
+    pub merkle_hash_scheme: MerkleHashScheme,
+
+    /// Signature scheme `signature` was made with.
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
+
+    /// How `block_commitment` was completed from the leaves.
+    #[serde(default = "MerkleRootVersion::unversioned")]
+    pub merkle_root_version: MerkleRootVersion,
 }
 
 impl Block {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         block_height: u64,
         encrypted_transaction_list: Vec<Option<EncryptedTransaction>>,
         raw_transaction_list: Vec<RawTransaction>,
+        transaction_hash_list: Vec<RawTransactionHash>,
+        invalid_transaction_list: Vec<InvalidTransaction>,
         signature: Signature,
         block_commitment: BlockCommitment,
         block_creator_address: Address,
+        merkle_hash_scheme: MerkleHashScheme,
+        signature_scheme: SignatureScheme,
+        merkle_root_version: MerkleRootVersion,
     ) -> Self {
         Self {
             block_height,
             encrypted_transaction_list,
             raw_transaction_list,
+            transaction_hash_list,
+            invalid_transaction_list,
             signature,
             block_commitment,
             block_creator_address,
+            merkle_hash_scheme,
+            signature_scheme,
+            merkle_root_version,
         }
     }
+
+    /// Returns the Merkle leaves of the block. Blocks stored before the leaves
+    /// were recorded fall back to the hashes of their raw transactions.
+    pub fn merkle_leaf_list(&self) -> Result<Vec<RawTransactionHash>, Error> {
+        if !self.transaction_hash_list.is_empty() {
+            return Ok(self.transaction_hash_list.clone());
+        }
+
+        self.raw_transaction_list
+            .iter()
+            .map(|raw_transaction| raw_transaction.raw_transaction_hash())
+            .collect()
+    }
 }
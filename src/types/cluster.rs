@@ -116,6 +116,31 @@ impl Cluster {
             .map(|(_index, sequencer_rpc_info)| sequencer_rpc_info.clone())
     }
 
+    /// Returns the sequencers that take over from the given leader, in order
+    /// of priority: the ones registered after it, wrapping around to the
+    /// start of the set.
+    pub fn get_fallback_leader_list(&self, leader_address: &Address) -> Vec<SequencerRpcInfo> {
+        let leader_index = self
+            .sequencer_rpc_infos
+            .iter()
+            .find(|(_index, sequencer_rpc_info)| sequencer_rpc_info.address == leader_address)
+            .map(|(index, _sequencer_rpc_info)| *index);
+
+        let (after_leader, before_leader): (Vec<_>, Vec<_>) = self
+            .sequencer_rpc_infos
+            .iter()
+            .filter(|(_index, sequencer_rpc_info)| sequencer_rpc_info.address != leader_address)
+            .partition(|(index, _sequencer_rpc_info)| {
+                leader_index.is_some_and(|leader_index| **index > leader_index)
+            });
+
+        after_leader
+            .into_iter()
+            .chain(before_leader)
+            .map(|(_index, sequencer_rpc_info)| sequencer_rpc_info.clone())
+            .collect()
+    }
+
     pub fn register_sequencer(&mut self, index: usize, sequencer_rpc_info: SequencerRpcInfo) {
         self.sequencer_rpc_infos.insert(index, sequencer_rpc_info);
     }
@@ -232,3 +257,64 @@ pub enum LivenessEventType {
     DeregisteredSequencer(String),
     AddedRollup(String, String),
 }
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::ChainType;
+
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::from_slice(ChainType::Ethereum, &[byte; 20]).unwrap()
+    }
+
+    fn cluster(byte_list: &[u8]) -> Cluster {
+        let sequencer_rpc_infos = byte_list
+            .iter()
+            .map(|byte| SequencerRpcInfo {
+                address: address(*byte),
+                ..Default::default()
+            })
+            .enumerate()
+            .collect();
+
+        Cluster::new(sequencer_rpc_infos, BTreeSet::new(), address(1), 0)
+    }
+
+    fn fallback_leader_list(cluster: &Cluster, leader_byte: u8) -> Vec<Address> {
+        cluster
+            .get_fallback_leader_list(&address(leader_byte))
+            .into_iter()
+            .map(|sequencer_rpc_info| sequencer_rpc_info.address)
+            .collect()
+    }
+
+    #[test]
+    fn fallback_leaders_follow_the_leader_and_wrap_around() {
+        let cluster = cluster(&[1, 2, 3, 4]);
+
+        assert_eq!(
+            fallback_leader_list(&cluster, 2),
+            [address(3), address(4), address(1)]
+        );
+        assert_eq!(
+            fallback_leader_list(&cluster, 4),
+            [address(1), address(2), address(3)]
+        );
+    }
+
+    #[test]
+    fn every_sequencer_is_a_fallback_for_an_unknown_leader() {
+        let cluster = cluster(&[1, 2, 3]);
+
+        assert_eq!(
+            fallback_leader_list(&cluster, 9),
+            [address(1), address(2), address(3)]
+        );
+    }
+
+    #[test]
+    fn single_sequencer_has_no_fallback() {
+        assert!(fallback_leader_list(&cluster(&[1]), 1).is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use radius_sdk::signature::{ChainType, PrivateKeySigner};
+
+use super::prelude::*;
+use crate::error::Error;
+
+/// Seconds a cluster message stays valid after it has been sealed.
+pub const CLUSTER_MESSAGE_LIFETIME: u64 = 30;
+
+static LAST_CLUSTER_MESSAGE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Authenticates a message sent between the sequencers of a cluster.
+///
+/// The signature covers the RPC method, the message, the signer, the sequence
+/// and the expiry, so an envelope can neither be attached to another method
+/// nor be accepted twice.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClusterEnvelope {
+    #[serde(serialize_with = "serialize_address")]
+    pub signer: Address,
+    /// Strictly increasing per sender. Derived from the wall clock in
+    /// microseconds so that it keeps increasing across restarts.
+    pub sequence: u64,
+    /// Unix timestamp in seconds after which the message is rejected.
+    pub expiry: u64,
+    pub signature: Signature,
+}
+
+#[derive(Serialize)]
+struct ClusterEnvelopeSignMessage<'a, T> {
+    method: &'a str,
+    message: &'a T,
+    signer: String,
+    sequence: u64,
+    expiry: u64,
+}
+
+impl ClusterEnvelope {
+    pub fn seal<T: Serialize>(
+        signer: &PrivateKeySigner,
+        method: &str,
+        message: &T,
+    ) -> Result<Self, Error> {
+        let signer_address = signer.address().clone();
+        let sequence = next_sequence();
+        let expiry = unix_timestamp() + CLUSTER_MESSAGE_LIFETIME;
+
+        let signature = signer
+            .sign_message(&ClusterEnvelopeSignMessage {
+                method,
+                message,
+                signer: signer_address.as_hex_string(),
+                sequence,
+                expiry,
+            })
+            .map_err(Error::Signature)?;
+
+        Ok(Self {
+            signer: signer_address,
+            sequence,
+            expiry,
+            signature,
+        })
+    }
+
+    /// Verifies the expiry and the signature, then records the sequence so
+    /// that the same envelope is accepted only once. Whether the signer may
+    /// send the message is left to the caller.
+    pub fn open<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        method: &str,
+        message: &T,
+    ) -> Result<(), Error> {
+        let now = unix_timestamp();
+        if self.expiry < now || self.expiry > now + CLUSTER_MESSAGE_LIFETIME * 2 {
+            tracing::warn!(
+                "Expired cluster message - method: {:?} / signer: {:?} / expiry: {:?}",
+                method,
+                self.signer.as_hex_string(),
+                self.expiry
+            );
+            return Err(Error::ExpiredClusterMessage);
+        }
+
+        self.signature
+            .verify_message(
+                chain_type,
+                &ClusterEnvelopeSignMessage {
+                    method,
+                    message,
+                    signer: self.signer.as_hex_string(),
+                    sequence: self.sequence,
+                    expiry: self.expiry,
+                },
+                &self.signer,
+            )
+            .map_err(|error| {
+                tracing::error!(
+                    "Failed to verify the cluster message - method: {:?} / signer: {:?} / error: {:?}",
+                    method,
+                    self.signer.as_hex_string(),
+                    error
+                );
+                Error::InvalidSignature
+            })?;
+
+        let mut cluster_message_window =
+            ClusterMessageWindow::get_mut_or(&self.signer.as_hex_string(), Default::default)
+                .map_err(Error::Database)?;
+        cluster_message_window.accept(self.sequence, self.expiry, now)?;
+        cluster_message_window.update().map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    /// Fails unless the envelope was sealed by `address`.
+    pub fn ensure_signer(&self, address: &Address) -> Result<(), Error> {
+        if self.signer != *address {
+            tracing::warn!(
+                "Unauthorized cluster message signer: {:?}",
+                self.signer.as_hex_string()
+            );
+            return Err(Error::UnauthorizedClusterMessage);
+        }
+
+        Ok(())
+    }
+}
+
+/// Sequences accepted from a single sender that have not expired yet.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(signer: &str))]
+pub struct ClusterMessageWindow {
+    /// Accepted sequences mapped to the expiry of their message.
+    sequence_list: BTreeMap<u64, u64>,
+    /// Highest sequence dropped from the window after its message expired.
+    expired_sequence: u64,
+}
+
+impl ClusterMessageWindow {
+    fn accept(&mut self, sequence: u64, expiry: u64, now: u64) -> Result<(), Error> {
+        let expired_sequence_list: Vec<u64> = self
+            .sequence_list
+            .iter()
+            .filter(|(_, expiry)| **expiry < now)
+            .map(|(sequence, _)| *sequence)
+            .collect();
+        for expired_sequence in expired_sequence_list {
+            self.sequence_list.remove(&expired_sequence);
+            self.expired_sequence = self.expired_sequence.max(expired_sequence);
+        }
+
+        if sequence <= self.expired_sequence || self.sequence_list.contains_key(&sequence) {
+            tracing::warn!("Replayed cluster message - sequence: {:?}", sequence);
+            return Err(Error::ReplayedClusterMessage);
+        }
+        self.sequence_list.insert(sequence, expiry);
+
+        Ok(())
+    }
+}
+
+fn next_sequence() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64;
+
+    let previous = LAST_CLUSTER_MESSAGE_SEQUENCE
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or_default();
+
+    now.max(previous + 1)
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    // Well-known development keys.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const OTHER_SIGNING_KEY: &str =
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn signer(signing_key: &str) -> PrivateKeySigner {
+        PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap()
+    }
+
+    #[test]
+    fn window_rejects_a_replayed_sequence() {
+        let mut cluster_message_window = ClusterMessageWindow::default();
+
+        cluster_message_window.accept(5, 130, 100).unwrap();
+        cluster_message_window.accept(3, 130, 100).unwrap();
+        let replayed_sequence = cluster_message_window.accept(5, 130, 100);
+
+        assert!(matches!(
+            replayed_sequence,
+            Err(Error::ReplayedClusterMessage)
+        ));
+    }
+
+    #[test]
+    fn window_rejects_sequences_up_to_the_expired_ones() {
+        let mut cluster_message_window = ClusterMessageWindow::default();
+        cluster_message_window.accept(5, 130, 100).unwrap();
+        cluster_message_window.accept(8, 160, 100).unwrap();
+
+        // Sequence 5 expired, so it is dropped from the window.
+        cluster_message_window.accept(9, 170, 140).unwrap();
+
+        assert_eq!(
+            cluster_message_window
+                .sequence_list
+                .keys()
+                .collect::<Vec<_>>(),
+            [&8, &9]
+        );
+        assert!(matches!(
+            cluster_message_window.accept(4, 170, 140),
+            Err(Error::ReplayedClusterMessage)
+        ));
+        assert!(matches!(
+            cluster_message_window.accept(5, 170, 140),
+            Err(Error::ReplayedClusterMessage)
+        ));
+        assert!(cluster_message_window.accept(6, 170, 140).is_ok());
+    }
+
+    #[test]
+    fn sequence_is_strictly_increasing() {
+        let sequence_list: Vec<u64> = (0..100).map(|_| next_sequence()).collect();
+
+        assert!(sequence_list.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn envelope_is_opened_once() {
+        init_test_database();
+        let signer = signer(SIGNING_KEY);
+        let cluster_envelope = ClusterEnvelope::seal(&signer, "sync_block", &"message").unwrap();
+
+        cluster_envelope
+            .open(ChainType::Ethereum, "sync_block", &"message")
+            .unwrap();
+        let replayed_envelope =
+            cluster_envelope.open(ChainType::Ethereum, "sync_block", &"message");
+
+        assert!(matches!(
+            replayed_envelope,
+            Err(Error::ReplayedClusterMessage)
+        ));
+        assert!(cluster_envelope.ensure_signer(signer.address()).is_ok());
+    }
+
+    #[test]
+    fn envelope_is_bound_to_the_method_and_the_message() {
+        init_test_database();
+        let cluster_envelope =
+            ClusterEnvelope::seal(&signer(SIGNING_KEY), "sync_block", &"message").unwrap();
+
+        let other_method = cluster_envelope.open(ChainType::Ethereum, "finalize_block", &"message");
+        let other_message =
+            cluster_envelope.open(ChainType::Ethereum, "sync_block", &"other_message");
+
+        assert!(matches!(other_method, Err(Error::InvalidSignature)));
+        assert!(matches!(other_message, Err(Error::InvalidSignature)));
+    }
+
+    #[test]
+    fn expired_envelope_is_rejected() {
+        init_test_database();
+        let mut cluster_envelope =
+            ClusterEnvelope::seal(&signer(SIGNING_KEY), "sync_block", &"message").unwrap();
+        cluster_envelope.expiry = unix_timestamp() - 1;
+
+        let expired_envelope = cluster_envelope.open(ChainType::Ethereum, "sync_block", &"message");
+
+        assert!(matches!(
+            expired_envelope,
+            Err(Error::ExpiredClusterMessage)
+        ));
+    }
+
+    #[test]
+    fn other_signer_is_unauthorized() {
+        let cluster_envelope =
+            ClusterEnvelope::seal(&signer(SIGNING_KEY), "sync_block", &"message").unwrap();
+
+        let unauthorized_signer =
+            cluster_envelope.ensure_signer(signer(OTHER_SIGNING_KEY).address());
+
+        assert!(matches!(
+            unauthorized_signer,
+            Err(Error::UnauthorizedClusterMessage)
+        ));
+    }
+}
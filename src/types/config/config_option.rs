@@ -8,8 +8,10 @@ use super::ConfigPath;
 const DEFAULT_EXTERNAL_RPC_URL: &str = "http://127.0.0.1:3000";
 const DEFAULT_INTERNAL_RPC_URL: &str = "http://127.0.0.1:4000";
 const DEFAULT_CLUSTER_RPC_URL: &str = "http://127.0.0.1:5000";
+pub const DEFAULT_SUBSCRIPTION_RPC_URL: &str = "ws://127.0.0.1:3100";
 const DEFAULT_SEEDER_RPC_URL: &str = "http://127.0.0.1:6000";
 const DEFAULT_DISTRIBUTED_KEY_GENERATION_RPC_URL: &str = "http://127.0.0.1:7100";
+pub const DEFAULT_DUPLICATE_TRANSACTION_RETENTION: u64 = 1024;
 
 #[derive(Debug, Deserialize, Parser, Serialize)]
 pub struct ConfigOption {
@@ -29,6 +31,22 @@ pub struct ConfigOption {
     #[clap(long = "cluster-rpc-url")]
     pub cluster_rpc_url: Option<String>,
 
+    #[doc = "Set the subscription (WebSocket) rpc url"]
+    #[clap(long = "subscription-rpc-url")]
+    pub subscription_rpc_url: Option<String>,
+
+    #[doc = "Set the Ethereum JSON-RPC compatibility rpc url (disabled if unset)"]
+    #[clap(long = "ethereum-rpc-url")]
+    pub ethereum_rpc_url: Option<String>,
+
+    #[doc = "Set the rollup served by the Ethereum JSON-RPC compatibility server"]
+    #[clap(long = "ethereum-rollup-id")]
+    pub ethereum_rollup_id: Option<String>,
+
+    #[doc = "Set the execution rpc url of the rollup to proxy Ethereum read methods to"]
+    #[clap(long = "ethereum-execution-rpc-url")]
+    pub ethereum_execution_rpc_url: Option<String>,
+
     #[doc = "Set the seeder rpc url"]
     #[clap(long = "seeder-rpc-url")]
     pub seeder_rpc_url: Option<String>,
@@ -40,6 +58,14 @@ pub struct ConfigOption {
     #[doc = "Set using zkp"]
     #[clap(long = "is-using-zkp")]
     pub is_using_zkp: Option<bool>,
+
+    #[doc = "Set watching order commitments for equivocation"]
+    #[clap(long = "is-watcher")]
+    pub is_watcher: Option<bool>,
+
+    #[doc = "Set the number of rollup blocks to deduplicate transactions over"]
+    #[clap(long = "duplicate-transaction-retention")]
+    pub duplicate_transaction_retention: Option<u64>,
 }
 
 impl Default for ConfigOption {
@@ -50,6 +76,11 @@ impl Default for ConfigOption {
             external_rpc_url: Some(DEFAULT_EXTERNAL_RPC_URL.into()),
             internal_rpc_url: Some(DEFAULT_INTERNAL_RPC_URL.into()),
             cluster_rpc_url: Some(DEFAULT_CLUSTER_RPC_URL.into()),
+            subscription_rpc_url: Some(DEFAULT_SUBSCRIPTION_RPC_URL.into()),
+
+            ethereum_rpc_url: None,
+            ethereum_rollup_id: None,
+            ethereum_execution_rpc_url: None,
 
             seeder_rpc_url: Some(DEFAULT_SEEDER_RPC_URL.into()),
             distributed_key_generation_rpc_url: Some(
@@ -57,6 +88,8 @@ impl Default for ConfigOption {
             ),
 
             is_using_zkp: Some(false),
+            is_watcher: Some(false),
+            duplicate_transaction_retention: Some(DEFAULT_DUPLICATE_TRANSACTION_RETENTION),
         }
     }
 }
@@ -74,6 +107,39 @@ impl ConfigOption {
         set_toml_comment(&mut toml_string, "Set cluster rpc url");
         set_toml_name_value(&mut toml_string, "cluster_rpc_url", &self.cluster_rpc_url);
 
+        set_toml_comment(&mut toml_string, "Set subscription (WebSocket) rpc url");
+        set_toml_name_value(
+            &mut toml_string,
+            "subscription_rpc_url",
+            &self.subscription_rpc_url,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set Ethereum JSON-RPC compatibility rpc url (disabled if unset)",
+        );
+        set_toml_name_value(&mut toml_string, "ethereum_rpc_url", &self.ethereum_rpc_url);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set rollup served by the Ethereum JSON-RPC compatibility server",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "ethereum_rollup_id",
+            &self.ethereum_rollup_id,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set execution rpc url to proxy Ethereum read methods to",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "ethereum_execution_rpc_url",
+            &self.ethereum_execution_rpc_url,
+        );
+
         set_toml_comment(&mut toml_string, "Set seeder rpc url");
         set_toml_name_value(&mut toml_string, "seeder_rpc_url", &self.seeder_rpc_url);
 
@@ -87,6 +153,19 @@ impl ConfigOption {
         set_toml_comment(&mut toml_string, "Set using zkp");
         set_toml_name_value(&mut toml_string, "is_using_zkp", &self.is_using_zkp);
 
+        set_toml_comment(&mut toml_string, "Set watching order commitments");
+        set_toml_name_value(&mut toml_string, "is_watcher", &self.is_watcher);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set rollup blocks to remember ordered transactions for (0 to disable)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "duplicate_transaction_retention",
+            &self.duplicate_transaction_retention,
+        );
+
         toml_string
     }
 
@@ -107,6 +186,25 @@ impl ConfigOption {
             self.cluster_rpc_url.clone_from(&other.cluster_rpc_url);
         }
 
+        if other.subscription_rpc_url.is_some() {
+            self.subscription_rpc_url
+                .clone_from(&other.subscription_rpc_url);
+        }
+
+        if other.ethereum_rpc_url.is_some() {
+            self.ethereum_rpc_url.clone_from(&other.ethereum_rpc_url);
+        }
+
+        if other.ethereum_rollup_id.is_some() {
+            self.ethereum_rollup_id
+                .clone_from(&other.ethereum_rollup_id);
+        }
+
+        if other.ethereum_execution_rpc_url.is_some() {
+            self.ethereum_execution_rpc_url
+                .clone_from(&other.ethereum_execution_rpc_url);
+        }
+
         if other.seeder_rpc_url.is_some() {
             self.seeder_rpc_url.clone_from(&other.seeder_rpc_url)
         }
@@ -120,6 +218,15 @@ impl ConfigOption {
             self.is_using_zkp.clone_from(&other.is_using_zkp);
         }
 
+        if other.is_watcher.is_some() {
+            self.is_watcher.clone_from(&other.is_watcher);
+        }
+
+        if other.duplicate_transaction_retention.is_some() {
+            self.duplicate_transaction_retention
+                .clone_from(&other.duplicate_transaction_retention);
+        }
+
         self
     }
 }
@@ -141,3 +248,46 @@ where
 
     toml_string.push_str(&name_value);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ethereum_server_is_disabled_by_default() {
+        let toml_string = ConfigOption::default().get_toml_string();
+
+        assert!(toml_string.contains("# ethereum_rpc_url = None\n"));
+        assert!(toml_string.contains("# ethereum_rollup_id = None\n"));
+        assert!(toml_string.contains("# ethereum_execution_rpc_url = None\n"));
+    }
+
+    #[test]
+    fn merge_overrides_only_the_set_ethereum_options() {
+        let config_option = ConfigOption {
+            ethereum_rpc_url: Some("http://127.0.0.1:8545".into()),
+            ethereum_rollup_id: Some("rollup".into()),
+            ..ConfigOption::default()
+        };
+        let other = ConfigOption {
+            ethereum_rollup_id: Some("other_rollup".into()),
+            ethereum_execution_rpc_url: Some("http://127.0.0.1:9545".into()),
+            ..ConfigOption::default()
+        };
+
+        let config_option = config_option.merge(&other);
+
+        assert_eq!(
+            config_option.ethereum_rpc_url.as_deref(),
+            Some("http://127.0.0.1:8545")
+        );
+        assert_eq!(
+            config_option.ethereum_rollup_id.as_deref(),
+            Some("other_rollup")
+        );
+        assert_eq!(
+            config_option.ethereum_execution_rpc_url.as_deref(),
+            Some("http://127.0.0.1:9545")
+        );
+    }
+}
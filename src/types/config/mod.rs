@@ -23,6 +23,11 @@ pub struct Config {
     pub external_rpc_url: String,
     pub internal_rpc_url: String,
     pub cluster_rpc_url: String,
+    pub subscription_rpc_url: String,
+
+    pub ethereum_rpc_url: Option<String>,
+    pub ethereum_rollup_id: Option<String>,
+    pub ethereum_execution_rpc_url: Option<String>,
 
     pub seeder_rpc_url: String,
 
@@ -31,6 +36,10 @@ pub struct Config {
     pub signing_key: String,
 
     pub is_using_zkp: bool,
+
+    pub is_watcher: bool,
+
+    pub duplicate_transaction_retention: u64,
 }
 
 /// Provides a default implementation for the `Config` struct.
@@ -46,12 +55,24 @@ pub struct Config {
 /// - `internal_rpc_url`: Internal RPC server address for internal
 ///   communication.
 /// - `cluster_rpc_url`: Address for cluster-related operations.
+/// - `subscription_rpc_url`: WebSocket server address for block and order
+///   commitment subscriptions.
+/// - `ethereum_rpc_url`: Ethereum JSON-RPC compatibility server address,
+///   disabled if unset.
+/// - `ethereum_rollup_id`: Rollup served by the Ethereum JSON-RPC compatibility
+///   server.
+/// - `ethereum_execution_rpc_url`: Execution RPC address of that rollup, which
+///   Ethereum read methods are proxied to.
 /// - `seeder_rpc_url`: Seeder service RPC address.
 /// - `distributed_key_generation_rpc_url`: RPC address for distributed key
 ///   generation service.
 /// - `signing_key`: A placeholder signing key for development.
 /// - `is_using_zkp`: Boolean flag indicating whether Zero-Knowledge Proofs
 ///   (ZKP) are enabled.
+/// - `is_watcher`: Boolean flag indicating whether the sequencer cross-checks
+///   the order commitments of the others for equivocation.
+/// - `duplicate_transaction_retention`: Number of rollup blocks for which a
+///   resubmitted transaction is answered with its existing order commitment.
 ///
 /// Note: For production use, ensure these values are set explicitly in the
 /// configuration file or environment variables to meet security and functional
@@ -63,10 +84,16 @@ impl Default for Config {
             external_rpc_url: "http://127.0.0.1:3000".to_string(),
             internal_rpc_url: "http://127.0.0.1:4000".to_string(),
             cluster_rpc_url: "http://127.0.0.1:5000".to_string(),
+            subscription_rpc_url: DEFAULT_SUBSCRIPTION_RPC_URL.to_string(),
+            ethereum_rpc_url: None,
+            ethereum_rollup_id: None,
+            ethereum_execution_rpc_url: None,
             seeder_rpc_url: "http://127.0.0.1:6000".to_string(),
             distributed_key_generation_rpc_url: "http://127.0.0.1:7100".to_string(),
             signing_key: DEFAULT_SIGNING_KEY.to_string(),
             is_using_zkp: true,
+            is_watcher: false,
+            duplicate_transaction_retention: DEFAULT_DUPLICATE_TRANSACTION_RETENTION,
         }
     }
 }
@@ -102,12 +129,22 @@ impl Config {
             external_rpc_url: merged_config_option.external_rpc_url.unwrap(),
             internal_rpc_url: merged_config_option.internal_rpc_url.unwrap(),
             cluster_rpc_url: merged_config_option.cluster_rpc_url.unwrap(),
+            subscription_rpc_url: merged_config_option
+                .subscription_rpc_url
+                .unwrap_or(DEFAULT_SUBSCRIPTION_RPC_URL.to_string()),
+            ethereum_rpc_url: merged_config_option.ethereum_rpc_url,
+            ethereum_rollup_id: merged_config_option.ethereum_rollup_id,
+            ethereum_execution_rpc_url: merged_config_option.ethereum_execution_rpc_url,
             seeder_rpc_url: merged_config_option.seeder_rpc_url.unwrap(),
             distributed_key_generation_rpc_url: merged_config_option
                 .distributed_key_generation_rpc_url
                 .unwrap(),
             signing_key,
             is_using_zkp: merged_config_option.is_using_zkp.unwrap(),
+            is_watcher: merged_config_option.is_watcher.unwrap_or(false),
+            duplicate_transaction_retention: merged_config_option
+                .duplicate_transaction_retention
+                .unwrap_or(DEFAULT_DUPLICATE_TRANSACTION_RETENTION),
         })
     }
 
@@ -136,6 +173,24 @@ impl Config {
             .ok_or(ConfigError::InvalidClusterPort)?
             .to_string())
     }
+
+    pub fn subscription_port(&self) -> Result<String, ConfigError> {
+        Ok(self
+            .subscription_rpc_url
+            .split(':')
+            .last()
+            .ok_or(ConfigError::InvalidSubscriptionPort)?
+            .to_string())
+    }
+
+    pub fn ethereum_port(&self) -> Result<String, ConfigError> {
+        Ok(self
+            .ethereum_rpc_url
+            .as_ref()
+            .and_then(|ethereum_rpc_url| ethereum_rpc_url.split(':').last())
+            .ok_or(ConfigError::InvalidEthereumPort)?
+            .to_string())
+    }
 }
 
 #[derive(Debug)]
@@ -148,6 +203,8 @@ pub enum ConfigError {
     CreatePrivateKeyFile(std::io::Error),
     InvalidExternalPort,
     InvalidClusterPort,
+    InvalidSubscriptionPort,
+    InvalidEthereumPort,
 }
 
 impl std::fmt::Display for ConfigError {
@@ -0,0 +1,181 @@
+use std::collections::btree_set::{self, BTreeSet};
+
+use super::prelude::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EquivocationKind {
+    /// Two different signed order commitments for the same transaction order.
+    ConflictingOrderCommitment,
+    /// The block does not include the transaction at its committed order.
+    BlockOrderMismatch,
+}
+
+/// Signed block the order commitment is checked against, with all of its
+/// Merkle leaves so that the commitment can be recomputed on-chain.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockEvidence {
+    #[serde(serialize_with = "serialize_address")]
+    pub block_creator_address: Address,
+    pub block_signature: Signature,
+    pub block_commitment: BlockCommitment,
+    pub transaction_hash_list: Vec<RawTransactionHash>,
+    pub merkle_hash_scheme: MerkleHashScheme,
+}
+
+/// Self-contained evidence that a leader broke an order commitment.
+///
+/// `order_commitment` is the one received first. A conflicting order
+/// commitment comes with [`EquivocationKind::ConflictingOrderCommitment`] and
+/// a block with [`EquivocationKind::BlockOrderMismatch`].
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64, transaction_order: u64))]
+pub struct EquivocationEvidence {
+    pub kind: EquivocationKind,
+
+    pub order_commitment: SignOrderCommitment,
+    pub conflicting_order_commitment: Option<SignOrderCommitment>,
+    pub block: Option<BlockEvidence>,
+}
+
+impl EquivocationEvidence {
+    pub fn conflicting_order_commitment(
+        order_commitment: SignOrderCommitment,
+        conflicting_order_commitment: SignOrderCommitment,
+    ) -> Self {
+        Self {
+            kind: EquivocationKind::ConflictingOrderCommitment,
+            order_commitment,
+            conflicting_order_commitment: Some(conflicting_order_commitment),
+            block: None,
+        }
+    }
+
+    pub fn block_order_mismatch(
+        order_commitment: SignOrderCommitment,
+        block: &Block,
+        merkle_leaf_list: Vec<RawTransactionHash>,
+    ) -> Self {
+        Self {
+            kind: EquivocationKind::BlockOrderMismatch,
+            order_commitment,
+            conflicting_order_commitment: None,
+            block: Some(BlockEvidence {
+                block_creator_address: block.block_creator_address.clone(),
+                block_signature: block.signature.clone(),
+                block_commitment: block.block_commitment.clone(),
+                transaction_hash_list: merkle_leaf_list,
+                merkle_hash_scheme: block.merkle_hash_scheme,
+            }),
+        }
+    }
+
+    /// Stores the evidence unless evidence for the same transaction order
+    /// already exists, in which case the first one is kept. The order
+    /// commitments must have been verified against the block leaders.
+    pub fn record(&self) -> Result<(), KvStoreError> {
+        let order_commitment_data = &self.order_commitment.data;
+        let rollup_id = &order_commitment_data.rollup_id;
+        let rollup_block_height = order_commitment_data.block_height;
+        let transaction_order = order_commitment_data.transaction_order;
+
+        let mut equivocation_evidence_list =
+            EquivocationEvidenceList::get_mut_or(rollup_id, EquivocationEvidenceList::default)?;
+        if equivocation_evidence_list.contains(rollup_block_height, transaction_order) {
+            return Ok(());
+        }
+
+        self.put(rollup_id, rollup_block_height, transaction_order)?;
+        equivocation_evidence_list.insert(rollup_block_height, transaction_order);
+        equivocation_evidence_list.update()?;
+
+        tracing::warn!(
+            "Equivocation recorded - kind: {:?} / rollup_id: {:?} / rollup_block_height: {:?} / transaction_order: {:?}",
+            self.kind,
+            rollup_id,
+            rollup_block_height,
+            transaction_order
+        );
+
+        Ok(())
+    }
+}
+
+/// `(rollup_block_height, transaction_order)` of the recorded evidence.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str))]
+pub struct EquivocationEvidenceList(BTreeSet<(u64, u64)>);
+
+impl EquivocationEvidenceList {
+    pub fn insert(&mut self, rollup_block_height: u64, transaction_order: u64) {
+        self.0.insert((rollup_block_height, transaction_order));
+    }
+
+    pub fn contains(&self, rollup_block_height: u64, transaction_order: u64) -> bool {
+        self.0.contains(&(rollup_block_height, transaction_order))
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, (u64, u64)> {
+        self.0.iter()
+    }
+}
+
+/// Next rollup block height the equivocation watcher checks.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str))]
+pub struct EquivocationWatchHeight(pub u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    fn sign_order_commitment(rollup_id: &str, transaction_hash_byte: u8) -> SignOrderCommitment {
+        SignOrderCommitment {
+            data: OrderCommitmentData {
+                rollup_id: rollup_id.to_owned(),
+                block_height: 5,
+                transaction_order: 2,
+                transaction_hash: RawTransactionHash::from([transaction_hash_byte; 32]).as_string(),
+                ..Default::default()
+            },
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn first_evidence_of_an_order_is_kept() {
+        init_test_database();
+        let rollup_id = "equivocation_evidence_first";
+
+        for conflicting_transaction_hash_byte in [2, 3] {
+            EquivocationEvidence::conflicting_order_commitment(
+                sign_order_commitment(rollup_id, 1),
+                sign_order_commitment(rollup_id, conflicting_transaction_hash_byte),
+            )
+            .record()
+            .unwrap();
+        }
+
+        let equivocation_evidence = EquivocationEvidence::get(rollup_id, 5, 2).unwrap();
+        let equivocation_evidence_list = EquivocationEvidenceList::get(rollup_id).unwrap();
+
+        assert_eq!(
+            equivocation_evidence.kind,
+            EquivocationKind::ConflictingOrderCommitment
+        );
+        assert_eq!(
+            equivocation_evidence
+                .conflicting_order_commitment
+                .map(|sign_order_commitment| sign_order_commitment.data.transaction_hash),
+            Some(RawTransactionHash::from([2u8; 32]).as_string())
+        );
+        assert_eq!(
+            equivocation_evidence_list
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [(5, 2)]
+        );
+    }
+}
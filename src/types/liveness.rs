@@ -38,8 +38,12 @@ pub struct LivenessRadius {
     pub contract_address: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct LivenessLocal;
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LivenessLocal {
+    /// JSON file with the list of local clusters, loaded on startup.
+    #[serde(default)]
+    pub cluster_file_path: Option<String>,
+}
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
 #[kvstore(key())]
@@ -1,21 +1,167 @@
 use std::sync::Arc;
 
-use sha3::{Digest, Keccak256};
 use tokio::sync::Mutex;
 
+use super::prelude::*;
+
+/// Last leaf added to the Merkle tree of the open block, with the frontier it
+/// was added to. Restoring from it replaces re-adding every leaf on restart.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str))]
+pub struct MerkleTreeCheckpoint {
+    pub rollup_block_height: u64,
+    pub leaf_index: u64,
+    pub pre_merkle_path: Vec<[u8; 32]>,
+    pub leaf: [u8; 32],
+
+    #[serde(default)]
+    pub hash_scheme: MerkleHashScheme,
+}
+
+/// How [`MerkleTree::finalize_tree`] completes the tree up to its root.
+///
+/// Every block records the version it was built with, so that its commitment
+/// is always rebuilt the same way. Blocks opened from now on use
+/// [`Self::LATEST`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MerkleRootVersion {
+    /// Misses the parent of the pair completed by the last leaf and of the
+    /// levels the padding made even, leaving trailing leaves out of the root
+    /// for some block sizes (e.g. 4, 6 and 7 leaves).
+    V0,
+    /// Computes every missing parent.
+    #[default]
+    V1,
+}
+
+impl MerkleRootVersion {
+    pub const LATEST: Self = Self::V1;
+
+    /// Version of the records stored before it was recorded.
+    pub fn unversioned() -> Self {
+        Self::V0
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct MerkleTree {
     pub nodes: Arc<Mutex<Vec<Vec<[u8; 32]>>>>, // nodes by tree level
+
+    hash_scheme: MerkleHashScheme,
+    /// `(rollup_id, rollup_block_height)` to checkpoint every added leaf to.
+    checkpoint_key: Option<Arc<(String, u64)>>,
 }
 
 impl MerkleTree {
     pub fn new() -> Self {
+        Self::with_hash_scheme(MerkleHashScheme::default())
+    }
+
+    pub fn with_hash_scheme(hash_scheme: MerkleHashScheme) -> Self {
         Self {
             nodes: Arc::new(Mutex::new(vec![vec![]])),
+            hash_scheme,
+            checkpoint_key: None,
         }
     }
 
-    fn update_tree(nodes: &mut Vec<Vec<[u8; 32]>>) {
+    /// Creates the tree of an open block, which stores a
+    /// [`MerkleTreeCheckpoint`] on every [`Self::add_data`].
+    pub fn with_checkpoint(
+        rollup_id: &str,
+        rollup_block_height: u64,
+        hash_scheme: MerkleHashScheme,
+    ) -> Self {
+        Self {
+            checkpoint_key: Some(Arc::new((rollup_id.to_owned(), rollup_block_height))),
+            ..Self::with_hash_scheme(hash_scheme)
+        }
+    }
+
+    /// Restores the tree of an open block from its checkpoint.
+    pub fn from_checkpoint(
+        rollup_id: &str,
+        merkle_tree_checkpoint: &MerkleTreeCheckpoint,
+    ) -> Option<Self> {
+        let nodes = Self::seed_nodes(
+            merkle_tree_checkpoint.leaf_index,
+            &merkle_tree_checkpoint.pre_merkle_path,
+            merkle_tree_checkpoint.leaf,
+        )?;
+
+        Some(Self {
+            nodes: Arc::new(Mutex::new(nodes)),
+            hash_scheme: merkle_tree_checkpoint.hash_scheme,
+            checkpoint_key: Some(Arc::new((
+                rollup_id.to_owned(),
+                merkle_tree_checkpoint.rollup_block_height,
+            ))),
+        })
+    }
+
+    /// Restores the tree as it was right after `data` was added at
+    /// `leaf_index` with the given pre-Merkle path, so that adding the later
+    /// leaves reproduces the original Merkle root.
+    ///
+    /// Later insertions and [`Self::finalize_tree`] only read the nodes on the
+    /// pre-Merkle path, so the nodes left of it are filled with placeholders.
+    pub fn from_pre_merkle_path(
+        leaf_index: u64,
+        pre_merkle_path: &[[u8; 32]],
+        data: &str,
+        hash_scheme: MerkleHashScheme,
+    ) -> Option<Self> {
+        let leaf = hash_scheme.hasher().hash_leaf(data);
+        let nodes = Self::seed_nodes(leaf_index, pre_merkle_path, leaf)?;
+
+        Some(Self {
+            nodes: Arc::new(Mutex::new(nodes)),
+            hash_scheme,
+            checkpoint_key: None,
+        })
+    }
+
+    fn seed_nodes(
+        leaf_index: u64,
+        pre_merkle_path: &[[u8; 32]],
+        leaf: [u8; 32],
+    ) -> Option<Vec<Vec<[u8; 32]>>> {
+        if pre_merkle_path.len() != leaf_index.count_ones() as usize {
+            return None;
+        }
+
+        let mut nodes = vec![vec![]];
+        let mut level = 0;
+        while (leaf_index >> level) > 0 {
+            let level_nodes = vec![[0u8; 32]; (leaf_index >> level) as usize];
+            if level == 0 {
+                nodes[0] = level_nodes;
+            } else {
+                nodes.push(level_nodes);
+            }
+
+            level += 1;
+        }
+
+        // The path lists the roots of the perfect subtrees, largest first.
+        let mut pre_merkle_path = pre_merkle_path.iter();
+        for level in (0..nodes.len()).rev() {
+            if (leaf_index >> level) & 1 == 1 {
+                *nodes[level].last_mut()? = *pre_merkle_path.next()?;
+            }
+        }
+
+        nodes[0].push(leaf);
+
+        Some(nodes)
+    }
+
+    pub fn hash_scheme(&self) -> MerkleHashScheme {
+        self.hash_scheme
+    }
+
+    fn update_tree(hasher: &dyn MerkleHasher, nodes: &mut Vec<Vec<[u8; 32]>>) {
         let mut current_level = 0;
 
         if nodes[current_level].is_empty() {
@@ -27,7 +173,7 @@ impl MerkleTree {
             let right_node = &level[level.len() - 1];
             let left_node = &level[level.len() - 2];
 
-            let parent_node = Self::hash(&Self::concat_arrays(*left_node, *right_node));
+            let parent_node = hasher.hash_node(*left_node, *right_node);
 
             if nodes.len() <= current_level + 1 {
                 nodes.push(vec![parent_node]);
@@ -39,15 +185,30 @@ impl MerkleTree {
         }
     }
 
-    pub async fn finalize_tree(&self) {
+    /// Pads every level with an odd number of nodes with the last leaf and
+    /// computes the parents that are still missing, up to the root.
+    pub async fn finalize_tree(&self, merkle_root_version: MerkleRootVersion) {
+        let hasher = self.hash_scheme.hasher();
         let mut nodes = self.nodes.lock().await;
         let last_node = nodes[0].last().cloned().unwrap_or_default();
+
+        match merkle_root_version {
+            MerkleRootVersion::V0 => Self::finalize_tree_v0(hasher, &mut nodes, last_node),
+            MerkleRootVersion::V1 => Self::finalize_tree_v1(hasher, &mut nodes, last_node),
+        }
+    }
+
+    fn finalize_tree_v0(
+        hasher: &dyn MerkleHasher,
+        nodes: &mut Vec<Vec<[u8; 32]>>,
+        last_node: [u8; 32],
+    ) {
         let mut current_level = 0;
 
         while nodes[current_level].len() > 1 {
             if nodes[current_level].len() % 2 == 1 {
-                let left_node = nodes[current_level].last().unwrap();
-                let parent_node = Self::hash(&Self::concat_arrays(*left_node, last_node));
+                let left_node = nodes[current_level][nodes[current_level].len() - 1];
+                let parent_node = hasher.hash_node(left_node, last_node);
 
                 nodes[current_level].push(last_node);
                 nodes[current_level + 1].push(parent_node);
@@ -57,7 +218,7 @@ impl MerkleTree {
                 let left_node = nodes[current_level][0];
                 let right_node = nodes[current_level][1];
 
-                let merkle_root = Self::hash(&Self::concat_arrays(left_node, right_node));
+                let merkle_root = hasher.hash_node(left_node, right_node);
                 nodes.push(vec![merkle_root]);
             }
 
@@ -65,16 +226,70 @@ impl MerkleTree {
         }
     }
 
+    fn finalize_tree_v1(
+        hasher: &dyn MerkleHasher,
+        nodes: &mut Vec<Vec<[u8; 32]>>,
+        last_node: [u8; 32],
+    ) {
+        let mut current_level = 0;
+
+        while nodes[current_level].len() > 1 {
+            if nodes[current_level].len() % 2 == 1 {
+                nodes[current_level].push(last_node);
+            }
+
+            if nodes.len() <= current_level + 1 {
+                nodes.push(vec![]);
+            }
+
+            // `add_data` merges a pair only when the next leaf arrives.
+            let parent_node_count = nodes[current_level].len() / 2;
+            while nodes[current_level + 1].len() < parent_node_count {
+                let index = nodes[current_level + 1].len() * 2;
+                let left_node = nodes[current_level][index];
+                let right_node = nodes[current_level][index + 1];
+
+                let parent_node = hasher.hash_node(left_node, right_node);
+                nodes[current_level + 1].push(parent_node);
+            }
+
+            current_level += 1;
+        }
+    }
+
     pub async fn add_data(&self, data: &str) -> (u64, Vec<[u8; 32]>) {
+        let hasher = self.hash_scheme.hasher();
         let mut nodes = self.nodes.lock().await;
-        Self::update_tree(&mut nodes);
+        Self::update_tree(hasher, &mut nodes);
 
         let pre_merkle_path = Self::get_pre_merkle_path(&nodes);
 
-        let hashed_data = Self::hash(data.as_bytes());
+        let hashed_data = hasher.hash_leaf(data);
         nodes[0].push(hashed_data);
+        let leaf_index = (nodes[0].len() - 1) as u64;
+
+        // Stored under the lock so that checkpoints are written in order.
+        if let Some(checkpoint_key) = &self.checkpoint_key {
+            let (rollup_id, rollup_block_height) = checkpoint_key.as_ref();
+            let merkle_tree_checkpoint = MerkleTreeCheckpoint {
+                rollup_block_height: *rollup_block_height,
+                leaf_index,
+                pre_merkle_path: pre_merkle_path.clone(),
+                leaf: hashed_data,
+                hash_scheme: self.hash_scheme,
+            };
+
+            if let Err(error) = merkle_tree_checkpoint.put(rollup_id) {
+                tracing::error!(
+                    "Failed to store the Merkle tree checkpoint - rollup_id: {:?} / leaf_index: {:?} / error: {:?}",
+                    rollup_id,
+                    leaf_index,
+                    error
+                );
+            }
+        }
 
-        ((nodes[0].len() - 1) as u64, pre_merkle_path)
+        (leaf_index, pre_merkle_path)
     }
 
     fn get_pre_merkle_path(nodes: &Vec<Vec<[u8; 32]>>) -> Vec<[u8; 32]> {
@@ -141,7 +356,7 @@ impl MerkleTree {
     pub async fn get_merkle_root(&self) -> [u8; 32] {
         let nodes = self.nodes.lock().await;
         if nodes[0].is_empty() {
-            return Self::hash(b"");
+            return self.hash_scheme.hasher().empty_root();
         }
 
         nodes
@@ -150,15 +365,6 @@ impl MerkleTree {
             .unwrap()
     }
 
-    pub fn hash(data: &[u8]) -> [u8; 32] {
-        let mut hasher = Keccak256::new();
-        hasher.update(data);
-        let result = hasher.finalize();
-        let mut hash = [0u8; 32];
-        hash.copy_from_slice(&result);
-        hash
-    }
-
     pub async fn get_post_merkle_path(&self, mut index: usize) -> Vec<[u8; 32]> {
         let nodes = self.nodes.lock().await;
         let mut post_merkle_path = Vec::new();
@@ -173,12 +379,160 @@ impl MerkleTree {
 
         post_merkle_path
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    const LEAF_LIST: [&str; 5] = ["0x01", "0x02", "0x03", "0x04", "0x05"];
+
+    async fn build_tree(leaf_count: usize) -> MerkleTree {
+        let merkle_tree = MerkleTree::new();
+        for data in &LEAF_LIST[..leaf_count] {
+            merkle_tree.add_data(data).await;
+        }
+
+        merkle_tree
+    }
+
+    fn leaf(index: usize) -> [u8; 32] {
+        KeccakHasher.hash_leaf(LEAF_LIST[index])
+    }
+
+    fn node(left_node: [u8; 32], right_node: [u8; 32]) -> [u8; 32] {
+        KeccakHasher.hash_node(left_node, right_node)
+    }
+
+    #[tokio::test]
+    async fn v1_root_covers_every_leaf() {
+        let merkle_tree = build_tree(4).await;
+        merkle_tree.finalize_tree(MerkleRootVersion::V1).await;
+
+        let merkle_root = node(node(leaf(0), leaf(1)), node(leaf(2), leaf(3)));
+        assert_eq!(merkle_tree.get_merkle_root().await, merkle_root);
+    }
 
-    fn concat_arrays(a: [u8; 32], b: [u8; 32]) -> [u8; 64] {
-        let mut array: [u8; 64] = [0; 64];
-        for (index, value) in a.into_iter().chain(b.into_iter()).enumerate() {
-            array[index] = value;
+    #[tokio::test]
+    async fn v1_root_pads_odd_levels_with_the_last_leaf() {
+        let merkle_tree = build_tree(3).await;
+        merkle_tree.finalize_tree(MerkleRootVersion::V1).await;
+
+        let merkle_root = node(node(leaf(0), leaf(1)), node(leaf(2), leaf(2)));
+        assert_eq!(merkle_tree.get_merkle_root().await, merkle_root);
+    }
+
+    #[tokio::test]
+    async fn v0_root_is_unchanged() {
+        // Blocks built before the version was recorded must keep their root,
+        // which leaves the last pair out for 4 leaves.
+        let merkle_tree = build_tree(4).await;
+        merkle_tree.finalize_tree(MerkleRootVersion::V0).await;
+
+        assert_eq!(merkle_tree.get_merkle_root().await, node(leaf(0), leaf(1)));
+    }
+
+    #[tokio::test]
+    async fn merkle_path_leads_to_the_root() {
+        let merkle_tree = build_tree(5).await;
+        merkle_tree.finalize_tree(MerkleRootVersion::V1).await;
+        let merkle_root = merkle_tree.get_merkle_root().await;
+
+        for index in 0..LEAF_LIST.len() {
+            let merkle_path = merkle_tree.get_merkle_path(index).await;
+            let computed_merkle_root = merkle_path.iter().enumerate().fold(
+                leaf(index),
+                |current_node, (level, sibling_node)| {
+                    if (index >> level) % 2 == 0 {
+                        node(current_node, *sibling_node)
+                    } else {
+                        node(*sibling_node, current_node)
+                    }
+                },
+            );
+
+            assert_eq!(computed_merkle_root, merkle_root);
+        }
+    }
+
+    #[tokio::test]
+    async fn pre_merkle_path_reproduces_the_root() {
+        for merkle_root_version in [MerkleRootVersion::V0, MerkleRootVersion::V1] {
+            let merkle_tree = MerkleTree::new();
+            let mut pre_merkle_path_list = Vec::new();
+            for data in LEAF_LIST {
+                pre_merkle_path_list.push(merkle_tree.add_data(data).await.1);
+            }
+            merkle_tree.finalize_tree(merkle_root_version).await;
+            let merkle_root = merkle_tree.get_merkle_root().await;
+
+            for (index, pre_merkle_path) in pre_merkle_path_list.iter().enumerate() {
+                let restored_merkle_tree = MerkleTree::from_pre_merkle_path(
+                    index as u64,
+                    pre_merkle_path,
+                    LEAF_LIST[index],
+                    MerkleHashScheme::Keccak,
+                )
+                .unwrap();
+                for data in &LEAF_LIST[index + 1..] {
+                    restored_merkle_tree.add_data(data).await;
+                }
+                restored_merkle_tree
+                    .finalize_tree(merkle_root_version)
+                    .await;
+
+                assert_eq!(restored_merkle_tree.get_merkle_root().await, merkle_root);
+            }
         }
-        array
+    }
+
+    #[test]
+    fn pre_merkle_path_of_the_wrong_length_is_rejected() {
+        let merkle_tree =
+            MerkleTree::from_pre_merkle_path(3, &[[0u8; 32]], "0x04", MerkleHashScheme::Keccak);
+
+        assert!(merkle_tree.is_none());
+    }
+
+    #[tokio::test]
+    async fn checkpoint_restores_the_open_tree() {
+        init_test_database();
+        let rollup_id = "merkle_tree_checkpoint";
+
+        let merkle_tree = MerkleTree::with_checkpoint(rollup_id, 7, MerkleHashScheme::Keccak);
+        for data in &LEAF_LIST[..3] {
+            merkle_tree.add_data(data).await;
+        }
+
+        let merkle_tree_checkpoint = MerkleTreeCheckpoint::get(rollup_id).unwrap();
+        let restored_merkle_tree =
+            MerkleTree::from_checkpoint(rollup_id, &merkle_tree_checkpoint).unwrap();
+        for data in &LEAF_LIST[3..] {
+            restored_merkle_tree.add_data(data).await;
+        }
+        restored_merkle_tree
+            .finalize_tree(MerkleRootVersion::V1)
+            .await;
+
+        let merkle_tree = build_tree(LEAF_LIST.len()).await;
+        merkle_tree.finalize_tree(MerkleRootVersion::V1).await;
+
+        assert_eq!(merkle_tree_checkpoint.rollup_block_height, 7);
+        assert_eq!(merkle_tree_checkpoint.leaf_index, 2);
+        assert_eq!(
+            restored_merkle_tree.get_merkle_root().await,
+            merkle_tree.get_merkle_root().await
+        );
+        // The restored tree keeps checkpointing the leaves added to it.
+        assert_eq!(MerkleTreeCheckpoint::get(rollup_id).unwrap().leaf_index, 4);
+    }
+
+    #[test]
+    fn unversioned_records_are_v0() {
+        let merkle_root_version: MerkleRootVersion = serde_json::from_str("\"v0\"").unwrap();
+
+        assert_eq!(merkle_root_version, MerkleRootVersion::unversioned());
+        assert_eq!(MerkleRootVersion::default(), MerkleRootVersion::LATEST);
     }
 }
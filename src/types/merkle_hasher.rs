@@ -0,0 +1,184 @@
+use ark_bn254::Fr;
+use light_poseidon::{Poseidon, PoseidonBytesHasher};
+use sha3::{Digest, Keccak256};
+
+use super::prelude::*;
+
+/// Hash function of the order-commitment Merkle tree.
+pub trait MerkleHasher {
+    /// Hashes a transaction hash into a leaf.
+    fn hash_leaf(&self, data: &str) -> [u8; 32];
+
+    fn hash_node(&self, left_node: [u8; 32], right_node: [u8; 32]) -> [u8; 32];
+
+    /// Root of a tree without leaves.
+    fn empty_root(&self) -> [u8; 32];
+}
+
+/// Leaf hashing of a rollup's Merkle tree, recorded in the blocks and the
+/// order commitments built with it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MerkleHashScheme {
+    #[default]
+    Keccak,
+    Poseidon,
+}
+
+impl MerkleHashScheme {
+    pub fn hasher(self) -> &'static dyn MerkleHasher {
+        match self {
+            Self::Keccak => &KeccakHasher,
+            Self::Poseidon => &PoseidonHasher,
+        }
+    }
+}
+
+/// Keccak-256 over the hex string bytes of the transaction hash for the
+/// leaves, and over the concatenated children for the nodes.
+pub struct KeccakHasher;
+
+impl KeccakHasher {
+    pub fn hash(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(data);
+        let result = hasher.finalize();
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&result);
+        hash
+    }
+}
+
+impl MerkleHasher for KeccakHasher {
+    fn hash_leaf(&self, data: &str) -> [u8; 32] {
+        Self::hash(data.as_bytes())
+    }
+
+    fn hash_node(&self, left_node: [u8; 32], right_node: [u8; 32]) -> [u8; 32] {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(&left_node);
+        data[32..].copy_from_slice(&right_node);
+
+        Self::hash(&data)
+    }
+
+    fn empty_root(&self) -> [u8; 32] {
+        Self::hash(b"")
+    }
+}
+
+/// Circom-compatible Poseidon over the BN254 scalar field, so that a zk
+/// circuit can verify order commitments.
+///
+/// The 32 raw bytes of the transaction hash do not fit in a field element,
+/// so a leaf is `Poseidon(high 16 bytes, low 16 bytes)`. A transaction hash
+/// that is not 32 bytes of hex is Keccak-hashed into 32 bytes first.
+pub struct PoseidonHasher;
+
+impl PoseidonHasher {
+    fn hash(left_input: &[u8], right_input: &[u8]) -> [u8; 32] {
+        // Both inputs are below the field modulus: halves of 32 bytes, or
+        // outputs of this hash.
+        Poseidon::<Fr>::new_circom(2)
+            .and_then(|mut poseidon| poseidon.hash_bytes_be(&[left_input, right_input]))
+            .expect("Poseidon input must be below the BN254 field modulus")
+    }
+}
+
+impl MerkleHasher for PoseidonHasher {
+    fn hash_leaf(&self, data: &str) -> [u8; 32] {
+        let transaction_hash: [u8; 32] = const_hex::decode(data)
+            .ok()
+            .and_then(|transaction_hash| transaction_hash.try_into().ok())
+            .unwrap_or_else(|| KeccakHasher::hash(data.as_bytes()));
+
+        Self::hash(&transaction_hash[..16], &transaction_hash[16..])
+    }
+
+    fn hash_node(&self, left_node: [u8; 32], right_node: [u8; 32]) -> [u8; 32] {
+        Self::hash(&left_node, &right_node)
+    }
+
+    fn empty_root(&self) -> [u8; 32] {
+        [0u8; 32]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_element(value: u8) -> [u8; 32] {
+        let mut field_element = [0u8; 32];
+        field_element[31] = value;
+        field_element
+    }
+
+    #[test]
+    fn poseidon_matches_circom() {
+        // Poseidon(1, 2) of circomlib.
+        let hash =
+            const_hex::decode("0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a")
+                .unwrap();
+
+        assert_eq!(
+            PoseidonHasher.hash_node(field_element(1), field_element(2)),
+            hash.as_slice()
+        );
+    }
+
+    #[test]
+    fn poseidon_leaf_splits_the_transaction_hash() {
+        let transaction_hash = KeccakHasher::hash(b"transaction");
+        let leaf = PoseidonHasher.hash_leaf(&const_hex::encode_prefixed(transaction_hash));
+
+        assert_eq!(
+            leaf,
+            PoseidonHasher::hash(&transaction_hash[..16], &transaction_hash[16..])
+        );
+    }
+
+    #[test]
+    fn poseidon_leaf_hashes_other_data_with_keccak_first() {
+        let leaf = PoseidonHasher.hash_leaf("transaction");
+        let transaction_hash = const_hex::encode_prefixed(KeccakHasher::hash(b"transaction"));
+
+        assert_eq!(leaf, PoseidonHasher.hash_leaf(&transaction_hash));
+    }
+
+    #[test]
+    fn keccak_leaf_hashes_the_hex_string() {
+        let transaction_hash = const_hex::encode_prefixed([1u8; 32]);
+
+        assert_eq!(
+            KeccakHasher.hash_leaf(&transaction_hash),
+            KeccakHasher::hash(transaction_hash.as_bytes())
+        );
+    }
+
+    #[test]
+    fn hash_schemes_differ() {
+        let transaction_hash = const_hex::encode_prefixed([1u8; 32]);
+
+        assert_ne!(
+            MerkleHashScheme::Keccak
+                .hasher()
+                .hash_leaf(&transaction_hash),
+            MerkleHashScheme::Poseidon
+                .hasher()
+                .hash_leaf(&transaction_hash)
+        );
+        assert_ne!(
+            MerkleHashScheme::Keccak.hasher().empty_root(),
+            MerkleHashScheme::Poseidon.hasher().empty_root()
+        );
+    }
+
+    #[test]
+    fn hash_scheme_defaults_to_keccak() {
+        let hash_scheme: MerkleHashScheme = serde_json::from_str("\"poseidon\"").unwrap();
+
+        assert_eq!(hash_scheme, MerkleHashScheme::Poseidon);
+        assert_eq!(MerkleHashScheme::default(), MerkleHashScheme::Keccak);
+    }
+}
@@ -1,25 +1,35 @@
 mod block;
 mod cluster;
+mod cluster_envelope;
 mod config;
+mod equivocation_evidence;
 mod liveness;
 mod merkle;
+mod merkle_hasher;
 mod order_commitment;
 mod platform;
 mod rollup;
+mod signature_scheme;
+mod skde_key_status;
 mod transaction;
 mod validation;
 mod version;
 
 pub use block::*;
 pub use cluster::*;
+pub use cluster_envelope::*;
 pub use config::*;
+pub use equivocation_evidence::*;
 pub use liveness::*;
 pub use merkle::*;
+pub use merkle_hasher::*;
 pub use order_commitment::*;
 pub use platform::*;
 use radius_sdk::signature::Address;
 pub use rollup::*;
 use serde::ser::SerializeSeq;
+pub use signature_scheme::*;
+pub use skde_key_status::*;
 pub use transaction::*;
 pub use validation::*;
 pub use version::*;
@@ -100,3 +110,19 @@ where
 
     deserializer.deserialize_seq(MerklePathVisitor)
 }
+
+/// Opens the database shared by the tests of this process. Each test keeps
+/// its records apart under its own rollup id.
+#[cfg(test)]
+pub(crate) fn init_test_database() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+
+    INIT.call_once(|| {
+        let database_path =
+            std::env::temp_dir().join(format!("sequencer-test-{}", std::process::id()));
+        radius_sdk::kvstore::KvStoreBuilder::default()
+            .build(database_path)
+            .unwrap()
+            .init();
+    });
+}
@@ -1,10 +1,161 @@
-use radius_sdk::signature::Signature;
+use radius_sdk::signature::{PrivateKeySigner, Signature};
 use serde::{Deserialize, Serialize};
 
 use super::SingleOrderCommitment;
+use crate::{
+    error::Error,
+    types::{BundleOrderCommitmentMessage, SignatureScheme},
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BundleOrderCommitment {
     pub order_commitment_list: Vec<SingleOrderCommitment>,
     pub signature: Signature,
 }
+
+impl BundleOrderCommitment {
+    /// Signs the order commitments of a batch whose first transaction took
+    /// `first_transaction_order`. `signing_key` is the key `signer` was
+    /// created from.
+    pub fn sign(
+        signature_scheme: &SignatureScheme,
+        signer: &PrivateKeySigner,
+        signing_key: &str,
+        rollup_id: &str,
+        rollup_block_height: u64,
+        first_transaction_order: u64,
+        order_commitment_list: Vec<SingleOrderCommitment>,
+    ) -> Result<Self, Error> {
+        let signature = signature_scheme.sign(
+            signer,
+            signing_key,
+            &BundleOrderCommitmentMessage {
+                rollup_id,
+                rollup_block_height,
+                first_transaction_order,
+                order_commitment_list: &order_commitment_list,
+            },
+        )?;
+
+        Ok(Self {
+            order_commitment_list,
+            signature,
+        })
+    }
+
+    /// Message `signature` was made over.
+    pub fn message<'a>(
+        &'a self,
+        rollup_id: &'a str,
+        rollup_block_height: u64,
+        first_transaction_order: u64,
+    ) -> BundleOrderCommitmentMessage<'a> {
+        BundleOrderCommitmentMessage {
+            rollup_id,
+            rollup_block_height,
+            first_transaction_order,
+            order_commitment_list: &self.order_commitment_list,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use radius_sdk::signature::{Address, ChainType};
+
+    use super::*;
+    use crate::types::{Eip712Domain, TransactionHashOrderCommitment};
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const SIGNER_ADDRESS: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+    fn order_commitment_list() -> Vec<SingleOrderCommitment> {
+        ["0x01", "0x02"]
+            .into_iter()
+            .map(|transaction_hash| {
+                SingleOrderCommitment::TransactionHash(TransactionHashOrderCommitment::new(
+                    transaction_hash.to_owned(),
+                ))
+            })
+            .collect()
+    }
+
+    fn signature_scheme_list() -> [SignatureScheme; 2] {
+        [
+            SignatureScheme::Json,
+            SignatureScheme::Eip712(Eip712Domain {
+                chain_id: 1,
+                verifying_contract: Address::from_slice(ChainType::Ethereum, &[0xcc; 20]).unwrap(),
+            }),
+        ]
+    }
+
+    fn sign(signature_scheme: &SignatureScheme) -> BundleOrderCommitment {
+        let signer = PrivateKeySigner::from_str(ChainType::Ethereum, SIGNING_KEY).unwrap();
+
+        BundleOrderCommitment::sign(
+            signature_scheme,
+            &signer,
+            SIGNING_KEY,
+            "rollup_id",
+            7,
+            3,
+            order_commitment_list(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bundle_is_signed_with_the_signature_scheme() {
+        let signer_address = Address::from_str(ChainType::Ethereum, SIGNER_ADDRESS).unwrap();
+
+        for signature_scheme in signature_scheme_list() {
+            let bundle_order_commitment = sign(&signature_scheme);
+
+            assert!(signature_scheme
+                .verify(
+                    ChainType::Ethereum,
+                    &bundle_order_commitment.signature,
+                    &bundle_order_commitment.message("rollup_id", 7, 3),
+                    &signer_address,
+                )
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn bundle_signature_is_bound_to_the_order_commitment_list() {
+        let signer_address = Address::from_str(ChainType::Ethereum, SIGNER_ADDRESS).unwrap();
+
+        for signature_scheme in signature_scheme_list() {
+            let mut bundle_order_commitment = sign(&signature_scheme);
+            bundle_order_commitment.order_commitment_list.reverse();
+
+            assert!(signature_scheme
+                .verify(
+                    ChainType::Ethereum,
+                    &bundle_order_commitment.signature,
+                    &bundle_order_commitment.message("rollup_id", 7, 3),
+                    &signer_address,
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn eip712_bundle_signature_is_bound_to_the_first_order() {
+        let signer_address = Address::from_str(ChainType::Ethereum, SIGNER_ADDRESS).unwrap();
+        let signature_scheme = signature_scheme_list()[1].clone();
+        let bundle_order_commitment = sign(&signature_scheme);
+
+        assert!(signature_scheme
+            .verify(
+                ChainType::Ethereum,
+                &bundle_order_commitment.signature,
+                &bundle_order_commitment.message("rollup_id", 7, 4),
+                &signer_address,
+            )
+            .is_err());
+    }
+}
@@ -22,3 +22,15 @@ impl Default for OrderCommitment {
         Self::Single(SingleOrderCommitment::default())
     }
 }
+
+impl OrderCommitment {
+    /// Returns the signed order commitment of a single transaction.
+    pub fn as_sign_order_commitment(&self) -> Option<&SignOrderCommitment> {
+        match self {
+            Self::Single(SingleOrderCommitment::Sign(sign_order_commitment)) => {
+                Some(sign_order_commitment)
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{deserialize_merkle_path, serialize_merkle_path, RawTransactionHash};
+use crate::types::{
+    deserialize_merkle_path, serialize_merkle_path, MerkleHashScheme, RawTransactionHash,
+    SignatureScheme,
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SignOrderCommitment {
@@ -21,6 +24,21 @@ pub struct OrderCommitmentData {
         deserialize_with = "deserialize_merkle_path"
     )]
     pub pre_merkle_path: Vec<[u8; 32]>,
+
+    /// Hash scheme of the Merkle tree `pre_merkle_path` belongs to.
+    #[serde(default)]
+    pub merkle_hash_scheme: MerkleHashScheme,
+
+    /// Key id an SKDE transaction was encrypted under, which the leader
+    /// checked could not be decrypted yet when it ordered the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<u64>,
+
+    /// Signature scheme of the block, which `signature` was made with. The
+    /// JSON scheme signs it along with the rest of the data, while an EIP-712
+    /// signature commits to it through its domain.
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
 }
 
 impl Default for OrderCommitmentData {
@@ -31,6 +49,9 @@ impl Default for OrderCommitmentData {
             transaction_order: 0,
             transaction_hash: RawTransactionHash::default().as_string(),
             pre_merkle_path: Vec::new(),
+            merkle_hash_scheme: MerkleHashScheme::default(),
+            key_id: None,
+            signature_scheme: SignatureScheme::default(),
         }
     }
 }
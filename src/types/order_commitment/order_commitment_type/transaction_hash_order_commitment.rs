@@ -7,4 +7,8 @@ impl TransactionHashOrderCommitment {
     pub fn new(value: String) -> Self {
         Self(value)
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
@@ -15,3 +15,15 @@ impl Default for SingleOrderCommitment {
         Self::TransactionHash(TransactionHashOrderCommitment::default())
     }
 }
+
+impl SingleOrderCommitment {
+    /// Hash of the transaction the order commitment was issued for.
+    pub fn transaction_hash(&self) -> &str {
+        match self {
+            Self::TransactionHash(transaction_hash_order_commitment) => {
+                transaction_hash_order_commitment.as_str()
+            }
+            Self::Sign(sign_order_commitment) => &sign_order_commitment.data.transaction_hash,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::types::prelude::*;
+
+/// Sequencers that led a rollup block, in the order they took over. A leader
+/// change only adds to the list, so order commitments issued before the
+/// change stay attributable.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64))]
+pub struct BlockLeader {
+    #[serde(serialize_with = "serialize_address_list")]
+    pub leader_address_list: Vec<Address>,
+}
+
+impl BlockLeader {
+    pub fn record(
+        rollup_id: &str,
+        rollup_block_height: u64,
+        leader_address: &Address,
+    ) -> Result<(), KvStoreError> {
+        let mut block_leader =
+            BlockLeader::get_mut_or(rollup_id, rollup_block_height, BlockLeader::default)?;
+        if !block_leader.contains(leader_address) {
+            block_leader
+                .leader_address_list
+                .push(leader_address.clone());
+            block_leader.update()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.leader_address_list.contains(address)
+    }
+}
@@ -1,9 +1,11 @@
+mod block_leader;
 mod rollup_metadata;
 mod rollup_type;
 mod rollup_validation_info;
 
 use std::collections::{btree_set, BTreeSet};
 
+pub use block_leader::*;
 pub use rollup_metadata::*;
 pub use rollup_type::*;
 pub use rollup_validation_info::*;
@@ -31,6 +33,25 @@ pub struct Rollup {
     pub executor_address_list: Vec<Address>,
 
     pub max_gas_limit: u64,
+
+    /// Chain id raw transactions must be signed for. `None` accepts any.
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+
+    #[serde(default)]
+    pub nonce_policy: NoncePolicy,
+
+    /// Applies from the block opened by the next `finalize_block`.
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
+
+    /// Applies from the block opened by the next `finalize_block`.
+    #[serde(default)]
+    pub merkle_hash_scheme: MerkleHashScheme,
+
+    /// PVDE transactions are rejected until it is set.
+    #[serde(default)]
+    pub time_lock_puzzle_params: Option<TimeLockPuzzleParams>,
 }
 
 impl Rollup {
@@ -62,12 +83,46 @@ impl Rollup {
             platform,
             service_provider,
             max_gas_limit: 0,
+            chain_id: None,
+            nonce_policy: NoncePolicy::default(),
+            signature_scheme: SignatureScheme::default(),
+            merkle_hash_scheme: MerkleHashScheme::default(),
+            time_lock_puzzle_params: None,
         }
     }
 
     pub fn set_executor_address_list(&mut self, executor_address_list: Vec<Address>) {
         self.executor_address_list = executor_address_list;
     }
+
+    /// Returns the signature scheme of the block at `rollup_block_height`,
+    /// which is the one staged in [`RollupMetadata`] when the block was
+    /// opened. Unknown blocks fall back to the scheme of the rollup.
+    pub fn get_signature_scheme(
+        &self,
+        rollup_block_height: u64,
+    ) -> Result<SignatureScheme, KvStoreError> {
+        match RollupMetadata::get(&self.rollup_id) {
+            Ok(rollup_metadata) if rollup_metadata.rollup_block_height == rollup_block_height => {
+                return Ok(rollup_metadata.signature_scheme);
+            }
+            Ok(_) => {}
+            Err(error) if error.is_none_type() => {}
+            Err(error) => return Err(error),
+        }
+
+        match BlockBuildJob::get(&self.rollup_id, rollup_block_height) {
+            Ok(block_build_job) => return Ok(block_build_job.signature_scheme),
+            Err(error) if error.is_none_type() => {}
+            Err(error) => return Err(error),
+        }
+
+        match Block::get(&self.rollup_id, rollup_block_height) {
+            Ok(block) => Ok(block.signature_scheme),
+            Err(error) if error.is_none_type() => Ok(self.signature_scheme.clone()),
+            Err(error) => Err(error),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
@@ -1,7 +1,11 @@
 use radius_sdk::kvstore::Model;
 use serde::{Deserialize, Serialize};
 
-use crate::client::liveness::seeder::SequencerRpcInfo;
+use crate::{
+    client::liveness::seeder::SequencerRpcInfo,
+    error::Error,
+    types::{MerkleHashScheme, MerkleRootVersion, SignatureScheme},
+};
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
 #[kvstore(key(rollup_id: &str))]
@@ -14,4 +18,87 @@ pub struct RollupMetadata {
     pub leader_sequencer_rpc_info: SequencerRpcInfo,
     pub max_gas_limit: u64,
     pub current_gas: u64,
+
+    /// Merkle hash scheme of the open block.
+    #[serde(default)]
+    pub merkle_hash_scheme: MerkleHashScheme,
+
+    /// Signature scheme of the open block.
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
+
+    /// Merkle root version of the open block.
+    #[serde(default = "MerkleRootVersion::unversioned")]
+    pub merkle_root_version: MerkleRootVersion,
+}
+
+impl RollupMetadata {
+    /// Takes `transaction_count` consecutive orders of the open block for
+    /// transactions of `gas_limit` in total and returns the first one. Nothing
+    /// is taken if they would exceed `max_gas_limit`.
+    pub fn take_transaction_order(
+        &mut self,
+        transaction_count: u64,
+        gas_limit: u64,
+    ) -> Result<u64, Error> {
+        let current_gas = self.current_gas.saturating_add(gas_limit);
+        if self.max_gas_limit != 0 && current_gas > self.max_gas_limit {
+            return Err(Error::ExceedMaxGasLimit);
+        }
+
+        let first_transaction_order = self.transaction_order;
+        self.transaction_order += transaction_count;
+        self.current_gas = current_gas;
+
+        Ok(first_transaction_order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_take_consecutive_orders() {
+        let mut rollup_metadata = RollupMetadata {
+            transaction_order: 4,
+            ..RollupMetadata::default()
+        };
+
+        assert_eq!(rollup_metadata.take_transaction_order(3, 30).unwrap(), 4);
+        assert_eq!(rollup_metadata.take_transaction_order(2, 20).unwrap(), 7);
+        assert_eq!(rollup_metadata.transaction_order, 9);
+        assert_eq!(rollup_metadata.current_gas, 50);
+    }
+
+    #[test]
+    fn batch_over_the_gas_limit_takes_nothing() {
+        let mut rollup_metadata = RollupMetadata {
+            transaction_order: 4,
+            max_gas_limit: 100,
+            current_gas: 60,
+            ..RollupMetadata::default()
+        };
+
+        assert!(matches!(
+            rollup_metadata.take_transaction_order(3, 41),
+            Err(Error::ExceedMaxGasLimit)
+        ));
+        assert_eq!(rollup_metadata.transaction_order, 4);
+        assert_eq!(rollup_metadata.current_gas, 60);
+
+        assert_eq!(rollup_metadata.take_transaction_order(3, 40).unwrap(), 4);
+        assert_eq!(rollup_metadata.current_gas, 100);
+    }
+
+    #[test]
+    fn zero_max_gas_limit_is_unlimited() {
+        let mut rollup_metadata = RollupMetadata {
+            current_gas: u64::MAX - 1,
+            ..RollupMetadata::default()
+        };
+
+        assert_eq!(rollup_metadata.take_transaction_order(1, 10).unwrap(), 0);
+        assert_eq!(rollup_metadata.current_gas, u64::MAX);
+    }
 }
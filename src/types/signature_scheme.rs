@@ -0,0 +1,428 @@
+use std::str::FromStr;
+
+use ethers_core::{
+    k256::ecdsa::SigningKey,
+    types::{Signature as EthSignature, H256},
+};
+use radius_sdk::signature::{ChainType, PrivateKeySigner};
+use sha3::{Digest, Keccak256};
+
+use super::prelude::*;
+use crate::error::Error;
+
+pub const EIP712_DOMAIN_NAME: &str = "Radius Sequencer";
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// How the sequencers of a rollup sign order commitments and block
+/// commitments, and how the executor signs `finalize_block` messages.
+///
+/// Every sequencer of the cluster has to use the same scheme, so it is only
+/// changed through `set_signature_scheme`, which syncs it to the cluster.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// Signs the serde-JSON encoding of the message.
+    #[default]
+    Json,
+    /// Signs the EIP-712 typed-data hash of the message, which Solidity
+    /// contracts and hardware wallets can verify.
+    Eip712(Eip712Domain),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Eip712Domain {
+    pub chain_id: u64,
+    #[serde(serialize_with = "serialize_address")]
+    pub verifying_contract: Address,
+}
+
+impl Eip712Domain {
+    pub fn separator(&self) -> Result<[u8; 32], Error> {
+        let mut encoded_data = keccak256(EIP712_DOMAIN_TYPE.as_bytes()).to_vec();
+        encoded_data.extend(encode_string(EIP712_DOMAIN_NAME));
+        encoded_data.extend(encode_string(EIP712_DOMAIN_VERSION));
+        encoded_data.extend(encode_uint(self.chain_id));
+        encoded_data.extend(encode_address(&self.verifying_contract)?);
+
+        Ok(keccak256(&encoded_data))
+    }
+
+    /// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`
+    pub fn hash_message<T: Eip712Message>(&self, message: &T) -> Result<[u8; 32], Error> {
+        let mut encoded_data = vec![0x19, 0x01];
+        encoded_data.extend(self.separator()?);
+        encoded_data.extend(message.struct_hash()?);
+
+        Ok(keccak256(&encoded_data))
+    }
+
+    pub fn sign<T: Eip712Message>(
+        &self,
+        signing_key: &str,
+        message: &T,
+    ) -> Result<Signature, Error> {
+        let signing_key = const_hex::decode(signing_key.trim())
+            .ok()
+            .and_then(|signing_key| SigningKey::from_slice(&signing_key).ok())
+            .ok_or(Error::SignerNotFound)?;
+
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(&self.hash_message(message)?)
+            .map_err(|_| Error::InvalidSignature)?;
+
+        let mut signature = signature.to_bytes().to_vec();
+        signature.push(recovery_id.to_byte() + 27);
+
+        serde_json::from_value(serde_json::Value::String(const_hex::encode_prefixed(
+            signature,
+        )))
+        .map_err(|_| Error::InvalidSignature)
+    }
+
+    pub fn recover<T: Eip712Message>(
+        &self,
+        chain_type: ChainType,
+        signature: &Signature,
+        message: &T,
+    ) -> Result<Address, Error> {
+        let recovered_address = EthSignature::from_str(&signature.as_hex_string())
+            .map_err(|_| Error::InvalidSignature)?
+            .recover(H256::from(self.hash_message(message)?))
+            .map_err(|_| Error::InvalidSignature)?;
+
+        Address::from_str(chain_type, &format!("0x{:x}", recovered_address))
+            .map_err(|_| Error::InvalidSignature)
+    }
+}
+
+impl SignatureScheme {
+    /// `signing_key` is the key `signer` was created from. The EIP-712 hash
+    /// is signed as is, without the personal message prefix `signer` adds.
+    pub fn sign<T: Eip712Message + Serialize>(
+        &self,
+        signer: &PrivateKeySigner,
+        signing_key: &str,
+        message: &T,
+    ) -> Result<Signature, Error> {
+        match self {
+            Self::Json => signer.sign_message(message).map_err(Error::Signature),
+            Self::Eip712(domain) => domain.sign(signing_key, message),
+        }
+    }
+
+    pub fn verify<T: Eip712Message + Serialize>(
+        &self,
+        chain_type: ChainType,
+        signature: &Signature,
+        message: &T,
+        address: &Address,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Json => signature
+                .verify_message(chain_type, message, address)
+                .map_err(Error::Signature),
+            Self::Eip712(domain) => {
+                if domain.recover(chain_type, signature, message)? != *address {
+                    return Err(Error::InvalidSignature);
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Struct hashing of a message signed with [`SignatureScheme::Eip712`].
+pub trait Eip712Message {
+    /// Encoded type, e.g. `Mail(address from,address to,string contents)`.
+    const TYPE: &'static str;
+
+    /// Encoded members in the order of [`Self::TYPE`], 32 bytes each.
+    fn encode_data(&self) -> Result<Vec<u8>, Error>;
+
+    fn struct_hash(&self) -> Result<[u8; 32], Error> {
+        let mut encoded_data = keccak256(Self::TYPE.as_bytes()).to_vec();
+        encoded_data.extend(self.encode_data()?);
+
+        Ok(keccak256(&encoded_data))
+    }
+}
+
+/// Block commitment signed by the sequencer that built the block.
+///
+/// The JSON scheme signs the bare block commitment, so only the block
+/// commitment is serialized.
+#[derive(Clone, Debug)]
+pub struct BlockCommitmentMessage<'a> {
+    pub rollup_id: &'a str,
+    pub rollup_block_height: u64,
+    pub block_commitment: [u8; 32],
+}
+
+impl Serialize for BlockCommitmentMessage<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.block_commitment.serialize(serializer)
+    }
+}
+
+impl Eip712Message for BlockCommitmentMessage<'_> {
+    const TYPE: &'static str =
+        "BlockCommitment(string rollupId,uint64 rollupBlockHeight,bytes32 blockCommitment)";
+
+    fn encode_data(&self) -> Result<Vec<u8>, Error> {
+        let mut encoded_data = encode_string(self.rollup_id).to_vec();
+        encoded_data.extend(encode_uint(self.rollup_block_height));
+        encoded_data.extend(self.block_commitment);
+
+        Ok(encoded_data)
+    }
+}
+
+/// Order commitments issued for a transaction batch, signed as a whole.
+///
+/// The JSON scheme signs the bare order commitment list.
+#[derive(Clone, Debug)]
+pub struct BundleOrderCommitmentMessage<'a> {
+    pub rollup_id: &'a str,
+    pub rollup_block_height: u64,
+    pub first_transaction_order: u64,
+    pub order_commitment_list: &'a [SingleOrderCommitment],
+}
+
+impl Serialize for BundleOrderCommitmentMessage<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.order_commitment_list.serialize(serializer)
+    }
+}
+
+impl Eip712Message for BundleOrderCommitmentMessage<'_> {
+    const TYPE: &'static str = "BundleOrderCommitment(string rollupId,uint64 rollupBlockHeight,uint64 firstTransactionOrder,string[] transactionHashList)";
+
+    fn encode_data(&self) -> Result<Vec<u8>, Error> {
+        let mut encoded_data = encode_string(self.rollup_id).to_vec();
+        encoded_data.extend(encode_uint(self.rollup_block_height));
+        encoded_data.extend(encode_uint(self.first_transaction_order));
+        encoded_data.extend(keccak256(
+            &self
+                .order_commitment_list
+                .iter()
+                .map(|order_commitment| encode_string(order_commitment.transaction_hash()))
+                .collect::<Vec<[u8; 32]>>()
+                .concat(),
+        ));
+
+        Ok(encoded_data)
+    }
+}
+
+/// Encoded type of an order commitment carrying the key id of an SKDE
+/// transaction.
+const KEY_ID_ORDER_COMMITMENT_TYPE: &str = "OrderCommitment(string rollupId,uint64 blockHeight,uint64 transactionOrder,string transactionHash,bytes32[] preMerklePath,uint8 merkleHashScheme,uint64 keyId)";
+
+/// `merkleHashScheme` is `0` for Keccak and `1` for Poseidon.
+impl Eip712Message for OrderCommitmentData {
+    const TYPE: &'static str = "OrderCommitment(string rollupId,uint64 blockHeight,uint64 transactionOrder,string transactionHash,bytes32[] preMerklePath,uint8 merkleHashScheme)";
+
+    fn encode_data(&self) -> Result<Vec<u8>, Error> {
+        let mut encoded_data = encode_string(&self.rollup_id).to_vec();
+        encoded_data.extend(encode_uint(self.block_height));
+        encoded_data.extend(encode_uint(self.transaction_order));
+        encoded_data.extend(encode_string(&self.transaction_hash));
+        encoded_data.extend(keccak256(&self.pre_merkle_path.concat()));
+        encoded_data.extend(encode_uint(match self.merkle_hash_scheme {
+            MerkleHashScheme::Keccak => 0,
+            MerkleHashScheme::Poseidon => 1,
+        }));
+        if let Some(key_id) = self.key_id {
+            encoded_data.extend(encode_uint(key_id));
+        }
+
+        Ok(encoded_data)
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Error> {
+        let encoded_type = match self.key_id {
+            Some(_) => KEY_ID_ORDER_COMMITMENT_TYPE,
+            None => Self::TYPE,
+        };
+
+        let mut encoded_data = keccak256(encoded_type.as_bytes()).to_vec();
+        encoded_data.extend(self.encode_data()?);
+
+        Ok(keccak256(&encoded_data))
+    }
+}
+
+pub fn encode_string(value: &str) -> [u8; 32] {
+    keccak256(value.as_bytes())
+}
+
+pub fn encode_uint(value: u64) -> [u8; 32] {
+    let mut encoded_value = [0u8; 32];
+    encoded_value[24..].copy_from_slice(&value.to_be_bytes());
+    encoded_value
+}
+
+pub fn encode_address(address: &Address) -> Result<[u8; 32], Error> {
+    let address = const_hex::decode(address.as_hex_string()).map_err(|_| Error::Convert)?;
+    if address.len() != 20 {
+        return Err(Error::Convert);
+    }
+
+    let mut encoded_address = [0u8; 32];
+    encoded_address[12..].copy_from_slice(&address);
+    Ok(encoded_address)
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Keccak256::digest(data));
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const SIGNER_ADDRESS: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+    fn domain(chain_id: u64) -> Eip712Domain {
+        Eip712Domain {
+            chain_id,
+            verifying_contract: Address::from_slice(ChainType::Ethereum, &[0xcc; 20]).unwrap(),
+        }
+    }
+
+    fn order_commitment_data() -> OrderCommitmentData {
+        OrderCommitmentData {
+            rollup_id: "rollup_id".to_owned(),
+            block_height: 7,
+            transaction_order: 3,
+            transaction_hash: RawTransactionHash::from([1u8; 32]).as_string(),
+            pre_merkle_path: vec![[2u8; 32], [3u8; 32]],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn domain_type_hash_matches_the_standard() {
+        assert_eq!(
+            const_hex::encode_prefixed(keccak256(EIP712_DOMAIN_TYPE.as_bytes())),
+            "0x8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f"
+        );
+    }
+
+    #[test]
+    fn values_are_encoded_into_32_bytes() {
+        let mut encoded_uint = [0u8; 32];
+        encoded_uint[30..].copy_from_slice(&[0x01, 0x02]);
+        let mut encoded_address = [0u8; 32];
+        encoded_address[12..].copy_from_slice(&[0xcc; 20]);
+
+        assert_eq!(encode_uint(0x0102), encoded_uint);
+        assert_eq!(encode_string("rollup_id"), keccak256(b"rollup_id"));
+        assert_eq!(
+            encode_address(&domain(1).verifying_contract).unwrap(),
+            encoded_address
+        );
+    }
+
+    #[test]
+    fn block_commitment_struct_hash() {
+        let message = BlockCommitmentMessage {
+            rollup_id: "rollup_id",
+            rollup_block_height: 7,
+            block_commitment: [4u8; 32],
+        };
+
+        let mut encoded_data = keccak256(BlockCommitmentMessage::TYPE.as_bytes()).to_vec();
+        encoded_data.extend(keccak256(b"rollup_id"));
+        encoded_data.extend(encode_uint(7));
+        encoded_data.extend([4u8; 32]);
+
+        assert_eq!(message.struct_hash().unwrap(), keccak256(&encoded_data));
+    }
+
+    #[test]
+    fn key_id_changes_the_order_commitment_type() {
+        let order_commitment_data = order_commitment_data();
+        let key_id_order_commitment_data = OrderCommitmentData {
+            key_id: Some(0),
+            ..order_commitment_data.clone()
+        };
+
+        assert_ne!(
+            order_commitment_data.struct_hash().unwrap(),
+            key_id_order_commitment_data.struct_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn signer_is_recovered() {
+        let domain = domain(1);
+        let order_commitment_data = order_commitment_data();
+        let signer_address = Address::from_str(ChainType::Ethereum, SIGNER_ADDRESS).unwrap();
+
+        let signature = domain.sign(SIGNING_KEY, &order_commitment_data).unwrap();
+
+        assert_eq!(
+            domain
+                .recover(ChainType::Ethereum, &signature, &order_commitment_data)
+                .unwrap(),
+            signer_address
+        );
+        assert!(SignatureScheme::Eip712(domain)
+            .verify(
+                ChainType::Ethereum,
+                &signature,
+                &order_commitment_data,
+                &signer_address
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn signature_is_bound_to_the_message_and_the_domain() {
+        let order_commitment_data = order_commitment_data();
+        let signer_address = Address::from_str(ChainType::Ethereum, SIGNER_ADDRESS).unwrap();
+        let signature = domain(1).sign(SIGNING_KEY, &order_commitment_data).unwrap();
+
+        let other_order_commitment_data = OrderCommitmentData {
+            transaction_order: 4,
+            ..order_commitment_data.clone()
+        };
+        let other_message = SignatureScheme::Eip712(domain(1)).verify(
+            ChainType::Ethereum,
+            &signature,
+            &other_order_commitment_data,
+            &signer_address,
+        );
+        let other_domain = SignatureScheme::Eip712(domain(2)).verify(
+            ChainType::Ethereum,
+            &signature,
+            &order_commitment_data,
+            &signer_address,
+        );
+
+        assert!(other_message.is_err());
+        assert!(other_domain.is_err());
+    }
+
+    #[test]
+    fn invalid_signing_key_is_rejected() {
+        let signature = domain(1).sign("0x1234", &order_commitment_data());
+
+        assert!(matches!(signature, Err(Error::SignerNotFound)));
+    }
+}
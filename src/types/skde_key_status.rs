@@ -0,0 +1,70 @@
+use super::prelude::*;
+use crate::error::Error;
+
+/// Key ids of the distributed key generation, tracked by `track_skde_key`.
+///
+/// Decryption keys are released in key id order, so every key id below
+/// `first_unexpired_key_id` can already be decrypted by anyone and a
+/// transaction encrypted under it is not time-locked anymore.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key())]
+pub struct SkdeKeyStatus {
+    pub latest_key_id: u64,
+    pub first_unexpired_key_id: u64,
+}
+
+impl SkdeKeyStatus {
+    /// Checks that a transaction encrypted under `key_id` cannot be
+    /// decrypted yet. Nothing is accepted before the key ids are tracked.
+    pub fn check_key_id(key_id: u64) -> Result<(), Error> {
+        let skde_key_status = match Self::get() {
+            Ok(skde_key_status) => skde_key_status,
+            Err(error) if error.is_none_type() => return Err(Error::UnknownKeyId),
+            Err(error) => return Err(Error::Database(error)),
+        };
+
+        if key_id < skde_key_status.first_unexpired_key_id {
+            return Err(Error::ExpiredKeyId);
+        }
+        if key_id > skde_key_status.latest_key_id {
+            return Err(Error::UnknownKeyId);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    // The key status is a single entry, so the cases run in order in one test.
+    #[test]
+    fn check_key_id_accepts_only_the_unexpired_tracked_key_ids() {
+        init_test_database();
+
+        assert!(matches!(
+            SkdeKeyStatus::check_key_id(0),
+            Err(Error::UnknownKeyId)
+        ));
+
+        SkdeKeyStatus {
+            latest_key_id: 5,
+            first_unexpired_key_id: 3,
+        }
+        .put()
+        .unwrap();
+
+        assert!(matches!(
+            SkdeKeyStatus::check_key_id(2),
+            Err(Error::ExpiredKeyId)
+        ));
+        assert!(SkdeKeyStatus::check_key_id(3).is_ok());
+        assert!(SkdeKeyStatus::check_key_id(5).is_ok());
+        assert!(matches!(
+            SkdeKeyStatus::check_key_id(6),
+            Err(Error::UnknownKeyId)
+        ));
+    }
+}
@@ -11,36 +11,139 @@ pub struct EthBundleTransactionData {
 }
 
 impl EthBundleTransactionData {
-    pub fn convert_to_rollup_transaction(&self) -> Result<RollupTransaction, Error> {
-        if self.plain_data.is_none() {
-            return Err(Error::PlainDataDoesNotExist);
+    pub fn new(encrypted_data: EncryptedData, open_data: EthBundleOpenData) -> Self {
+        Self {
+            encrypted_data,
+            open_data,
+            plain_data: None,
         }
+    }
+
+    pub fn convert_to_rollup_transaction(&self) -> Result<RollupTransaction, Error> {
+        let plain_data = self
+            .plain_data
+            .as_ref()
+            .ok_or(Error::PlainDataDoesNotExist)?;
 
-        // TODO:
-        // let rollup_transaction = self
-        // .open_data
-        // .convert_to_rollup_transaction(self.plain_data.as_ref().unwrap());
+        Ok(RollupTransaction::EthBundle(
+            self.open_data
+                .convert_to_rollup_transaction_list(plain_data)?,
+        ))
+    }
+
+    pub fn update_plain_data(&mut self, plain_data: EthBundlePlainData) {
+        self.plain_data = Some(plain_data);
+    }
 
-        Ok(RollupTransaction::EthBundle)
+    pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
+        self.open_data.get_transaction_gas_limit()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct EthBundleOpenData {
+    /// Hash of the signed bundle, computed as in
+    /// [`EthRawBundleTransaction::raw_transaction_hash`]: the keccak256 of the
+    /// concatenated 32-byte transaction hashes in execution order, or the
+    /// transaction hash itself for a bundle of one transaction.
     pub raw_tx_hash: RawTransactionHash,
+
+    /// Open data of each transaction in the bundle, in execution order.
+    pub open_data_list: Vec<EthOpenData>,
 }
 
 impl EthBundleOpenData {
     pub fn raw_tx_hash(&self) -> &RawTransactionHash {
         &self.raw_tx_hash
     }
+
+    /// Returns the sum of the gas limits of the transactions in the bundle.
+    pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
+        self.open_data_list
+            .iter()
+            .try_fold(0u64, |gas_limit, open_data| {
+                gas_limit.checked_add(open_data.gas_limit.try_into().ok()?)
+            })
+            .ok_or(Error::InvalidTransaction)
+    }
+
+    pub fn convert_to_rollup_transaction_list(
+        &self,
+        plain_data: &EthBundlePlainData,
+    ) -> Result<Vec<eth_types::Transaction>, Error> {
+        if self.open_data_list.len() != plain_data.0.len() {
+            tracing::error!(
+                "Bundle open data and plain data length mismatch: {:?} != {:?}",
+                self.open_data_list.len(),
+                plain_data.0.len()
+            );
+            return Err(Error::InvalidTransaction);
+        }
+
+        Ok(self
+            .open_data_list
+            .iter()
+            .zip(plain_data.0.iter())
+            .map(|(open_data, plain_data)| open_data.convert_to_rollup_transaction(plain_data))
+            .collect())
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct EthBundlePlainData {
-    pub to: Option<eth_types::Address>,
-    pub value: eth_types::U256,
+/// Decrypted payload of a bundle, one entry per transaction in
+/// [`EthBundleOpenData::open_data_list`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EthBundlePlainData(pub Vec<EthPlainData>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[serde(rename = "data")]
-    pub input: eth_types::Bytes,
+    fn eth_bundle_open_data(gas_limit_list: &[eth_types::U256]) -> EthBundleOpenData {
+        EthBundleOpenData {
+            raw_tx_hash: RawTransactionHash::default(),
+            open_data_list: gas_limit_list
+                .iter()
+                .map(|gas_limit| {
+                    EthOpenData::from(eth_types::Transaction {
+                        gas: *gas_limit,
+                        ..Default::default()
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn bundle_gas_limit_is_summed() {
+        let eth_bundle_open_data = eth_bundle_open_data(&[21_000.into(), 50_000.into()]);
+
+        assert_eq!(
+            eth_bundle_open_data.get_transaction_gas_limit().unwrap(),
+            71_000
+        );
+    }
+
+    #[test]
+    fn bundle_gas_limit_overflow_is_rejected() {
+        for gas_limit_list in [
+            [eth_types::U256::from(u64::MAX), eth_types::U256::one()],
+            [eth_types::U256::MAX, eth_types::U256::zero()],
+        ] {
+            assert!(matches!(
+                eth_bundle_open_data(&gas_limit_list).get_transaction_gas_limit(),
+                Err(Error::InvalidTransaction)
+            ));
+        }
+    }
+
+    #[test]
+    fn plain_data_of_another_length_is_rejected() {
+        let eth_bundle_open_data = eth_bundle_open_data(&[21_000.into(), 21_000.into()]);
+
+        assert!(matches!(
+            eth_bundle_open_data
+                .convert_to_rollup_transaction_list(&EthBundlePlainData(Vec::new())),
+            Err(Error::InvalidTransaction)
+        ));
+    }
 }
@@ -64,6 +64,19 @@ pub struct EthOpenData {
     pub other: eth_types::OtherFields,
 }
 
+impl EthOpenData {
+    /// Sender and nonce the open data claims, which are only authenticated
+    /// once the transaction is decrypted.
+    pub fn sender_nonce(&self) -> Result<(eth_types::Address, u64), Error> {
+        let nonce = self
+            .nonce
+            .try_into()
+            .map_err(|_| Error::InvalidTransaction)?;
+
+        Ok((self.from, nonce))
+    }
+}
+
 impl From<eth_types::Transaction> for EthOpenData {
     fn from(transaction: eth_types::Transaction) -> Self {
         Self {
@@ -27,40 +27,54 @@ impl EncryptedTransactionList {
 #[serde(tag = "type", content = "data")]
 #[serde(rename_all = "snake_case")]
 pub enum EncryptedTransaction {
+    Pvde(PvdeEncryptedTransaction),
     Skde(SkdeEncryptedTransaction),
 }
 
 impl EncryptedTransaction {
+    pub fn try_into_pvde_transaction(self) -> Result<PvdeEncryptedTransaction, Error> {
+        match self {
+            EncryptedTransaction::Pvde(pvde_transaction) => Ok(pvde_transaction),
+            _ => Err(Error::UnsupportedEncryptedMempool),
+        }
+    }
+
     pub fn try_into_skde_transaction(self) -> Result<SkdeEncryptedTransaction, Error> {
         match self {
             EncryptedTransaction::Skde(skde_transaction) => Ok(skde_transaction),
+            _ => Err(Error::UnsupportedEncryptedMempool),
         }
     }
 }
 
 impl EncryptedTransaction {
     pub fn raw_transaction_hash(&self) -> RawTransactionHash {
-        match self {
-            Self::Skde(skde_encrypted_transaction) => {
-                return skde_encrypted_transaction
-                    .transaction_data
-                    .raw_transaction_hash();
-            }
-        }
+        self.transaction_data().raw_transaction_hash()
     }
 
     pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
+        self.transaction_data().get_transaction_gas_limit()
+    }
+
+    /// `(sender, nonce)` of each transaction, from the open data.
+    pub fn sender_nonce_list(&self) -> Result<Vec<(eth_types::Address, u64)>, Error> {
+        self.transaction_data().sender_nonce_list()
+    }
+
+    /// Key id of the distributed key generation an SKDE transaction is
+    /// encrypted under.
+    pub fn key_id(&self) -> Option<u64> {
         match self {
-            Self::Skde(skde_encrypted_transaction) => {
-                return skde_encrypted_transaction
-                    .transaction_data
-                    .get_transaction_gas_limit();
-            }
+            Self::Pvde(_) => None,
+            Self::Skde(skde_encrypted_transaction) => Some(skde_encrypted_transaction.key_id),
         }
     }
 
     pub fn update_transaction_data(&mut self, transaction_data: TransactionData) {
         match self {
+            Self::Pvde(pvde) => {
+                pvde.transaction_data = transaction_data;
+            }
             Self::Skde(skde) => {
                 skde.transaction_data = transaction_data;
             }
@@ -69,19 +83,70 @@ impl EncryptedTransaction {
 
     pub fn transaction_data(&self) -> &TransactionData {
         match self {
+            Self::Pvde(pvde_encrypted_transaction) => &pvde_encrypted_transaction.transaction_data,
             Self::Skde(skde_encrypted_transaction) => &skde_encrypted_transaction.transaction_data,
         }
     }
 
     pub fn encrypted_data(&self) -> &EncryptedData {
-        match self {
-            Self::Skde(skde_encrypted_transaction) => {
-                &skde_encrypted_transaction.transaction_data.encrypted_data()
-            }
+        self.transaction_data().encrypted_data()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PvdeEncryptedTransaction {
+    pub transaction_data: TransactionData,
+    pub time_lock_puzzle: TimeLockPuzzle,
+}
+
+impl PvdeEncryptedTransaction {
+    pub fn new(transaction_data: TransactionData, time_lock_puzzle: TimeLockPuzzle) -> Self {
+        Self {
+            transaction_data,
+            time_lock_puzzle,
         }
     }
 }
 
+/// The time-lock puzzle a PVDE transaction is encrypted under.
+///
+/// The symmetric key `k = o^(2^t) mod n` can only be obtained by `t`
+/// sequential squarings, and the Poseidon hash of `k` is committed in
+/// `k_hash_value` so that the solved key can be checked before decryption.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TimeLockPuzzle {
+    pub t: u32,
+    pub o: String,
+    pub n: String,
+    pub k_hash_value: String,
+}
+
+impl TimeLockPuzzle {
+    /// Checks that the puzzle is set up with the parameters of the rollup.
+    /// `t` is the number of squarings the block builder runs to solve it, so
+    /// a puzzle that is not bound by the rollup could stall the block.
+    pub fn check_params(
+        &self,
+        time_lock_puzzle_params: Option<&TimeLockPuzzleParams>,
+    ) -> Result<(), Error> {
+        let time_lock_puzzle_params =
+            time_lock_puzzle_params.ok_or(Error::EmptyTimeLockPuzzleParams)?;
+
+        if self.t != time_lock_puzzle_params.t || self.n != time_lock_puzzle_params.n {
+            return Err(Error::InvalidTimeLockPuzzle);
+        }
+
+        Ok(())
+    }
+}
+
+/// Time-lock puzzle parameters a rollup accepts PVDE transactions under.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TimeLockPuzzleParams {
+    pub t: u32,
+    pub n: String,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SkdeEncryptedTransaction {
     pub transaction_data: TransactionData,
@@ -121,7 +186,19 @@ impl TransactionData {
     pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
         match self {
             Self::Eth(data) => data.get_transaction_gas_limit(),
-            Self::EthBundle(_data) => todo!("eth_bundle max_gas_limit"),
+            Self::EthBundle(data) => data.get_transaction_gas_limit(),
+        }
+    }
+
+    pub fn sender_nonce_list(&self) -> Result<Vec<(eth_types::Address, u64)>, Error> {
+        match self {
+            Self::Eth(data) => Ok(vec![data.open_data.sender_nonce()?]),
+            Self::EthBundle(data) => data
+                .open_data
+                .open_data_list
+                .iter()
+                .map(EthOpenData::sender_nonce)
+                .collect(),
         }
     }
 
@@ -132,15 +209,16 @@ impl TransactionData {
         }
     }
 
-    pub fn update_plain_data(&mut self, plain_data: EthPlainData) {
-        match self {
-            Self::Eth(data) => {
-                data.plain_data = Some(plain_data);
-            }
-            Self::EthBundle(_data) => {
-                unimplemented!()
+    pub fn update_plain_data(&mut self, plain_data: PlainData) -> Result<(), Error> {
+        match (self, plain_data) {
+            (Self::Eth(data), PlainData::Eth(plain_data)) => data.update_plain_data(plain_data),
+            (Self::EthBundle(data), PlainData::EthBundle(plain_data)) => {
+                data.update_plain_data(plain_data)
             }
+            _ => return Err(Error::InvalidTransaction),
         }
+
+        Ok(())
     }
 
     pub fn encrypted_data(&self) -> &EncryptedData {
@@ -184,7 +262,7 @@ impl From<EthBundlePlainData> for PlainData {
 #[serde(rename_all = "snake_case")]
 pub enum RollupTransaction {
     Eth(eth_types::Transaction),
-    EthBundle,
+    EthBundle(Vec<eth_types::Transaction>),
 }
 
 impl RollupTransaction {
@@ -198,10 +276,16 @@ impl RollupTransaction {
                     raw_transaction_string,
                 )))
             }
-            // Todo: implement EthBundle
-            Self::EthBundle => Ok(RawTransaction::EthBundle(EthRawBundleTransaction::from(
-                String::new(),
-            ))),
+            Self::EthBundle(transaction_list) => {
+                let eth_raw_transaction_list = transaction_list
+                    .iter()
+                    .map(|transaction| EthRawTransaction::from(to_raw_tx(transaction.clone())))
+                    .collect::<Vec<EthRawTransaction>>();
+
+                Ok(RawTransaction::EthBundle(EthRawBundleTransaction::from(
+                    eth_raw_transaction_list,
+                )))
+            }
         }
     }
 }
@@ -234,3 +318,54 @@ impl From<String> for EncryptedData {
         Self(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_lock_puzzle(t: u32, n: &str) -> TimeLockPuzzle {
+        TimeLockPuzzle {
+            t,
+            o: "2".to_owned(),
+            n: n.to_owned(),
+            k_hash_value: String::new(),
+        }
+    }
+
+    #[test]
+    fn time_lock_puzzle_of_the_rollup_params_is_accepted() {
+        let time_lock_puzzle_params = TimeLockPuzzleParams {
+            t: 4,
+            n: "35".to_owned(),
+        };
+
+        assert!(time_lock_puzzle(4, "35")
+            .check_params(Some(&time_lock_puzzle_params))
+            .is_ok());
+    }
+
+    #[test]
+    fn time_lock_puzzle_of_other_params_is_rejected() {
+        let time_lock_puzzle_params = TimeLockPuzzleParams {
+            t: 4,
+            n: "35".to_owned(),
+        };
+
+        assert!(matches!(
+            time_lock_puzzle(1_000_000, "35").check_params(Some(&time_lock_puzzle_params)),
+            Err(Error::InvalidTimeLockPuzzle)
+        ));
+        assert!(matches!(
+            time_lock_puzzle(4, "33").check_params(Some(&time_lock_puzzle_params)),
+            Err(Error::InvalidTimeLockPuzzle)
+        ));
+    }
+
+    #[test]
+    fn time_lock_puzzle_without_rollup_params_is_rejected() {
+        assert!(matches!(
+            time_lock_puzzle(4, "35").check_params(None),
+            Err(Error::EmptyTimeLockPuzzleParams)
+        ));
+    }
+}
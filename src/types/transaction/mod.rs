@@ -1,9 +1,35 @@
 mod encrypted_transaction;
 mod raw_transaction;
+mod rejected_transaction;
+mod sender_nonce;
+mod transaction_lifecycle;
+mod transaction_order_index;
+mod transaction_order_status;
 
 pub use encrypted_transaction::*;
 pub use raw_transaction::*;
+pub use rejected_transaction::*;
+pub use sender_nonce::*;
 use serde::{Deserialize, Serialize};
+pub use transaction_lifecycle::*;
+pub use transaction_order_index::*;
+pub use transaction_order_status::*;
+
+use crate::error::Error;
+
+/// Returns the sum of the gas limits of a batch. The batch is rejected as a
+/// whole if a gas limit is invalid or the sum overflows.
+pub fn get_batch_gas_limit(
+    gas_limit_list: impl IntoIterator<Item = Result<u64, Error>>,
+) -> Result<u64, Error> {
+    gas_limit_list
+        .into_iter()
+        .try_fold(0u64, |batch_gas_limit, gas_limit| {
+            batch_gas_limit
+                .checked_add(gas_limit?)
+                .ok_or(Error::InvalidTransaction)
+        })
+}
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -28,3 +54,29 @@ impl From<String> for EncryptedTransactionType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_gas_limit_is_summed() {
+        let batch_gas_limit = get_batch_gas_limit([Ok(21_000), Ok(50_000)]).unwrap();
+
+        assert_eq!(batch_gas_limit, 71_000);
+    }
+
+    #[test]
+    fn batch_gas_limit_overflow_is_rejected() {
+        let batch_gas_limit = get_batch_gas_limit([Ok(u64::MAX), Ok(1)]);
+
+        assert!(matches!(batch_gas_limit, Err(Error::InvalidTransaction)));
+    }
+
+    #[test]
+    fn batch_gas_limit_error_is_returned() {
+        let batch_gas_limit = get_batch_gas_limit([Ok(21_000), Err(Error::InvalidTransaction)]);
+
+        assert!(matches!(batch_gas_limit, Err(Error::InvalidTransaction)));
+    }
+}
@@ -1,33 +1,211 @@
 use ethers_core::types as eth_types;
+use sha3::{Digest, Keccak256};
 
 use crate::{error::Error, types::prelude::*};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct EthRawBundleTransaction(pub String);
+/// Signed transactions of a bundle, which are included in a block
+/// contiguously and in the given order.
+///
+/// Serialized as the list of signed transactions. The single signed
+/// transaction a bundle used to be serialized as is still accepted, so that
+/// stored blocks and older clients keep working.
+#[derive(Clone, Debug, Serialize)]
+pub struct EthRawBundleTransaction(pub Vec<EthRawTransaction>);
 
-impl From<String> for EthRawBundleTransaction {
-    fn from(value: String) -> Self {
+impl<'de> Deserialize<'de> for EthRawBundleTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum EthRawBundleTransactionFormat {
+            List(Vec<EthRawTransaction>),
+            Single(EthRawTransaction),
+        }
+
+        match EthRawBundleTransactionFormat::deserialize(deserializer)? {
+            EthRawBundleTransactionFormat::List(eth_raw_transaction_list) => {
+                Ok(Self(eth_raw_transaction_list))
+            }
+            EthRawBundleTransactionFormat::Single(eth_raw_transaction) => {
+                Ok(Self(vec![eth_raw_transaction]))
+            }
+        }
+    }
+}
+
+impl From<Vec<EthRawTransaction>> for EthRawBundleTransaction {
+    fn from(value: Vec<EthRawTransaction>) -> Self {
         Self(value)
     }
 }
 
 impl EthRawBundleTransaction {
-    pub fn raw_transaction_hash(&self) -> RawTransactionHash {
-        let raw_transaction_string = serde_json::to_string(&self.0).unwrap();
-        let parsed_raw_transaction_string: String =
-            serde_json::from_str(&raw_transaction_string).unwrap();
-        let decoded_transaction = decode_rlp_transaction(&parsed_raw_transaction_string).unwrap();
-
-        RawTransactionHash::new(const_hex::encode_prefixed(
-            decoded_transaction.hash.as_bytes(),
-        ))
-    }
-
-    pub fn rollup_transaction(&self) -> Result<eth_types::Transaction, Error> {
-        let raw_transaction_string = serde_json::to_string(&self.0).unwrap();
-        let parsed_raw_transaction_string: String =
-            serde_json::from_str(&raw_transaction_string).unwrap();
-        decode_rlp_transaction(&parsed_raw_transaction_string)
-            .map_err(|_| Error::InvalidTransaction)
+    /// Returns the bundle hash, which clients also set as the `raw_tx_hash` of
+    /// an encrypted bundle.
+    ///
+    /// It is the keccak256 of the concatenated 32-byte hashes of the signed
+    /// transactions in execution order. A bundle of a single transaction
+    /// hashes to the hash of that transaction, as it did when a bundle was a
+    /// single signed transaction.
+    pub fn raw_transaction_hash(&self) -> Result<RawTransactionHash, Error> {
+        let transaction_hash_list = self
+            .rollup_transaction_list()?
+            .into_iter()
+            .map(|transaction| transaction.hash)
+            .collect::<Vec<eth_types::H256>>();
+
+        match transaction_hash_list.as_slice() {
+            [] => Err(Error::EmptyTransactionList),
+            [transaction_hash] => Ok(RawTransactionHash::from(transaction_hash.to_fixed_bytes())),
+            transaction_hash_list => {
+                let mut hasher = Keccak256::new();
+                for transaction_hash in transaction_hash_list {
+                    hasher.update(transaction_hash.as_bytes());
+                }
+
+                Ok(RawTransactionHash::new(hasher.finalize()))
+            }
+        }
+    }
+
+    /// Returns the hashes of the signed transactions in execution order.
+    pub fn transaction_hash_list(&self) -> Result<Vec<RawTransactionHash>, Error> {
+        Ok(self
+            .rollup_transaction_list()?
+            .into_iter()
+            .map(|transaction| RawTransactionHash::from(transaction.hash.to_fixed_bytes()))
+            .collect())
+    }
+
+    pub fn rollup_transaction_list(&self) -> Result<Vec<eth_types::Transaction>, Error> {
+        self.0
+            .iter()
+            .map(|eth_raw_transaction| eth_raw_transaction.rollup_transaction())
+            .collect()
+    }
+
+    pub fn validate(&self, chain_id: Option<u64>) -> Result<Vec<eth_types::Transaction>, Error> {
+        self.0
+            .iter()
+            .map(|eth_raw_transaction| eth_raw_transaction.validate(chain_id))
+            .collect()
+    }
+
+    pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
+        self.rollup_transaction_list()?
+            .iter()
+            .try_fold(0u64, |gas_limit, transaction| {
+                gas_limit.checked_add(transaction.gas.try_into().ok()?)
+            })
+            .ok_or(Error::InvalidTransaction)
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn into_inner(self) -> Vec<EthRawTransaction> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::transaction::raw_transaction::eth_transaction::sign_eth_transaction;
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn eth_raw_bundle_transaction(transaction_count: u64) -> EthRawBundleTransaction {
+        EthRawBundleTransaction::from(
+            (0..transaction_count)
+                .map(|nonce| sign_eth_transaction(SIGNING_KEY, Some(1), nonce))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn bundle_of_one_transaction_hashes_to_the_transaction_hash() {
+        let eth_raw_bundle_transaction = eth_raw_bundle_transaction(1);
+
+        assert_eq!(
+            eth_raw_bundle_transaction.raw_transaction_hash().unwrap(),
+            eth_raw_bundle_transaction.0[0].raw_transaction_hash()
+        );
+    }
+
+    #[test]
+    fn bundle_hash_covers_the_transaction_hashes_in_order() {
+        let eth_raw_bundle_transaction = eth_raw_bundle_transaction(2);
+
+        let mut hasher = Keccak256::new();
+        for eth_raw_transaction in &eth_raw_bundle_transaction.0 {
+            hasher.update(
+                const_hex::decode(eth_raw_transaction.raw_transaction_hash().as_string()).unwrap(),
+            );
+        }
+        let reversed_eth_raw_bundle_transaction = EthRawBundleTransaction::from(
+            eth_raw_bundle_transaction
+                .0
+                .iter()
+                .rev()
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            eth_raw_bundle_transaction.raw_transaction_hash().unwrap(),
+            RawTransactionHash::new(hasher.finalize())
+        );
+        assert_ne!(
+            eth_raw_bundle_transaction.raw_transaction_hash().unwrap(),
+            reversed_eth_raw_bundle_transaction
+                .raw_transaction_hash()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn empty_bundle_has_no_hash() {
+        assert!(matches!(
+            eth_raw_bundle_transaction(0).raw_transaction_hash(),
+            Err(Error::EmptyTransactionList)
+        ));
+    }
+
+    #[test]
+    fn bundle_gas_limit_is_summed() {
+        let eth_raw_bundle_transaction = eth_raw_bundle_transaction(3);
+
+        assert_eq!(
+            eth_raw_bundle_transaction
+                .get_transaction_gas_limit()
+                .unwrap(),
+            63_000
+        );
+        assert_eq!(eth_raw_bundle_transaction.transaction_count(), 3);
+        assert_eq!(
+            eth_raw_bundle_transaction.transaction_hash_list().unwrap(),
+            eth_raw_bundle_transaction
+                .0
+                .iter()
+                .map(EthRawTransaction::raw_transaction_hash)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn single_signed_transaction_is_still_accepted() {
+        let eth_raw_transaction = sign_eth_transaction(SIGNING_KEY, Some(1), 0);
+
+        let eth_raw_bundle_transaction: EthRawBundleTransaction =
+            serde_json::from_value(serde_json::Value::String(eth_raw_transaction.0.clone()))
+                .unwrap();
+
+        assert_eq!(eth_raw_bundle_transaction.transaction_count(), 1);
+        assert_eq!(eth_raw_bundle_transaction.0[0].0, eth_raw_transaction.0);
     }
 }
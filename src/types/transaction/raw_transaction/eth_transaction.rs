@@ -1,4 +1,4 @@
-use ethers_core::types as eth_types;
+use ethers_core::{types as eth_types, utils::rlp};
 
 use crate::{error::Error, types::prelude::*};
 
@@ -29,4 +29,152 @@ impl EthRawTransaction {
     pub fn rollup_transaction(&self) -> Result<eth_types::Transaction, Error> {
         decode_rlp_transaction(&self.0).map_err(|_| Error::InvalidTransaction)
     }
+
+    /// Decodes the signed transaction and recovers its sender into `from`.
+    ///
+    /// Rejects bytes trailing the RLP payload, signatures that do not
+    /// recover, and, if `chain_id` is set, transactions signed for another
+    /// chain or without EIP-155 replay protection.
+    pub fn validate(&self, chain_id: Option<u64>) -> Result<eth_types::Transaction, Error> {
+        let rlp_bytes = const_hex::decode(&self.0).map_err(|_| Error::InvalidTransaction)?;
+        // Typed transactions are prefixed with their type byte.
+        let rlp_payload = match rlp_bytes.first() {
+            Some(transaction_type) if *transaction_type <= 0x7f => &rlp_bytes[1..],
+            Some(_) => &rlp_bytes[..],
+            None => return Err(Error::InvalidTransaction),
+        };
+        let payload_info = rlp::Rlp::new(rlp_payload)
+            .payload_info()
+            .map_err(|_| Error::InvalidTransaction)?;
+        if payload_info.total() != rlp_payload.len() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        let mut transaction = self.rollup_transaction()?;
+        transaction.from = transaction
+            .recover_from()
+            .map_err(|_| Error::InvalidTransactionSignature)?;
+
+        if let Some(chain_id) = chain_id {
+            if transaction.chain_id != Some(chain_id.into()) {
+                return Err(Error::ChainIdMismatch);
+            }
+        }
+
+        Ok(transaction)
+    }
+}
+
+/// Signs a legacy transfer with `signing_key`, with EIP-155 replay protection
+/// if `chain_id` is set.
+#[cfg(test)]
+pub(crate) fn sign_eth_transaction(
+    signing_key: &str,
+    chain_id: Option<u64>,
+    nonce: u64,
+) -> EthRawTransaction {
+    use ethers_core::{k256::ecdsa::SigningKey, types::transaction::eip2718::TypedTransaction};
+
+    let signing_key = SigningKey::from_slice(&const_hex::decode(signing_key).unwrap()).unwrap();
+
+    let mut transaction_request = eth_types::TransactionRequest::new()
+        .to(eth_types::Address::repeat_byte(2))
+        .value(1u64)
+        .gas(21_000u64)
+        .gas_price(1u64)
+        .nonce(nonce);
+    if let Some(chain_id) = chain_id {
+        transaction_request = transaction_request.chain_id(chain_id);
+    }
+    let transaction = TypedTransaction::Legacy(transaction_request);
+
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(transaction.sighash().as_bytes())
+        .unwrap();
+    let signature = eth_types::Signature {
+        r: eth_types::U256::from_big_endian(&signature.r().to_bytes()),
+        s: eth_types::U256::from_big_endian(&signature.s().to_bytes()),
+        v: match chain_id {
+            Some(chain_id) => recovery_id.to_byte() as u64 + 35 + chain_id * 2,
+            None => recovery_id.to_byte() as u64 + 27,
+        },
+    };
+
+    EthRawTransaction::from(const_hex::encode_prefixed(
+        transaction.rlp_signed(&signature),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Well-known development key.
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const SENDER: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+    #[test]
+    fn sender_is_recovered() {
+        let eth_raw_transaction = sign_eth_transaction(SIGNING_KEY, Some(1), 5);
+
+        let transaction = eth_raw_transaction.validate(Some(1)).unwrap();
+
+        assert_eq!(
+            transaction.from,
+            SENDER.parse::<eth_types::Address>().unwrap()
+        );
+        assert_eq!(transaction.nonce, 5.into());
+    }
+
+    #[test]
+    fn transaction_of_another_chain_is_rejected() {
+        let eth_raw_transaction = sign_eth_transaction(SIGNING_KEY, Some(1), 0);
+
+        assert!(matches!(
+            eth_raw_transaction.validate(Some(2)),
+            Err(Error::ChainIdMismatch)
+        ));
+    }
+
+    #[test]
+    fn transaction_without_replay_protection_is_rejected_only_with_a_chain_id() {
+        let eth_raw_transaction = sign_eth_transaction(SIGNING_KEY, None, 0);
+
+        assert!(matches!(
+            eth_raw_transaction.validate(Some(1)),
+            Err(Error::ChainIdMismatch)
+        ));
+        assert!(eth_raw_transaction.validate(None).is_ok());
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let eth_raw_transaction = sign_eth_transaction(SIGNING_KEY, Some(1), 0);
+        let eth_raw_transaction = EthRawTransaction::from(format!("{}00", eth_raw_transaction.0));
+
+        assert!(matches!(
+            eth_raw_transaction.validate(Some(1)),
+            Err(Error::InvalidTransaction)
+        ));
+    }
+
+    #[test]
+    fn malformed_transaction_is_rejected() {
+        for eth_raw_transaction in ["", "0x", "not hex", "0xc0"] {
+            assert!(EthRawTransaction::from(eth_raw_transaction.to_owned())
+                .validate(None)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn tampered_signature_is_rejected() {
+        let mut transaction = sign_eth_transaction(SIGNING_KEY, Some(1), 0)
+            .rollup_transaction()
+            .unwrap();
+        transaction.r = eth_types::U256::zero();
+        let eth_raw_transaction = EthRawTransaction::from(to_raw_tx(transaction));
+
+        assert!(eth_raw_transaction.validate(Some(1)).is_err());
+    }
 }
@@ -1,3 +1,5 @@
+use ethers_core::types as eth_types;
+
 use crate::{
     error::Error,
     types::prelude::{Deserialize, Serialize},
@@ -11,7 +13,7 @@ pub use eth_bundle_transaction::*;
 pub use eth_transaction::*;
 pub use model::*;
 
-#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, Hash, PartialEq)]
 pub struct RawTransactionHash(String);
 
 impl Default for RawTransactionHash {
@@ -85,9 +87,9 @@ impl From<EthRawBundleTransaction> for RawTransaction {
 }
 
 impl RawTransaction {
-    pub fn raw_transaction_hash(&self) -> RawTransactionHash {
+    pub fn raw_transaction_hash(&self) -> Result<RawTransactionHash, Error> {
         match self {
-            RawTransaction::Eth(eth) => eth.raw_transaction_hash(),
+            RawTransaction::Eth(eth) => Ok(eth.raw_transaction_hash()),
             RawTransaction::EthBundle(eth_bundle) => eth_bundle.raw_transaction_hash(),
         }
     }
@@ -95,7 +97,46 @@ impl RawTransaction {
     pub fn get_transaction_gas_limit(&self) -> Result<u64, Error> {
         match self {
             RawTransaction::Eth(eth) => Ok(eth.rollup_transaction()?.gas.as_u64()),
-            RawTransaction::EthBundle(_eth_bundle) => todo!("eth_bundle max_gas_limit"),
+            RawTransaction::EthBundle(eth_bundle) => eth_bundle.get_transaction_gas_limit(),
+        }
+    }
+
+    /// Validates every signed transaction, see [`EthRawTransaction::validate`],
+    /// and returns their `(sender, nonce)` in execution order.
+    pub fn validate(&self, chain_id: Option<u64>) -> Result<Vec<(eth_types::Address, u64)>, Error> {
+        let transaction_list = match self {
+            RawTransaction::Eth(eth) => vec![eth.validate(chain_id)?],
+            RawTransaction::EthBundle(eth_bundle) => eth_bundle.validate(chain_id)?,
+        };
+
+        transaction_list
+            .into_iter()
+            .map(|transaction| {
+                let nonce =
+                    u64::try_from(transaction.nonce).map_err(|_| Error::InvalidTransaction)?;
+                Ok((transaction.from, nonce))
+            })
+            .collect()
+    }
+
+    /// Number of transactions [`Self::expand`] returns.
+    pub fn transaction_count(&self) -> usize {
+        match self {
+            RawTransaction::Eth(_) => 1,
+            RawTransaction::EthBundle(eth_bundle) => eth_bundle.transaction_count(),
+        }
+    }
+
+    /// Expands a bundle into its transactions so that they are included in a
+    /// block contiguously. Other transactions are returned as they are.
+    pub fn expand(self) -> Vec<RawTransaction> {
+        match self {
+            RawTransaction::Eth(eth) => vec![RawTransaction::Eth(eth)],
+            RawTransaction::EthBundle(eth_bundle) => eth_bundle
+                .into_inner()
+                .into_iter()
+                .map(RawTransaction::Eth)
+                .collect(),
         }
     }
 }
@@ -0,0 +1,66 @@
+use crate::{error::Error, types::prelude::*};
+
+/// Reason the leader rejected a transaction it could not order.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, transaction_hash: &str))]
+pub struct RejectedTransaction {
+    pub reason: String,
+}
+
+impl RejectedTransaction {
+    /// Records `error` as the reason of every transaction and returns it.
+    pub fn record(
+        rollup_id: &str,
+        transaction_hash_list: &[RawTransactionHash],
+        error: Error,
+    ) -> Error {
+        let rejected_transaction = Self {
+            reason: error.to_string(),
+        };
+
+        for transaction_hash in transaction_hash_list {
+            if let Err(put_error) = rejected_transaction.put(rollup_id, transaction_hash.as_ref()) {
+                tracing::warn!(
+                    "Failed to record rejected transaction - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+                    rollup_id,
+                    transaction_hash,
+                    put_error
+                );
+            }
+        }
+
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    #[test]
+    fn record_stores_the_reason_of_every_transaction() {
+        init_test_database();
+        let rollup_id = "rejected_transaction_record";
+        let transaction_hash_list = [
+            RawTransactionHash::from([1; 32]),
+            RawTransactionHash::from([2; 32]),
+        ];
+
+        let error = RejectedTransaction::record(
+            rollup_id,
+            &transaction_hash_list,
+            Error::EmptyTransactionList,
+        );
+
+        assert!(matches!(error, Error::EmptyTransactionList));
+        for transaction_hash in &transaction_hash_list {
+            assert_eq!(
+                RejectedTransaction::get(rollup_id, transaction_hash.as_ref())
+                    .unwrap()
+                    .reason,
+                Error::EmptyTransactionList.to_string()
+            );
+        }
+    }
+}
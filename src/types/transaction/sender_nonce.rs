@@ -0,0 +1,247 @@
+use std::collections::btree_map::{BTreeMap, Entry};
+
+use ethers_core::types as eth_types;
+
+use crate::{error::Error, types::prelude::*};
+
+/// How the leader orders a transaction whose nonce is not the next one
+/// expected from its sender.
+///
+/// The sender and nonce of an encrypted transaction are taken from its open
+/// data. They are tracked again once it is decrypted at block build.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoncePolicy {
+    /// Orders the transaction regardless of its nonce.
+    #[default]
+    Accept,
+    /// Rejects a nonce below (duplicate) or above (gap) the expected one.
+    Reject,
+}
+
+/// Next nonce expected from a sender of a rollup.
+///
+/// Nothing is known about a sender before its first transaction reaches the
+/// sequencer, so the first nonce seen is accepted as is.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, sender: &str))]
+pub struct SenderNonce {
+    pub next_nonce: u64,
+}
+
+impl SenderNonce {
+    pub fn get_by_sender(
+        rollup_id: &str,
+        sender: &eth_types::Address,
+    ) -> Result<Option<Self>, KvStoreError> {
+        match Self::get(rollup_id, &const_hex::encode_prefixed(sender)) {
+            Ok(sender_nonce) => Ok(Some(sender_nonce)),
+            Err(error) if error.is_none_type() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Checks the `(sender, nonce)` of transactions ordered consecutively
+    /// against `nonce_policy` and, if every one passes, records the next
+    /// nonce of each sender. Nothing is recorded if one of them is rejected.
+    pub fn record(
+        rollup_id: &str,
+        nonce_policy: NoncePolicy,
+        sender_nonce_list: &[(eth_types::Address, u64)],
+    ) -> Result<(), Error> {
+        let next_nonce_map = Self::check(rollup_id, nonce_policy, sender_nonce_list)?;
+
+        Self::record_next_nonce(rollup_id, &next_nonce_map)
+    }
+
+    /// Checks the `(sender, nonce)` of transactions to be ordered
+    /// consecutively against `nonce_policy` and returns the next nonce of each
+    /// sender once they are. Nothing is recorded.
+    pub fn check(
+        rollup_id: &str,
+        nonce_policy: NoncePolicy,
+        sender_nonce_list: &[(eth_types::Address, u64)],
+    ) -> Result<BTreeMap<eth_types::Address, u64>, Error> {
+        let mut next_nonce_map = BTreeMap::<eth_types::Address, Option<u64>>::new();
+
+        for (sender, nonce) in sender_nonce_list {
+            let next_nonce = match next_nonce_map.entry(*sender) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    Self::get_by_sender(rollup_id, sender)
+                        .map_err(Error::Database)?
+                        .map(|sender_nonce| sender_nonce.next_nonce),
+                ),
+            };
+
+            if let (NoncePolicy::Reject, Some(expected_nonce)) = (nonce_policy, *next_nonce) {
+                if *nonce < expected_nonce {
+                    return Err(Error::DuplicateNonce);
+                }
+                if *nonce > expected_nonce {
+                    return Err(Error::NonceGap);
+                }
+            }
+
+            let following_nonce = nonce.checked_add(1).ok_or(Error::InvalidTransaction)?;
+            *next_nonce = Some(next_nonce.map_or(following_nonce, |next_nonce| {
+                next_nonce.max(following_nonce)
+            }));
+        }
+
+        Ok(next_nonce_map
+            .into_iter()
+            .map(|(sender, next_nonce)| (sender, next_nonce.unwrap_or_default()))
+            .collect())
+    }
+
+    /// Records the next nonces returned by [`Self::check`] once the
+    /// transactions are stored.
+    pub fn record_next_nonce(
+        rollup_id: &str,
+        next_nonce_map: &BTreeMap<eth_types::Address, u64>,
+    ) -> Result<(), Error> {
+        for (sender, next_nonce) in next_nonce_map {
+            Self {
+                next_nonce: *next_nonce,
+            }
+            .put(rollup_id, &const_hex::encode_prefixed(sender))
+            .map_err(Error::Database)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the nonces of transactions that are already ordered, either by
+    /// the leader or as decrypted at block build, so that they stay tracked if
+    /// this sequencer takes over as the leader. Errors are only logged.
+    pub fn follow(
+        rollup_id: &str,
+        sender_nonce_list: Result<Vec<(eth_types::Address, u64)>, Error>,
+    ) {
+        if let Err(error) = sender_nonce_list.and_then(|sender_nonce_list| {
+            Self::record(rollup_id, NoncePolicy::Accept, &sender_nonce_list)
+        }) {
+            tracing::warn!(
+                "Failed to record sender nonces - rollup_id: {:?} / error: {:?}",
+                rollup_id,
+                error
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    fn sender(byte: u8) -> eth_types::Address {
+        eth_types::Address::repeat_byte(byte)
+    }
+
+    fn next_nonce(rollup_id: &str, byte: u8) -> Option<u64> {
+        SenderNonce::get_by_sender(rollup_id, &sender(byte))
+            .unwrap()
+            .map(|sender_nonce| sender_nonce.next_nonce)
+    }
+
+    #[test]
+    fn first_nonce_is_accepted() {
+        init_test_database();
+        let rollup_id = "sender_nonce_first_nonce";
+
+        SenderNonce::record(rollup_id, NoncePolicy::Reject, &[(sender(1), 7)]).unwrap();
+
+        assert_eq!(next_nonce(rollup_id, 1), Some(8));
+    }
+
+    #[test]
+    fn consecutive_nonces_of_a_sender_are_accepted() {
+        init_test_database();
+        let rollup_id = "sender_nonce_consecutive";
+
+        SenderNonce::record(
+            rollup_id,
+            NoncePolicy::Reject,
+            &[(sender(1), 0), (sender(2), 4), (sender(1), 1)],
+        )
+        .unwrap();
+
+        assert_eq!(next_nonce(rollup_id, 1), Some(2));
+        assert_eq!(next_nonce(rollup_id, 2), Some(5));
+    }
+
+    #[test]
+    fn reject_policy_rejects_duplicates_and_gaps() {
+        init_test_database();
+        let rollup_id = "sender_nonce_reject";
+        SenderNonce::record(rollup_id, NoncePolicy::Reject, &[(sender(1), 5)]).unwrap();
+
+        let duplicate_nonce =
+            SenderNonce::record(rollup_id, NoncePolicy::Reject, &[(sender(1), 5)]);
+        let nonce_gap = SenderNonce::record(rollup_id, NoncePolicy::Reject, &[(sender(1), 7)]);
+
+        assert!(matches!(duplicate_nonce, Err(Error::DuplicateNonce)));
+        assert!(matches!(nonce_gap, Err(Error::NonceGap)));
+        assert_eq!(next_nonce(rollup_id, 1), Some(6));
+    }
+
+    #[test]
+    fn rejected_batch_records_nothing() {
+        init_test_database();
+        let rollup_id = "sender_nonce_rejected_batch";
+        SenderNonce::record(rollup_id, NoncePolicy::Reject, &[(sender(1), 0)]).unwrap();
+
+        let sender_nonce = SenderNonce::record(
+            rollup_id,
+            NoncePolicy::Reject,
+            &[(sender(2), 0), (sender(1), 1), (sender(1), 1)],
+        );
+
+        assert!(matches!(sender_nonce, Err(Error::DuplicateNonce)));
+        assert_eq!(next_nonce(rollup_id, 1), Some(1));
+        assert_eq!(next_nonce(rollup_id, 2), None);
+    }
+
+    #[test]
+    fn checked_nonces_are_recorded_only_once_stored() {
+        init_test_database();
+        let rollup_id = "sender_nonce_check";
+
+        let next_nonce_map = SenderNonce::check(
+            rollup_id,
+            NoncePolicy::Reject,
+            &[(sender(1), 2), (sender(1), 3)],
+        )
+        .unwrap();
+        assert_eq!(next_nonce(rollup_id, 1), None);
+
+        SenderNonce::record_next_nonce(rollup_id, &next_nonce_map).unwrap();
+        assert_eq!(next_nonce(rollup_id, 1), Some(4));
+    }
+
+    #[test]
+    fn accept_policy_keeps_the_highest_next_nonce() {
+        init_test_database();
+        let rollup_id = "sender_nonce_accept";
+
+        SenderNonce::record(rollup_id, NoncePolicy::Accept, &[(sender(1), 9)]).unwrap();
+        SenderNonce::record(rollup_id, NoncePolicy::Accept, &[(sender(1), 3)]).unwrap();
+        SenderNonce::record(rollup_id, NoncePolicy::Accept, &[(sender(1), 12)]).unwrap();
+
+        assert_eq!(next_nonce(rollup_id, 1), Some(13));
+    }
+
+    #[test]
+    fn nonce_overflow_is_rejected() {
+        init_test_database();
+        let rollup_id = "sender_nonce_overflow";
+
+        let sender_nonce =
+            SenderNonce::record(rollup_id, NoncePolicy::Accept, &[(sender(1), u64::MAX)]);
+
+        assert!(matches!(sender_nonce, Err(Error::InvalidTransaction)));
+        assert_eq!(next_nonce(rollup_id, 1), None);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::types::prelude::*;
+
+/// Where a transaction was ordered, used to answer `get_transaction_status`.
+///
+/// Recorded along with the [`TransactionOrderIndex`], but kept once the index
+/// is pruned after `duplicate_transaction_retention` rollup blocks.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, transaction_kind: TransactionKind, transaction_hash: &str))]
+pub struct TransactionLifecycle {
+    pub rollup_block_height: u64,
+    pub transaction_order: u64,
+
+    /// Whether the leader sent the transaction to the followers, or this
+    /// follower received it.
+    #[serde(default)]
+    pub is_synced: bool,
+}
@@ -0,0 +1,293 @@
+use crate::types::prelude::*;
+
+/// Namespace a transaction hash is indexed under.
+///
+/// The hash of an encrypted transaction is the `raw_tx_hash` of its open
+/// data, which is not checked before decryption. Keeping it apart from the
+/// hashes of raw transactions prevents an encrypted transaction claiming the
+/// hash of someone else's raw transaction from taking over its order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    Raw,
+    Encrypted,
+}
+
+/// Where a transaction was ordered, used by the leader to answer a
+/// resubmitted transaction with its existing order commitment.
+///
+/// Recorded once the order commitment is stored, and pruned when the block
+/// falls out of the `duplicate_transaction_retention` window.
+#[derive(Clone, Debug, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, transaction_kind: TransactionKind, transaction_hash: &str))]
+pub struct TransactionOrderIndex {
+    pub rollup_block_height: u64,
+    pub transaction_order: u64,
+
+    /// Whether the leader sent the transaction to the followers, or this
+    /// follower received it.
+    #[serde(default)]
+    pub is_synced: bool,
+}
+
+impl TransactionOrderIndex {
+    /// Also records the [`TransactionLifecycle`] of the transaction.
+    pub fn record(
+        rollup_id: &str,
+        transaction_kind: TransactionKind,
+        transaction_hash: &RawTransactionHash,
+        rollup_block_height: u64,
+        transaction_order: u64,
+        is_synced: bool,
+    ) -> Result<(), KvStoreError> {
+        Self {
+            rollup_block_height,
+            transaction_order,
+            is_synced,
+        }
+        .put(rollup_id, transaction_kind, transaction_hash.as_ref())?;
+
+        TransactionLifecycle {
+            rollup_block_height,
+            transaction_order,
+            is_synced,
+        }
+        .put(rollup_id, transaction_kind, transaction_hash.as_ref())
+    }
+
+    /// Marks transactions the leader sent to the followers. Errors are only
+    /// logged as the transactions are already synced.
+    pub fn mark_synced(
+        rollup_id: &str,
+        transaction_kind: TransactionKind,
+        transaction_hash_list: &[RawTransactionHash],
+    ) {
+        for transaction_hash in transaction_hash_list {
+            let result = Self::get_mut(rollup_id, transaction_kind, transaction_hash.as_ref())
+                .and_then(|mut transaction_order_index| {
+                    transaction_order_index.is_synced = true;
+                    transaction_order_index.update()
+                })
+                .and_then(|_| {
+                    let mut transaction_lifecycle = TransactionLifecycle::get_mut(
+                        rollup_id,
+                        transaction_kind,
+                        transaction_hash.as_ref(),
+                    )?;
+                    transaction_lifecycle.is_synced = true;
+                    transaction_lifecycle.update()
+                });
+
+            if let Err(error) = result {
+                tracing::warn!(
+                    "Failed to mark transaction as synced - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+                    rollup_id,
+                    transaction_hash,
+                    error
+                );
+            }
+        }
+    }
+
+    /// Returns where the transaction was ordered if it was within the last
+    /// `retention` rollup blocks up to `rollup_block_height`.
+    pub fn get_within(
+        rollup_id: &str,
+        transaction_kind: TransactionKind,
+        transaction_hash: &RawTransactionHash,
+        rollup_block_height: u64,
+        retention: u64,
+    ) -> Result<Option<Self>, KvStoreError> {
+        match Self::get(rollup_id, transaction_kind, transaction_hash.as_ref()) {
+            Ok(transaction_order_index)
+                if transaction_order_index
+                    .rollup_block_height
+                    .saturating_add(retention)
+                    > rollup_block_height =>
+            {
+                Ok(Some(transaction_order_index))
+            }
+            Ok(_) => Ok(None),
+            Err(error) if error.is_none_type() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Removes the entries of the transactions of `block` that still point
+    /// at it. Errors are only logged as the entries are expired anyway.
+    pub fn prune(rollup_id: &str, block: &Block) {
+        let transaction_hash_list = match block.merkle_leaf_list() {
+            Ok(transaction_hash_list) => transaction_hash_list,
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to prune transaction order index - rollup_id: {:?} / rollup_block_height: {:?} / error: {:?}",
+                    rollup_id,
+                    block.block_height,
+                    error
+                );
+                return;
+            }
+        };
+
+        for (transaction_order, transaction_hash) in transaction_hash_list.iter().enumerate() {
+            let transaction_kind = match block.encrypted_transaction_list.get(transaction_order) {
+                Some(Some(_)) => TransactionKind::Encrypted,
+                _ => TransactionKind::Raw,
+            };
+
+            let result = match Self::get(rollup_id, transaction_kind, transaction_hash.as_ref()) {
+                // The transaction was ordered again in a later block.
+                Ok(transaction_order_index)
+                    if transaction_order_index.rollup_block_height != block.block_height =>
+                {
+                    Ok(())
+                }
+                Ok(_) => Self::delete(rollup_id, transaction_kind, transaction_hash.as_ref()),
+                Err(error) if error.is_none_type() => Ok(()),
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = result {
+                tracing::warn!(
+                    "Failed to prune transaction order index - rollup_id: {:?} / transaction_hash: {:?} / error: {:?}",
+                    rollup_id,
+                    transaction_hash,
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::init_test_database;
+
+    fn transaction_hash(byte: u8) -> RawTransactionHash {
+        RawTransactionHash::from([byte; 32])
+    }
+
+    #[test]
+    fn transaction_kinds_are_kept_apart() {
+        init_test_database();
+        let rollup_id = "transaction_order_index_kind";
+        TransactionOrderIndex::record(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash(1),
+            10,
+            3,
+            false,
+        )
+        .unwrap();
+
+        let raw_transaction_order_index = TransactionOrderIndex::get_within(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash(1),
+            10,
+            1,
+        )
+        .unwrap();
+        let encrypted_transaction_order_index = TransactionOrderIndex::get_within(
+            rollup_id,
+            TransactionKind::Encrypted,
+            &transaction_hash(1),
+            10,
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(
+            raw_transaction_order_index
+                .map(|transaction_order_index| transaction_order_index.transaction_order),
+            Some(3)
+        );
+        assert!(encrypted_transaction_order_index.is_none());
+    }
+
+    #[test]
+    fn entries_expire_after_the_retention() {
+        init_test_database();
+        let rollup_id = "transaction_order_index_retention";
+        TransactionOrderIndex::record(
+            rollup_id,
+            TransactionKind::Raw,
+            &transaction_hash(1),
+            10,
+            0,
+            false,
+        )
+        .unwrap();
+
+        let get_within = |rollup_block_height, retention| {
+            TransactionOrderIndex::get_within(
+                rollup_id,
+                TransactionKind::Raw,
+                &transaction_hash(1),
+                rollup_block_height,
+                retention,
+            )
+            .unwrap()
+            .is_some()
+        };
+
+        assert!(get_within(10, 1));
+        assert!(get_within(14, 5));
+        assert!(!get_within(15, 5));
+        assert!(!get_within(10, 0));
+        assert!(get_within(u64::MAX - 1, u64::MAX));
+    }
+
+    #[test]
+    fn unknown_transaction_is_not_a_duplicate() {
+        init_test_database();
+
+        let transaction_order_index = TransactionOrderIndex::get_within(
+            "transaction_order_index_unknown",
+            TransactionKind::Encrypted,
+            &transaction_hash(2),
+            0,
+            10,
+        )
+        .unwrap();
+
+        assert!(transaction_order_index.is_none());
+    }
+
+    #[test]
+    fn synced_transactions_are_marked() {
+        init_test_database();
+        let rollup_id = "transaction_order_index_synced";
+        for byte in [1, 2] {
+            TransactionOrderIndex::record(
+                rollup_id,
+                TransactionKind::Encrypted,
+                &transaction_hash(byte),
+                1,
+                byte as u64,
+                false,
+            )
+            .unwrap();
+        }
+
+        TransactionOrderIndex::mark_synced(
+            rollup_id,
+            TransactionKind::Encrypted,
+            &[transaction_hash(1)],
+        );
+
+        let is_synced = |byte| {
+            TransactionOrderIndex::get(
+                rollup_id,
+                TransactionKind::Encrypted,
+                transaction_hash(byte).as_ref(),
+            )
+            .unwrap()
+            .is_synced
+        };
+        assert!(is_synced(1));
+        assert!(!is_synced(2));
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeSet;
+
+use crate::types::prelude::*;
+
+/// Transaction orders a follower holds for a rollup block, used to detect
+/// the ones it missed from the leader's sync messages.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
+#[kvstore(key(rollup_id: &str, rollup_block_height: u64))]
+pub struct TransactionOrderStatus {
+    /// Every order below this one is stored locally.
+    pub contiguous_transaction_count: u64,
+    /// One past the highest order known to exist, either stored locally or
+    /// reported by the leader.
+    pub transaction_count: u64,
+    /// Orders stored locally above the contiguous ones.
+    received_transaction_order_set: BTreeSet<u64>,
+}
+
+impl TransactionOrderStatus {
+    pub fn insert(&mut self, transaction_order: u64) {
+        self.transaction_count = self.transaction_count.max(transaction_order + 1);
+
+        if transaction_order < self.contiguous_transaction_count {
+            return;
+        }
+
+        self.received_transaction_order_set
+            .insert(transaction_order);
+        while self
+            .received_transaction_order_set
+            .remove(&self.contiguous_transaction_count)
+        {
+            self.contiguous_transaction_count += 1;
+        }
+    }
+
+    pub fn update_transaction_count(&mut self, transaction_count: u64) {
+        self.transaction_count = self.transaction_count.max(transaction_count);
+    }
+
+    pub fn missing_transaction_count(&self) -> u64 {
+        self.transaction_count
+            - self.contiguous_transaction_count
+            - self.received_transaction_order_set.len() as u64
+    }
+
+    /// Returns the ranges of missing orders as `(start, end)` with `end`
+    /// exclusive, each at most `max_range_length` long.
+    pub fn missing_transaction_order_range_list(&self, max_range_length: u64) -> Vec<(u64, u64)> {
+        let mut range_list = Vec::new();
+
+        let mut start = self.contiguous_transaction_count;
+        for &received_transaction_order in self
+            .received_transaction_order_set
+            .iter()
+            .chain(std::iter::once(&self.transaction_count))
+        {
+            while start < received_transaction_order {
+                let end = received_transaction_order.min(start + max_range_length);
+                range_list.push((start, end));
+                start = end;
+            }
+            start = received_transaction_order + 1;
+        }
+
+        range_list
+    }
+
+    /// Records the stored transaction orders of a rollup block.
+    pub fn record(
+        rollup_id: &str,
+        rollup_block_height: u64,
+        transaction_order_list: impl IntoIterator<Item = u64>,
+    ) -> Result<(), KvStoreError> {
+        let mut transaction_order_status =
+            Self::get_mut_or(rollup_id, rollup_block_height, Self::default)?;
+        for transaction_order in transaction_order_list {
+            transaction_order_status.insert(transaction_order);
+        }
+        transaction_order_status.update()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_order_status(transaction_order_list: &[u64]) -> TransactionOrderStatus {
+        let mut transaction_order_status = TransactionOrderStatus::default();
+        for transaction_order in transaction_order_list {
+            transaction_order_status.insert(*transaction_order);
+        }
+
+        transaction_order_status
+    }
+
+    #[test]
+    fn contiguous_orders_advance_in_any_arrival_order() {
+        let transaction_order_status = transaction_order_status(&[2, 0, 1, 1, 0]);
+
+        assert_eq!(transaction_order_status.contiguous_transaction_count, 3);
+        assert_eq!(transaction_order_status.transaction_count, 3);
+        assert_eq!(transaction_order_status.missing_transaction_count(), 0);
+        assert!(transaction_order_status
+            .missing_transaction_order_range_list(10)
+            .is_empty());
+    }
+
+    #[test]
+    fn gaps_are_reported_as_ranges() {
+        let mut transaction_order_status = transaction_order_status(&[0, 1, 4, 7]);
+        transaction_order_status.update_transaction_count(10);
+
+        assert_eq!(transaction_order_status.contiguous_transaction_count, 2);
+        assert_eq!(transaction_order_status.missing_transaction_count(), 6);
+        assert_eq!(
+            transaction_order_status.missing_transaction_order_range_list(10),
+            [(2, 4), (5, 7), (8, 10)]
+        );
+    }
+
+    #[test]
+    fn ranges_are_split_at_the_maximum_length() {
+        let mut transaction_order_status = transaction_order_status(&[5]);
+        transaction_order_status.update_transaction_count(8);
+
+        assert_eq!(
+            transaction_order_status.missing_transaction_order_range_list(2),
+            [(0, 2), (2, 4), (4, 5), (6, 8)]
+        );
+    }
+
+    #[test]
+    fn transaction_count_never_decreases() {
+        let mut transaction_order_status = transaction_order_status(&[0, 3]);
+        transaction_order_status.update_transaction_count(2);
+
+        assert_eq!(transaction_order_status.transaction_count, 4);
+        assert_eq!(transaction_order_status.missing_transaction_count(), 2);
+    }
+}
@@ -0,0 +1,366 @@
+//! Checks a user can run against an issued [`SignOrderCommitment`] to
+//! challenge a sequencer that reorders or drops the committed transaction.
+
+use radius_sdk::signature::{Address, ChainType, Signature};
+
+use crate::{error::Error, types::*};
+
+/// Returns the address in `candidate_address_list` that signed the order
+/// commitment.
+pub fn recover_order_commitment_signer<'a>(
+    chain_type: ChainType,
+    signature_scheme: &SignatureScheme,
+    order_commitment: &SignOrderCommitment,
+    candidate_address_list: impl IntoIterator<Item = &'a Address>,
+) -> Result<Address, Error> {
+    let signature: Signature = serde_json::from_value(serde_json::Value::String(
+        order_commitment.signature.clone(),
+    ))
+    .map_err(|_| Error::InvalidSignature)?;
+
+    candidate_address_list
+        .into_iter()
+        .find(|address| {
+            signature_scheme
+                .verify(chain_type, &signature, &order_commitment.data, address)
+                .is_ok()
+        })
+        .cloned()
+        .ok_or(Error::InvalidSignature)
+}
+
+/// Checks that the order commitment was issued for `transaction_hash` at the
+/// given order and, if it is signed, that a leader of the block signed it.
+pub fn verify_leader_order_commitment(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    transaction_order: u64,
+    transaction_hash: &RawTransactionHash,
+    order_commitment: &OrderCommitment,
+) -> Result<(), Error> {
+    match order_commitment {
+        OrderCommitment::Single(SingleOrderCommitment::TransactionHash(
+            transaction_hash_order_commitment,
+        )) => {
+            if transaction_hash_order_commitment.as_str() != AsRef::<str>::as_ref(transaction_hash)
+            {
+                return Err(Error::InvalidOrderCommitment);
+            }
+
+            Ok(())
+        }
+        OrderCommitment::Single(SingleOrderCommitment::Sign(sign_order_commitment)) => {
+            if sign_order_commitment.data.transaction_hash != AsRef::<str>::as_ref(transaction_hash)
+            {
+                return Err(Error::InvalidOrderCommitment);
+            }
+
+            verify_leader_sign_order_commitment(
+                rollup,
+                rollup_block_height,
+                transaction_order,
+                sign_order_commitment,
+            )
+        }
+        // Only the transaction batch RPCs return a bundle, each order is
+        // stored with its own commitment.
+        OrderCommitment::Bundle(_) => Err(Error::InvalidOrderCommitment),
+    }
+}
+
+/// Checks that the signed order commitment is for the given order and that a
+/// leader of the block signed it.
+pub fn verify_leader_sign_order_commitment(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    transaction_order: u64,
+    sign_order_commitment: &SignOrderCommitment,
+) -> Result<(), Error> {
+    let order_commitment_data = &sign_order_commitment.data;
+    if order_commitment_data.rollup_id != rollup.rollup_id
+        || order_commitment_data.block_height != rollup_block_height
+        || order_commitment_data.transaction_order != transaction_order
+    {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    let signature_scheme = rollup
+        .get_signature_scheme(rollup_block_height)
+        .map_err(Error::Database)?;
+    if order_commitment_data.signature_scheme != signature_scheme {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    let block_leader =
+        BlockLeader::get_or(&rollup.rollup_id, rollup_block_height, BlockLeader::default)
+            .map_err(Error::Database)?;
+
+    recover_order_commitment_signer(
+        rollup.platform.into(),
+        &signature_scheme,
+        sign_order_commitment,
+        block_leader.leader_address_list.iter(),
+    )
+    .map(|_| ())
+}
+
+/// Checks that the bundle order commitment of a synced batch commits to the
+/// transactions of the batch in order and that a leader of the block signed
+/// it.
+pub fn verify_leader_bundle_order_commitment(
+    rollup: &Rollup,
+    rollup_block_height: u64,
+    first_transaction_order: u64,
+    transaction_hash_list: &[RawTransactionHash],
+    bundle_order_commitment: &BundleOrderCommitment,
+) -> Result<(), Error> {
+    let order_commitment_list = &bundle_order_commitment.order_commitment_list;
+    if order_commitment_list.len() != transaction_hash_list.len()
+        || order_commitment_list.iter().zip(transaction_hash_list).any(
+            |(order_commitment, transaction_hash)| {
+                order_commitment.transaction_hash() != AsRef::<str>::as_ref(transaction_hash)
+            },
+        )
+    {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    let signature_scheme = rollup
+        .get_signature_scheme(rollup_block_height)
+        .map_err(Error::Database)?;
+    let block_leader =
+        BlockLeader::get_or(&rollup.rollup_id, rollup_block_height, BlockLeader::default)
+            .map_err(Error::Database)?;
+    let message = bundle_order_commitment.message(
+        &rollup.rollup_id,
+        rollup_block_height,
+        first_transaction_order,
+    );
+
+    block_leader
+        .leader_address_list
+        .iter()
+        .find(|address| {
+            signature_scheme
+                .verify(
+                    rollup.platform.into(),
+                    &bundle_order_commitment.signature,
+                    &message,
+                    address,
+                )
+                .is_ok()
+        })
+        .map(|_| ())
+        .ok_or(Error::InvalidSignature)
+}
+
+/// Rebuilds the block commitment from the pre-Merkle path of the order
+/// commitment, the committed transaction and the leaves that follow it.
+pub async fn compute_block_commitment(
+    order_commitment_data: &OrderCommitmentData,
+    later_transaction_hash_list: &[RawTransactionHash],
+    merkle_root_version: MerkleRootVersion,
+) -> Result<[u8; 32], Error> {
+    let merkle_tree = MerkleTree::from_pre_merkle_path(
+        order_commitment_data.transaction_order,
+        &order_commitment_data.pre_merkle_path,
+        &order_commitment_data.transaction_hash,
+        order_commitment_data.merkle_hash_scheme,
+    )
+    .ok_or(Error::InvalidOrderCommitment)?;
+
+    for transaction_hash in later_transaction_hash_list {
+        merkle_tree.add_data(transaction_hash.as_ref()).await;
+    }
+    merkle_tree.finalize_tree(merkle_root_version).await;
+
+    Ok(merkle_tree.get_merkle_root().await)
+}
+
+/// Checks that the block has the committed transaction at the committed
+/// order and that the pre-Merkle path reproduces its block commitment.
+pub async fn verify_block_inclusion(
+    order_commitment_data: &OrderCommitmentData,
+    transaction_hash_list: &[RawTransactionHash],
+    block_commitment: &BlockCommitment,
+    merkle_root_version: MerkleRootVersion,
+) -> Result<(), Error> {
+    let transaction_order = order_commitment_data.transaction_order as usize;
+    let is_committed_transaction =
+        transaction_hash_list
+            .get(transaction_order)
+            .is_some_and(|transaction_hash| {
+                AsRef::<str>::as_ref(transaction_hash) == order_commitment_data.transaction_hash
+            });
+    if !is_committed_transaction {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    let computed_block_commitment = compute_block_commitment(
+        order_commitment_data,
+        &transaction_hash_list[transaction_order + 1..],
+        merkle_root_version,
+    )
+    .await?;
+
+    let block_commitment = block_commitment
+        .as_bytes()
+        .map_err(|_| Error::InvalidOrderCommitment)?;
+    if block_commitment != computed_block_commitment {
+        return Err(Error::InvalidOrderCommitment);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_hash_list() -> Vec<RawTransactionHash> {
+        (1..=4)
+            .map(|byte| RawTransactionHash::from([byte; 32]))
+            .collect()
+    }
+
+    /// Returns the order commitment data of `transaction_order` and the
+    /// block commitment of the block built from `transaction_hash_list`.
+    async fn build_block(
+        transaction_hash_list: &[RawTransactionHash],
+        transaction_order: usize,
+        merkle_root_version: MerkleRootVersion,
+    ) -> (OrderCommitmentData, BlockCommitment) {
+        let merkle_tree = MerkleTree::new();
+        let mut pre_merkle_path_list = Vec::new();
+        for transaction_hash in transaction_hash_list {
+            pre_merkle_path_list.push(merkle_tree.add_data(transaction_hash.as_ref()).await.1);
+        }
+        merkle_tree.finalize_tree(merkle_root_version).await;
+
+        let order_commitment_data = OrderCommitmentData {
+            rollup_id: "rollup_id".to_owned(),
+            transaction_order: transaction_order as u64,
+            transaction_hash: transaction_hash_list[transaction_order].clone().as_string(),
+            pre_merkle_path: pre_merkle_path_list.swap_remove(transaction_order),
+            ..Default::default()
+        };
+
+        (
+            order_commitment_data,
+            BlockCommitment::from(merkle_tree.get_merkle_root().await),
+        )
+    }
+
+    #[tokio::test]
+    async fn committed_transaction_is_included() {
+        let transaction_hash_list = transaction_hash_list();
+
+        for transaction_order in 0..transaction_hash_list.len() {
+            let (order_commitment_data, block_commitment) = build_block(
+                &transaction_hash_list,
+                transaction_order,
+                MerkleRootVersion::V1,
+            )
+            .await;
+
+            assert!(verify_block_inclusion(
+                &order_commitment_data,
+                &transaction_hash_list,
+                &block_commitment,
+                MerkleRootVersion::V1,
+            )
+            .await
+            .is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_at_another_order_is_not_included() {
+        let transaction_hash_list = transaction_hash_list();
+        let (order_commitment_data, block_commitment) =
+            build_block(&transaction_hash_list, 1, MerkleRootVersion::V1).await;
+
+        let mut reordered_transaction_hash_list = transaction_hash_list.clone();
+        reordered_transaction_hash_list.swap(1, 2);
+        let dropped_transaction_hash_list = transaction_hash_list[..1].to_vec();
+
+        for transaction_hash_list in [
+            reordered_transaction_hash_list,
+            dropped_transaction_hash_list,
+        ] {
+            assert!(matches!(
+                verify_block_inclusion(
+                    &order_commitment_data,
+                    &transaction_hash_list,
+                    &block_commitment,
+                    MerkleRootVersion::V1,
+                )
+                .await,
+                Err(Error::InvalidOrderCommitment)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn block_of_other_later_transactions_is_rejected() {
+        let transaction_hash_list = transaction_hash_list();
+        let (order_commitment_data, block_commitment) =
+            build_block(&transaction_hash_list, 1, MerkleRootVersion::V1).await;
+
+        let mut other_transaction_hash_list = transaction_hash_list.clone();
+        other_transaction_hash_list[3] = RawTransactionHash::from([9u8; 32]);
+
+        assert!(matches!(
+            verify_block_inclusion(
+                &order_commitment_data,
+                &other_transaction_hash_list,
+                &block_commitment,
+                MerkleRootVersion::V1,
+            )
+            .await,
+            Err(Error::InvalidOrderCommitment)
+        ));
+    }
+
+    #[tokio::test]
+    async fn block_commitment_is_rebuilt_with_the_block_merkle_root_version() {
+        let transaction_hash_list = transaction_hash_list();
+        let (order_commitment_data, block_commitment) =
+            build_block(&transaction_hash_list, 0, MerkleRootVersion::V0).await;
+
+        assert!(verify_block_inclusion(
+            &order_commitment_data,
+            &transaction_hash_list,
+            &block_commitment,
+            MerkleRootVersion::V0,
+        )
+        .await
+        .is_ok());
+        assert!(verify_block_inclusion(
+            &order_commitment_data,
+            &transaction_hash_list,
+            &block_commitment,
+            MerkleRootVersion::V1,
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn pre_merkle_path_of_another_order_is_rejected() {
+        let transaction_hash_list = transaction_hash_list();
+        let (mut order_commitment_data, _) =
+            build_block(&transaction_hash_list, 2, MerkleRootVersion::V1).await;
+        order_commitment_data.pre_merkle_path.push([0u8; 32]);
+
+        assert!(matches!(
+            compute_block_commitment(
+                &order_commitment_data,
+                &transaction_hash_list[3..],
+                MerkleRootVersion::V1,
+            )
+            .await,
+            Err(Error::InvalidOrderCommitment)
+        ));
+    }
+}